@@ -1,7 +1,10 @@
 use heck::{ToLowerCamelCase, ToShoutySnakeCase, ToSnakeCase, ToUpperCamelCase};
+#[cfg(feature = "serde")]
+use serde::Deserialize;
 
 /// Style of indentation used in generated C code
 #[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Deserialize))]
 pub enum CIndentationStyle {
     // Braces on their own lines, not indented
     Allman,
@@ -15,6 +18,7 @@ pub enum CIndentationStyle {
 
 /// Style of documentation in generated C code
 #[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Deserialize))]
 pub enum CNamingStyle {
     /// Names all in lowercase without spacing e.g. 'thetypename'
     Lowercase,
@@ -55,6 +59,7 @@ impl ToNamingStyle for &str {
 
 /// Style of documentation in generated C code
 #[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Deserialize))]
 pub enum CDocumentationStyle {
     // No documentation comments are added to header file
     None,
@@ -64,6 +69,7 @@ pub enum CDocumentationStyle {
 
 /// Configures C code generation.
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Deserialize), serde(default))]
 pub struct Config {
     /// Whether to write conditional directives like `#ifndef _X`.
     pub directives: bool,
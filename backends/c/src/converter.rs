@@ -1,10 +1,33 @@
 use crate::config::ToNamingStyle;
 use crate::Config;
-use interoptopus::lang::c::{CType, CompositeType, Constant, ConstantValue, EnumType, FnPointerType, Function, OpaqueType, PrimitiveType, PrimitiveValue, Variant};
+use interoptopus::lang::c::{
+    CType, CompositeType, Constant, ConstantValue, EnumType, FnPointerType, Function, OpaqueType, PrimitiveType, PrimitiveValue, TaggedUnionType, UnionType, Variant,
+};
 use interoptopus::patterns::callbacks::NamedCallback;
 use interoptopus::patterns::TypePattern;
 use interoptopus::util::safe_name;
 
+/// Turns `x` into a C string literal; non-ASCII characters are written as octal UTF-8 bytes.
+fn string_literal(x: &str) -> String {
+    let mut rval = String::from("\"");
+
+    for byte in x.bytes() {
+        match byte {
+            b'"' => rval.push_str("\\\""),
+            b'\\' => rval.push_str("\\\\"),
+            b'\n' => rval.push_str("\\n"),
+            b'\r' => rval.push_str("\\r"),
+            b'\t' => rval.push_str("\\t"),
+            b' '..=b'~' => rval.push(byte as char),
+            // Octal escapes end after 3 digits, unlike `\x` which would swallow following hex digits.
+            _ => rval.push_str(&format!("\\{:03o}", byte)),
+        }
+    }
+
+    rval.push('"');
+    rval
+}
+
 /// Implements [`CTypeConverter`].
 #[derive(Clone)]
 pub struct Converter {
@@ -29,6 +52,17 @@ pub trait CTypeConverter {
     /// Converts an Rust struct name `Vec2` to a C# struct name `Vec2`.
     fn composite_to_typename(&self, x: &CompositeType) -> String;
 
+    /// Converts a Rust union name `Bits` to a C union name `Bits`.
+    fn union_to_typename(&self, x: &UnionType) -> String;
+
+    /// Converts a Rust data-carrying enum `Shape` to a C type name `shape`.
+    fn tagged_union_to_typename(&self, x: &TaggedUnionType) -> String;
+
+    /// Name of the union holding all payloads of a [`TagPlacement::Separate`](interoptopus::lang::c::TagPlacement::Separate) tagged union.
+    fn tagged_union_payload_to_typename(&self, x: &TaggedUnionType) -> String {
+        format!("{}{}Payload", self.config().prefix, x.rust_name()).to_naming_style(&self.config().type_naming)
+    }
+
     /// Converts an Rust `fn()` to a C# delegate name such as `InteropDelegate`.
     fn fnpointer_to_typename(&self, x: &FnPointerType) -> String;
 
@@ -63,6 +97,12 @@ impl CTypeConverter for Converter {
             PrimitiveType::I16 => "int16_t".to_string(),
             PrimitiveType::I32 => "int32_t".to_string(),
             PrimitiveType::I64 => "int64_t".to_string(),
+            // Not part of standard C, but supported by GCC and Clang.
+            PrimitiveType::U128 => "unsigned __int128".to_string(),
+            PrimitiveType::I128 => "__int128".to_string(),
+            PrimitiveType::USize => "size_t".to_string(),
+            PrimitiveType::ISize => "intptr_t".to_string(),
+            PrimitiveType::Char => "uint32_t".to_string(),
             PrimitiveType::F32 => "float".to_string(),
             PrimitiveType::F64 => "double".to_string(),
         }
@@ -90,6 +130,14 @@ impl CTypeConverter for Converter {
         format!("{}{}", self.config().prefix, x.rust_name()).to_naming_style(&self.config.type_naming)
     }
 
+    fn union_to_typename(&self, x: &UnionType) -> String {
+        format!("{}{}", self.config().prefix, x.rust_name()).to_naming_style(&self.config.type_naming)
+    }
+
+    fn tagged_union_to_typename(&self, x: &TaggedUnionType) -> String {
+        format!("{}{}", self.config().prefix, x.rust_name()).to_naming_style(&self.config.type_naming)
+    }
+
     fn fnpointer_to_typename(&self, x: &FnPointerType) -> String {
         let prefixed = format!("{}fptr", self.config().prefix);
         vec![prefixed, safe_name(&x.internal_name())].join("_")
@@ -101,6 +149,8 @@ impl CTypeConverter for Converter {
             CType::Enum(x) => self.enum_to_typename(x),
             CType::Opaque(x) => self.opaque_to_typename(x),
             CType::Composite(x) => self.composite_to_typename(x),
+            CType::Union(x) => self.union_to_typename(x),
+            CType::TaggedUnion(x) => self.tagged_union_to_typename(x),
             CType::ReadPointer(x) => format!("const {}*", self.to_type_specifier(x)),
             CType::ReadWritePointer(x) => format!("{}*", self.to_type_specifier(x)),
             CType::FnPointer(x) => self.fnpointer_to_typename(x),
@@ -123,14 +173,28 @@ impl CTypeConverter for Converter {
                 PrimitiveValue::U8(x) => format!("{}", x),
                 PrimitiveValue::U16(x) => format!("{}", x),
                 PrimitiveValue::U32(x) => format!("{}", x),
-                PrimitiveValue::U64(x) => format!("{}", x),
+                // Without a suffix, values above `i64::MAX` aren't valid `long long` literals.
+                PrimitiveValue::U64(x) => format!("{}ULL", x),
                 PrimitiveValue::I8(x) => format!("{}", x),
                 PrimitiveValue::I16(x) => format!("{}", x),
                 PrimitiveValue::I32(x) => format!("{}", x),
                 PrimitiveValue::I64(x) => format!("{}", x),
+                PrimitiveValue::U128(x) if *x <= u64::MAX as u128 => format!("{}ULL", x),
+                PrimitiveValue::I128(x) if *x >= i64::MIN as i128 && *x <= i64::MAX as i128 => format!("{}", x),
+                PrimitiveValue::U128(_) | PrimitiveValue::I128(_) => {
+                    panic!("C has no literals for 128-bit constants outside the 64-bit range, `validate()` reports these.")
+                }
+                PrimitiveValue::USize(x) => format!("{}", x),
+                PrimitiveValue::ISize(x) => format!("{}", x),
+                PrimitiveValue::Char(x) => format!("{}", *x as u32),
                 PrimitiveValue::F32(x) => format!("{}", x),
                 PrimitiveValue::F64(x) => format!("{}", x),
             },
+            ConstantValue::String(x) => string_literal(x),
+            ConstantValue::Array(_, values) | ConstantValue::Composite(_, values) => {
+                let values = values.iter().map(|x| self.constant_value_to_value(x)).collect::<Vec<_>>();
+                format!("{{ {} }}", values.join(", "))
+            }
         }
     }
 
@@ -40,6 +40,8 @@ impl<W: CWriter> DocGenerator<W> {
             CType::Enum(e) => e.meta(),
             CType::Opaque(o) => o.meta(),
             CType::Composite(c) => c.meta(),
+            CType::Union(u) => u.meta(),
+            CType::TaggedUnion(t) => t.meta(),
             CType::FnPointer(_) => return Ok(()),
             CType::ReadPointer(_) => return Ok(()),
             CType::ReadWritePointer(_) => return Ok(()),
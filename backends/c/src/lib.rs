@@ -90,6 +90,7 @@
 //! extern "C" {
 //! #endif
 //!
+//! #include <stddef.h>
 //! #include <stdint.h>
 //! #include <stdbool.h>
 //!
@@ -110,20 +111,22 @@
 //!
 //! ```
 
+use interoptopus::validate::Validation;
 use interoptopus::writer::IndentWriter;
 use interoptopus::Interop;
-use interoptopus::{Error, Inventory};
+use interoptopus::{Error, ErrorContext, Inventory};
 
 mod config;
 mod converter;
 mod docs;
 mod testing;
+mod validate;
 mod writer;
 
 pub use config::{CDocumentationStyle, CIndentationStyle, CNamingStyle, Config};
 pub use converter::{CTypeConverter, Converter};
 pub use docs::DocGenerator;
-pub use testing::compile_c_app_if_installed;
+pub use testing::{compile_c_app_if_installed, compile_c_app_with_library_if_installed, verify_layouts_if_installed};
 pub use writer::CWriter;
 
 /// **Start here**, main converter implementing [`Interop`].
@@ -145,7 +148,14 @@ impl Generator {
 
 impl Interop for Generator {
     fn write_to(&self, w: &mut IndentWriter) -> Result<(), Error> {
-        self.write_all(w)
+        self.validate()
+            .into_result()
+            .and_then(|_| self.write_all(w))
+            .map_err(|e| e.with_context(ErrorContext::Backend("C".to_string())))
+    }
+
+    fn validate(&self) -> Validation {
+        validate::validate(self)
     }
 }
 
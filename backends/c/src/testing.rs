@@ -1,6 +1,10 @@
 //! Test helpers for C bindings.
 
 use crate::Error;
+use crate::Generator;
+#[cfg(target_os = "linux")]
+use crate::{CTypeConverter, CWriter};
+use interoptopus::lang::rust::TypeLayout;
 use std::path::Path;
 
 /// Compile the given C app, ignore and succeed otherwise.
@@ -19,7 +23,237 @@ pub fn compile_c_app_if_installed<P: AsRef<Path>>(out_dir: P, app: &str) -> Resu
     Ok(())
 }
 
-#[cfg(not(all(target_os = "windows", feature = "cc")))]
+/// Compile the given C app to `out_dir` and run it, ignore and succeed if no C compiler is installed.
+///
+/// The app must not call into your library, see [`compile_c_app_with_library_if_installed`] for that.
+#[cfg(target_os = "linux")]
+pub fn compile_c_app_if_installed<P: AsRef<Path>>(out_dir: P, app: &str) -> Result<(), Error> {
+    compile_and_run_c_app(out_dir.as_ref(), app, None)
+}
+
+#[cfg(not(any(target_os = "linux", all(target_os = "windows", feature = "cc"))))]
 pub fn compile_c_app_if_installed<P: AsRef<Path>>(_out_dir: P, _app: &str) -> Result<(), Error> {
     Ok(())
 }
+
+/// Compile the given C app to `out_dir`, link it against `library` and run it, ignore and succeed if no C compiler is installed.
+///
+/// The app should include the generated header and exercise a few functions, returning non-zero from `main` if
+/// something is off. Compiler and app output are part of [`Error::TestFailed`]. The compiler is taken from `CC`,
+/// failing with [`Error::CommandNotFound`] if it can't be run, otherwise the first of `cc`, `gcc` and `clang` found.
+/// Only supported on Linux, a no-op elsewhere.
+#[cfg(target_os = "linux")]
+pub fn compile_c_app_with_library_if_installed<P: AsRef<Path>, L: AsRef<Path>>(out_dir: P, app: &str, library: L) -> Result<(), Error> {
+    compile_and_run_c_app(out_dir.as_ref(), app, Some(library.as_ref()))
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn compile_c_app_with_library_if_installed<P: AsRef<Path>, L: AsRef<Path>>(_out_dir: P, _app: &str, _library: L) -> Result<(), Error> {
+    Ok(())
+}
+
+#[cfg(target_os = "linux")]
+fn compile_and_run_c_app(out_dir: &Path, app: &str, library: Option<&Path>) -> Result<(), Error> {
+    use std::process::Command;
+
+    let compiler = match find_c_compiler()? {
+        Some(compiler) => compiler,
+        None => return Ok(()),
+    };
+
+    let app = Path::new(app);
+    let binary = out_dir.join(app.file_stem().ok_or(Error::FileNotFound)?);
+    let mut command = Command::new(&compiler);
+
+    command.arg("-std=c11").arg("-Wall").arg("-o").arg(&binary).arg(app);
+
+    if let Some(library) = library {
+        // Link the file itself, its name might not follow the `lib<name>.so` convention `-l` expects.
+        let library = std::fs::canonicalize(library).map_err(|_| Error::FileNotFound)?;
+        let directory = library.parent().ok_or(Error::FileNotFound)?;
+
+        command.arg(&library).arg(format!("-Wl,-rpath,{}", directory.display()));
+    }
+
+    std::fs::create_dir_all(out_dir)?;
+    let compiled = command.output()?;
+
+    if !compiled.status.success() {
+        return Err(Error::TestFailed {
+            command: format!("{} {}", compiler, app.display()),
+            output: String::from_utf8_lossy(&compiled.stderr).to_string(),
+        });
+    }
+
+    let ran = Command::new(&binary).output()?;
+
+    if !ran.status.success() {
+        let stdout = String::from_utf8_lossy(&ran.stdout);
+        let stderr = String::from_utf8_lossy(&ran.stderr);
+
+        return Err(Error::TestFailed {
+            command: binary.display().to_string(),
+            output: format!("{}\n{}{}", ran.status, stdout, stderr),
+        });
+    }
+
+    Ok(())
+}
+
+/// Checks every struct, union and enum of the inventory has the same layout in C as in Rust, if a C compiler is installed.
+///
+/// Writes and compiles a program to `out_dir` which includes `header` (as generated by `generator`) and prints
+/// `sizeof`, `alignof` and the `offsetof` every field for each of these types, including those behind patterns like
+/// [`FFISlice`](interoptopus::patterns::slice::FFISlice). Its output is then compared to the layouts `rustc`
+/// produced, which `rust` must contain for each type, usually obtained via [`LayoutInfo`](interoptopus::lang::rust::LayoutInfo):
+///
+/// ```no_run
+/// # use interoptopus::{ffi_type, Error, Inventory};
+/// # use interoptopus::lang::rust::LayoutInfo;
+/// # use interoptopus_backend_c::{verify_layouts_if_installed, Generator};
+/// # #[ffi_type]
+/// # #[repr(C)]
+/// # pub struct Vec2 { x: f32, y: f32 }
+/// # fn f(inventory: Inventory) -> Result<(), Error> {
+/// let generator = Generator::new(Default::default(), inventory);
+///
+/// verify_layouts_if_installed("target/layout/", &generator, "bindings/c/my_library.h", &[Vec2::layout_info()])?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// Mismatches are reported as part of [`Error::TestFailed`]. The compiler is taken from `CC`, failing with
+/// [`Error::CommandNotFound`] if it can't be run, otherwise the first of `cc`, `gcc` and `clang` found. Only
+/// supported on Linux, a no-op elsewhere.
+#[cfg(target_os = "linux")]
+pub fn verify_layouts_if_installed<P: AsRef<Path>>(out_dir: P, generator: &Generator, header: &str, rust: &[TypeLayout]) -> Result<(), Error> {
+    use interoptopus::lang::c::CType;
+    use std::collections::BTreeSet;
+    use std::fmt::Write;
+    use std::process::Command;
+
+    let compiler = match find_c_compiler()? {
+        Some(compiler) => compiler,
+        None => return Ok(()),
+    };
+
+    let out_dir = out_dir.as_ref();
+    let header = std::fs::canonicalize(header).map_err(|_| Error::FileNotFound)?;
+    let mut expected = BTreeSet::new();
+    let mut program = String::new();
+
+    writeln!(program, "#include <stddef.h>")?;
+    writeln!(program, "#include <stdio.h>")?;
+    writeln!(program, "#include \"{}\"", header.display())?;
+    writeln!(program)?;
+    writeln!(program, "int main(void) {{")?;
+
+    let mut missing = Vec::new();
+
+    for t in generator.inventory().ctypes() {
+        let resolved = match t {
+            CType::Pattern(x) => x.fallback_type(),
+            _ => t.clone(),
+        };
+
+        if !matches!(resolved, CType::Composite(_) | CType::Union(_) | CType::TaggedUnion(_) | CType::Enum(_)) {
+            continue;
+        }
+
+        let layout = match rust.iter().find(|x| x.name() == t.name_within_lib()) {
+            Some(x) => x,
+            None => {
+                missing.push(t.name_within_lib());
+                continue;
+            }
+        };
+
+        let c_name = generator.converter().to_type_specifier(&resolved);
+
+        expected.insert(format!("{} size {}", layout.name(), layout.size()));
+        expected.insert(format!("{} align {}", layout.name(), layout.align()));
+        writeln!(program, "    printf(\"{} size %zu\\n\", sizeof({}));", layout.name(), c_name)?;
+        writeln!(program, "    printf(\"{} align %zu\\n\", _Alignof({}));", layout.name(), c_name)?;
+
+        for field in layout.fields() {
+            expected.insert(format!("{}.{} offset {}", layout.name(), field.name(), field.offset()));
+            writeln!(
+                program,
+                "    printf(\"{}.{} offset %zu\\n\", offsetof({}, {}));",
+                layout.name(),
+                field.name(),
+                c_name,
+                field.name()
+            )?;
+        }
+    }
+
+    if !missing.is_empty() {
+        return Err(Error::TestFailed {
+            command: "verify_layouts_if_installed".to_string(),
+            output: format!("no Rust layout given for {}", missing.join(", ")),
+        });
+    }
+
+    writeln!(program, "    return 0;")?;
+    writeln!(program, "}}")?;
+
+    std::fs::create_dir_all(out_dir)?;
+    let source = out_dir.join("layout.c");
+    let binary = out_dir.join("layout");
+    std::fs::write(&source, program)?;
+
+    let compiled = Command::new(&compiler).arg("-std=c11").arg("-o").arg(&binary).arg(&source).output()?;
+
+    if !compiled.status.success() {
+        return Err(Error::TestFailed {
+            command: format!("{} {}", compiler, source.display()),
+            output: String::from_utf8_lossy(&compiled.stderr).to_string(),
+        });
+    }
+
+    let ran = Command::new(&binary).output()?;
+    let actual = String::from_utf8(ran.stdout)?.lines().map(|x| x.to_string()).collect::<BTreeSet<_>>();
+
+    if !ran.status.success() || actual != expected {
+        let mut mismatches = String::new();
+
+        for x in expected.difference(&actual) {
+            writeln!(mismatches, "rust: {}", x)?;
+        }
+
+        for x in actual.difference(&expected) {
+            writeln!(mismatches, "c:    {}", x)?;
+        }
+
+        return Err(Error::TestFailed {
+            command: binary.display().to_string(),
+            output: mismatches,
+        });
+    }
+
+    Ok(())
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn verify_layouts_if_installed<P: AsRef<Path>>(_out_dir: P, _generator: &Generator, _header: &str, _rust: &[TypeLayout]) -> Result<(), Error> {
+    Ok(())
+}
+
+/// Returns the C compiler from `CC`, or the first common one responding to `--version`.
+///
+/// A `CC` that doesn't respond is an error rather than a reason to skip, as it was asked for explicitly.
+#[cfg(target_os = "linux")]
+fn find_c_compiler() -> Result<Option<String>, Error> {
+    let responds = |x: &str| std::process::Command::new(x).arg("--version").output().map(|x| x.status.success()).unwrap_or(false);
+
+    if let Ok(compiler) = std::env::var("CC") {
+        return if responds(&compiler) {
+            Ok(Some(compiler))
+        } else {
+            Err(Error::CommandNotFound(compiler))
+        };
+    }
+
+    Ok(["cc", "gcc", "clang"].iter().find(|x| responds(x)).map(|x| x.to_string()))
+}
@@ -0,0 +1,66 @@
+use crate::{CWriter, Generator};
+use interoptopus::lang::c::{ConstantValue, PrimitiveValue};
+use interoptopus::validate::{Level, Validation};
+
+/// Keywords of C99, none of which we escape.
+const RESERVED: &[&str] = &[
+    "auto",
+    "break",
+    "case",
+    "char",
+    "const",
+    "continue",
+    "default",
+    "do",
+    "double",
+    "else",
+    "enum",
+    "extern",
+    "float",
+    "for",
+    "goto",
+    "if",
+    "inline",
+    "int",
+    "long",
+    "register",
+    "restrict",
+    "return",
+    "short",
+    "signed",
+    "sizeof",
+    "static",
+    "struct",
+    "switch",
+    "typedef",
+    "union",
+    "unsigned",
+    "void",
+    "volatile",
+    "while",
+    "_Bool",
+    "_Complex",
+    "_Imaginary",
+];
+
+pub(crate) fn validate(generator: &Generator) -> Validation {
+    let inventory = generator.inventory();
+    let mut validation = inventory.validate();
+
+    validation.check_reserved_words(inventory, "C", RESERVED);
+
+    for constant in inventory.constants() {
+        let fits = match constant.value() {
+            ConstantValue::Primitive(PrimitiveValue::U128(x)) => *x <= u64::MAX as u128,
+            ConstantValue::Primitive(PrimitiveValue::I128(x)) => *x >= i64::MIN as i128 && *x <= i64::MAX as i128,
+            _ => true,
+        };
+
+        if !fits {
+            let message = "value exceeds 64 bits, which C has no literals for";
+            validation.push(Level::Error, format!("const {}", constant.name()), message);
+        }
+    }
+
+    validation
+}
@@ -1,5 +1,8 @@
 use interoptopus::indented;
-use interoptopus::lang::c::{CType, CompositeType, Constant, Documentation, EnumType, Field, FnPointerType, Function, OpaqueType, Variant};
+use interoptopus::lang::c::{
+    CType, CompositeType, Constant, ConstantValue, Documentation, EnumType, Field, FnPointerType, Function, OpaqueType, PrimitiveType, TagPlacement, TaggedUnionType,
+    UnionType, Variant,
+};
 use interoptopus::patterns::callbacks::NamedCallback;
 use interoptopus::patterns::TypePattern;
 use interoptopus::util::sort_types_by_dependencies;
@@ -11,6 +14,10 @@ use crate::converter::CTypeConverter;
 use crate::converter::Converter;
 use crate::Config;
 
+fn is_aggregate_constant(constant: &Constant) -> bool {
+    matches!(constant.value(), ConstantValue::Array(_, _) | ConstantValue::Composite(_, _))
+}
+
 /// Writes the C file format, `impl` this trait to customize output.
 pub trait CWriter {
     /// Returns the user config.
@@ -31,6 +38,7 @@ pub trait CWriter {
     }
 
     fn write_imports(&self, w: &mut IndentWriter) -> Result<(), Error> {
+        indented!(w, r#"#include <stddef.h>"#)?;
         indented!(w, r#"#include <stdint.h>"#)?;
         indented!(w, r#"#include <stdbool.h>"#)?;
 
@@ -38,8 +46,18 @@ pub trait CWriter {
     }
 
     fn write_constants(&self, w: &mut IndentWriter) -> Result<(), Error> {
-        for constant in self.inventory().constants() {
-            self.write_constant(w, constant)?;
+        for constant in self.inventory().constants().iter().filter(|x| !is_aggregate_constant(x)) {
+            self.write_constant(w, constant).map_err(|e| e.with_item(format!("const {}", constant.name())))?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes struct and array constants, which must come after the types they use.
+    fn write_aggregate_constants(&self, w: &mut IndentWriter) -> Result<(), Error> {
+        for constant in self.inventory().constants().iter().filter(|x| is_aggregate_constant(x)) {
+            self.write_constant(w, constant).map_err(|e| e.with_item(format!("const {}", constant.name())))?;
+            w.newline()?;
         }
 
         Ok(())
@@ -47,23 +65,42 @@ pub trait CWriter {
 
     fn write_constant(&self, w: &mut IndentWriter, constant: &Constant) -> Result<(), Error> {
         let name = self.converter().const_name_to_name(constant);
-        let the_type = match constant.the_type() {
-            CType::Primitive(x) => self.converter().primitive_to_typename(&x),
-            _ => return Err(Error::Null),
-        };
+        let value = self.converter().constant_value_to_value(constant.value());
 
         if self.config().documentation == CDocumentationStyle::Inline {
             self.write_documentation(w, constant.meta().documentation())?;
         }
 
-        indented!(w, r#"const {} {} = {};"#, the_type, name, self.converter().constant_value_to_value(constant.value()))?;
+        match constant.the_type() {
+            CType::Primitive(x) => {
+                let the_type = self.converter().primitive_to_typename(&x);
+                indented!(w, r#"const {} {} = {};"#, the_type, name, value)?;
+            }
+            CType::Pattern(TypePattern::AsciiPointer) => {
+                indented!(w, r#"#define {} {}"#, name, value)?;
+            }
+            CType::Array(mut array) => {
+                let mut dimensions = format!("[{}]", array.len());
+                while let CType::Array(inner) = array.array_type().clone() {
+                    dimensions.push_str(&format!("[{}]", inner.len()));
+                    array = inner;
+                }
+
+                let the_type = self.converter().to_type_specifier(array.array_type());
+                indented!(w, r#"static const {} {}{} = {};"#, the_type, name, dimensions, value)?;
+            }
+            x => {
+                let the_type = self.converter().to_type_specifier(&x);
+                indented!(w, r#"static const {} {} = {};"#, the_type, name, value)?;
+            }
+        }
 
         Ok(())
     }
 
     fn write_functions(&self, w: &mut IndentWriter) -> Result<(), Error> {
         for function in self.inventory().functions() {
-            self.write_function(w, function)?;
+            self.write_function(w, function).map_err(|e| e.with_item(format!("fn {}", function.name())))?;
         }
 
         Ok(())
@@ -138,7 +175,8 @@ pub trait CWriter {
         let mut known_function_pointers = vec![];
 
         for the_type in &sort_types_by_dependencies(self.inventory().ctypes().to_vec()) {
-            self.write_type_definition(w, the_type, &mut known_function_pointers)?;
+            self.write_type_definition(w, the_type, &mut known_function_pointers)
+                .map_err(|e| e.with_item(format!("type {}", the_type.name_within_lib())))?;
         }
 
         Ok(())
@@ -160,6 +198,14 @@ pub trait CWriter {
                 self.write_type_definition_composite(w, c)?;
                 w.newline()?;
             }
+            CType::Union(u) => {
+                self.write_type_definition_union(w, u)?;
+                w.newline()?;
+            }
+            CType::TaggedUnion(t) => {
+                self.write_type_definition_tagged_union(w, t)?;
+                w.newline()?;
+            }
             CType::FnPointer(f) => {
                 self.write_type_definition_fn_pointer(w, f, known_function_pointers)?;
                 w.newline()?;
@@ -184,10 +230,30 @@ pub trait CWriter {
                     self.write_type_definition_composite(w, x)?;
                     w.newline()?;
                 }
+                TypePattern::Vec(x) => {
+                    self.write_type_definition_composite(w, x)?;
+                    w.newline()?;
+                }
+                TypePattern::Str(x) => {
+                    self.write_type_definition_composite(w, x)?;
+                    w.newline()?;
+                }
+                TypePattern::String(x) => {
+                    self.write_type_definition_composite(w, x)?;
+                    w.newline()?;
+                }
                 TypePattern::Option(x) => {
                     self.write_type_definition_composite(w, x)?;
                     w.newline()?;
                 }
+                TypePattern::Result(x) => {
+                    self.write_type_definition_composite(w, x.the_type())?;
+                    w.newline()?;
+                }
+                TypePattern::CallbackWithData(x) => {
+                    self.write_type_definition_composite(w, x.the_type())?;
+                    w.newline()?;
+                }
                 TypePattern::Bool => {}
                 TypePattern::CChar => {}
                 TypePattern::APIVersion => {}
@@ -248,6 +314,18 @@ pub trait CWriter {
             self.write_documentation(w, the_type.meta().documentation())?;
         }
 
+        // A C `enum` is as large as an `int`, so other sizes become a fixed-width typedef plus the named values.
+        if the_type.repr() != PrimitiveType::I32 {
+            let repr = self.converter().primitive_to_typename(&the_type.repr());
+            indented!(w, r#"typedef {} {};"#, repr, name)?;
+
+            self.write_braced_declaration_opening(w, "enum".to_string())?;
+            for variant in the_type.variants() {
+                self.write_type_definition_enum_variant(w, variant, the_type)?;
+            }
+            return self.write_braced_declaration_closing(w, String::new());
+        }
+
         self.write_braced_declaration_opening(w, format!("typedef enum {}", name))?;
 
         for variant in the_type.variants() {
@@ -326,6 +404,92 @@ pub trait CWriter {
         }
     }
 
+    fn write_type_definition_union(&self, w: &mut IndentWriter, the_type: &UnionType) -> Result<(), Error> {
+        if self.config().documentation == CDocumentationStyle::Inline {
+            self.write_documentation(w, the_type.meta().documentation())?;
+        }
+
+        let name = self.converter().union_to_typename(the_type);
+
+        self.write_braced_declaration_opening(w, format!(r#"typedef union {}"#, name))?;
+
+        for field in the_type.fields() {
+            self.write_type_definition_union_body_field(w, field, the_type)?;
+        }
+
+        self.write_braced_declaration_closing(w, name)
+    }
+
+    fn write_type_definition_union_body_field(&self, w: &mut IndentWriter, field: &Field, _the_type: &UnionType) -> Result<(), Error> {
+        match field.the_type() {
+            CType::Array(x) => {
+                let type_name = self.converter().to_type_specifier(x.array_type());
+                indented!(w, r#"{} {}[{}];"#, type_name, field.name(), x.len())
+            }
+            _ => {
+                let type_name = self.converter().to_type_specifier(field.the_type());
+                indented!(w, r#"{} {};"#, type_name, field.name())
+            }
+        }
+    }
+
+    fn write_type_definition_tagged_union(&self, w: &mut IndentWriter, the_type: &TaggedUnionType) -> Result<(), Error> {
+        self.write_type_definition_enum(w, &the_type.tag_enum())?;
+        w.newline()?;
+
+        for variant in the_type.payload_variants() {
+            self.write_type_definition_composite(w, &the_type.variant_composite(variant))?;
+            w.newline()?;
+        }
+
+        if self.config().documentation == CDocumentationStyle::Inline {
+            self.write_documentation(w, the_type.meta().documentation())?;
+        }
+
+        self.write_type_definition_tagged_union_body(w, the_type)
+    }
+
+    fn write_type_definition_tagged_union_body(&self, w: &mut IndentWriter, the_type: &TaggedUnionType) -> Result<(), Error> {
+        let name = self.converter().tagged_union_to_typename(the_type);
+        let tag = self.converter().primitive_to_typename(&the_type.tag());
+        let has_payload = the_type.payload_variants().next().is_some();
+
+        match the_type.placement() {
+            TagPlacement::Separate => {
+                let payload_name = self.converter().tagged_union_payload_to_typename(the_type);
+
+                if has_payload {
+                    self.write_braced_declaration_opening(w, format!(r#"typedef union {}"#, payload_name))?;
+                    self.write_type_definition_tagged_union_members(w, the_type)?;
+                    self.write_braced_declaration_closing(w, payload_name.clone())?;
+                    w.newline()?;
+                }
+
+                self.write_braced_declaration_opening(w, format!(r#"typedef struct {}"#, name))?;
+                indented!(w, r#"{} tag;"#, tag)?;
+                if has_payload {
+                    indented!(w, r#"{} payload;"#, payload_name)?;
+                }
+                self.write_braced_declaration_closing(w, name)
+            }
+            TagPlacement::Inline => {
+                self.write_braced_declaration_opening(w, format!(r#"typedef union {}"#, name))?;
+                indented!(w, r#"{} tag;"#, tag)?;
+                self.write_type_definition_tagged_union_members(w, the_type)?;
+                self.write_braced_declaration_closing(w, name)
+            }
+        }
+    }
+
+    fn write_type_definition_tagged_union_members(&self, w: &mut IndentWriter, the_type: &TaggedUnionType) -> Result<(), Error> {
+        for variant in the_type.payload_variants() {
+            let type_name = self.converter().composite_to_typename(&the_type.variant_composite(variant));
+            indented!(w, r#"{} {};"#, type_name, variant.name())?;
+        }
+
+        Ok(())
+    }
+
     fn write_ifndef(&self, w: &mut IndentWriter, f: impl FnOnce(&mut IndentWriter) -> Result<(), Error>) -> Result<(), Error> {
         if self.config().directives {
             indented!(w, r#"#ifndef {}"#, self.config().ifndef)?;
@@ -382,6 +546,8 @@ pub trait CWriter {
                 self.write_type_definitions(w)?;
                 w.newline()?;
 
+                self.write_aggregate_constants(w)?;
+
                 self.write_functions(w)?;
 
                 Ok(())
@@ -420,18 +586,21 @@ pub trait CWriter {
     }
 
     fn write_braced_declaration_closing(&self, w: &mut IndentWriter, name: String) -> Result<(), Error> {
+        // Anonymous declarations have nothing between the brace and the semicolon.
+        let name = if name.is_empty() { name } else { format!(" {}", name) };
+
         match self.config().indentation {
             CIndentationStyle::Allman | CIndentationStyle::KAndR => {
                 w.unindent();
-                indented!(w, "}} {};", name)?;
+                indented!(w, "}}{};", name)?;
             }
             CIndentationStyle::GNU => {
                 w.unindent();
-                indented!(w, "  }} {};", name)?;
+                indented!(w, "  }}{};", name)?;
             }
             CIndentationStyle::Whitesmiths => {
                 w.unindent();
-                indented!(w, [_], "}} {};", name)?;
+                indented!(w, [_], "}}{};", name)?;
             }
         }
 
@@ -1,7 +1,24 @@
-use interoptopus::testing::assert_file_matches_generated;
-use interoptopus::Error;
+use interoptopus::lang::rust::{FieldLayout, LayoutInfo, TypeLayout};
+use interoptopus::patterns::option::FFIOption;
+use interoptopus::patterns::primitives::FFIBool;
+use interoptopus::patterns::result::FFIResult;
+use interoptopus::patterns::slice::{FFISlice, FFISliceMut};
+use interoptopus::patterns::string::{FFIStr, FFIString};
+use interoptopus::patterns::vec::FFIVec;
+use interoptopus::testing::snapshot::assert_generated_matches_snapshot;
 use interoptopus::Interop;
-use interoptopus_backend_c::{compile_c_app_if_installed, CDocumentationStyle, CIndentationStyle, CNamingStyle, Config, Generator};
+use interoptopus::{Error, ErrorContext};
+use interoptopus_backend_c::{
+    compile_c_app_with_library_if_installed, verify_layouts_if_installed, CDocumentationStyle, CIndentationStyle, CNamingStyle, Config, Generator,
+};
+use interoptopus_reference_project::patterns::callbacks::MyCallbackData;
+use interoptopus_reference_project::patterns::option::Inner;
+use interoptopus_reference_project::patterns::result::FFIError;
+use interoptopus_reference_project::types::{
+    ambiguous1, ambiguous2, common, Array, EnumDocumented, EnumRenamedXYZ, EnumReprI8, ExtraType, Generic, Phantom, SomeForeignType, StructDocumented, StructRenamedXYZ,
+    TaggedInline, TaggedShape, Tupled, UnionBits, UseAsciiStringPattern, Vec3f32, Visibility1, Visibility2, Weird1, Weird2,
+};
+use std::env::consts::{DLL_PREFIX, DLL_SUFFIX};
 use std::path::Path;
 
 fn nodocs_config() -> Config {
@@ -25,15 +42,16 @@ fn docs_inline_config() -> Config {
     }
 }
 
+fn generator(config: Config) -> Generator {
+    Generator::new(config, interoptopus_reference_project::ffi_inventory_with_128bit())
+}
+
 fn generate_bindings_multi(folder: impl AsRef<Path>, config: Option<Config>) -> Result<(), Error> {
-    let config = config.unwrap_or_default();
+    std::fs::create_dir_all(folder.as_ref())?;
 
     let file_name = format!("{}/my_header.h", folder.as_ref().to_str().ok_or(Error::FileNotFound)?).replace("..", ".");
 
-    let inventory = interoptopus_reference_project::ffi_inventory();
-
-    let generator = Generator::new(config, inventory);
-    generator.write_file(file_name)?;
+    generator(config.unwrap_or_default()).write_file(file_name)?;
 
     Ok(())
 }
@@ -50,23 +68,121 @@ fn generate_bindings_multi(folder: impl AsRef<Path>, config: Option<Config>) ->
 
 #[test]
 #[cfg_attr(miri, ignore)]
-fn bindings_match_reference() -> Result<(), Error> {
+fn bindings_match_reference() {
+    assert_generated_matches_snapshot(&generator(nodocs_config()), "tests/output_nodocs/my_header.h.expected");
+    assert_generated_matches_snapshot(&generator(docs_inline_config()), "tests/output_docs_inline/my_header.h.expected");
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn bindings_work() -> Result<(), Error> {
     generate_bindings_multi("tests/output_nodocs/", Some(nodocs_config()))?;
     generate_bindings_multi("tests/output_docs_inline/", Some(docs_inline_config()))?;
 
-    assert_file_matches_generated("tests/output_nodocs/my_header.h");
-    assert_file_matches_generated("tests/output_docs_inline/my_header.h");
+    let deps = std::env::current_exe()?.parent().ok_or(Error::FileNotFound)?.to_path_buf();
+    let library = deps.join(format!("{}interoptopus_reference_project{}", DLL_PREFIX, DLL_SUFFIX));
+    let out_dir = Path::new(env!("CARGO_TARGET_TMPDIR"));
 
+    compile_c_app_with_library_if_installed(out_dir.join("app_nodocs"), "tests/output_nodocs/app.c", &library)?;
+    compile_c_app_with_library_if_installed(out_dir.join("app_docs_inline"), "tests/output_docs_inline/app.c", &library)?;
     Ok(())
 }
 
 #[test]
 #[cfg_attr(miri, ignore)]
-fn bindings_work() -> Result<(), Error> {
-    generate_bindings_multi("tests/output_nodocs/", Some(nodocs_config()))?;
-    generate_bindings_multi("tests/output_docs_inline/", Some(docs_inline_config()))?;
+fn layouts_match_rust() -> Result<(), Error> {
+    // `SomeForeignType` is only described by a surrogate, so its layout is given by hand.
+    let foreign = TypeLayout::new(
+        "SomeForeignType".to_string(),
+        std::mem::size_of::<SomeForeignType>(),
+        std::mem::align_of::<SomeForeignType>(),
+        vec![FieldLayout::new("x".to_string(), 0)],
+    );
+
+    let layouts = [
+        Tupled::layout_info(),
+        Generic::<u32>::layout_info(),
+        Generic::<u8>::layout_info(),
+        StructRenamedXYZ::layout_info(),
+        Vec3f32::layout_info(),
+        Array::layout_info(),
+        Phantom::<u8>::layout_info(),
+        StructDocumented::layout_info(),
+        Visibility1::layout_info(),
+        Visibility2::layout_info(),
+        ExtraType::<f32>::layout_info(),
+        UseAsciiStringPattern::layout_info(),
+        Weird1::<u32>::layout_info(),
+        Weird2::<u8, 5>::layout_info(),
+        EnumDocumented::layout_info(),
+        EnumRenamedXYZ::layout_info(),
+        EnumReprI8::layout_info(),
+        UnionBits::layout_info(),
+        TaggedInline::layout_info(),
+        TaggedShape::layout_info(),
+        ambiguous1::Vec::layout_info(),
+        ambiguous2::Vec::layout_info(),
+        common::Vec::layout_info(),
+        Inner::layout_info(),
+        FFIError::layout_info(),
+        MyCallbackData::layout_info(),
+        FFISlice::<FFIBool>::layout_info(),
+        FFISlice::<UseAsciiStringPattern>::layout_info(),
+        FFISlice::<common::Vec>::layout_info(),
+        FFISlice::<Vec3f32>::layout_info(),
+        FFISlice::<u32>::layout_info(),
+        FFISlice::<u8>::layout_info(),
+        FFISliceMut::<common::Vec>::layout_info(),
+        FFISliceMut::<u32>::layout_info(),
+        FFISliceMut::<u8>::layout_info(),
+        FFIVec::<Vec3f32>::layout_info(),
+        FFIVec::<u32>::layout_info(),
+        FFIStr::layout_info(),
+        FFIString::layout_info(),
+        FFIOption::<Inner>::layout_info(),
+        FFIOption::<common::Vec>::layout_info(),
+        FFIResult::<u32, FFIError>::layout_info(),
+        foreign,
+    ];
+
+    let inventory = interoptopus_reference_project::ffi_inventory_with_128bit();
+    let nodocs = Generator::new(nodocs_config(), inventory.clone());
+    let docs_inline = Generator::new(docs_inline_config(), inventory);
+
+    // Own headers, as `bindings_work` might be rewriting the ones in `tests/` in parallel.
+    let out_dir = Path::new(env!("CARGO_TARGET_TMPDIR"));
+
+    generate_bindings_multi(out_dir.join("layout_nodocs"), Some(nodocs_config()))?;
+    generate_bindings_multi(out_dir.join("layout_docs_inline"), Some(docs_inline_config()))?;
+
+    let nodocs_header = out_dir.join("layout_nodocs/my_header.h");
+    let docs_inline_header = out_dir.join("layout_docs_inline/my_header.h");
+
+    verify_layouts_if_installed(out_dir.join("layout_nodocs"), &nodocs, nodocs_header.to_str().ok_or(Error::FileNotFound)?, &layouts)?;
+    verify_layouts_if_installed(
+        out_dir.join("layout_docs_inline"),
+        &docs_inline,
+        docs_inline_header.to_str().ok_or(Error::FileNotFound)?,
+        &layouts,
+    )?;
 
-    compile_c_app_if_installed("tests/output_nodocs/", "tests/output_nodocs/app.c")?;
-    compile_c_app_if_installed("tests/output_docs_inline/", "tests/output_docs_inline/app.c")?;
     Ok(())
 }
+
+#[test]
+fn write_errors_name_the_file() {
+    let generator = Generator::new(nodocs_config(), interoptopus_reference_project::ffi_inventory_with_128bit());
+    let error = generator.write_file("tests/does_not_exist/my_header.h").unwrap_err();
+
+    assert_eq!(error.contexts(), [&ErrorContext::File("tests/does_not_exist/my_header.h".into())]);
+    assert_eq!(error.to_string(), "writing tests/does_not_exist/my_header.h");
+    assert!(matches!(error.root_cause(), Error::IO(_)));
+}
+
+#[test]
+fn reference_project_is_valid() {
+    let generator = Generator::new(nodocs_config(), interoptopus_reference_project::ffi_inventory_with_128bit());
+    let validation = generator.validate();
+
+    assert!(!validation.has_errors(), "{}", validation);
+}
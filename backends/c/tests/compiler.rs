@@ -0,0 +1,13 @@
+use interoptopus::Error;
+use interoptopus_backend_c::compile_c_app_if_installed;
+
+// Lives in its own test binary, as changing `CC` would affect other tests running in parallel.
+#[test]
+#[cfg(target_os = "linux")]
+fn missing_compiler_in_cc_is_an_error() {
+    std::env::set_var("CC", "interoptopus_no_such_compiler");
+
+    let error = compile_c_app_if_installed(env!("CARGO_TARGET_TMPDIR"), "tests/output_nodocs/app.c").unwrap_err();
+
+    assert!(matches!(error, Error::CommandNotFound(x) if x == "interoptopus_no_such_compiler"));
+}
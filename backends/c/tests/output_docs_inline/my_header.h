@@ -7,6 +7,7 @@
 extern "C" {
 #endif
 
+#include <stddef.h>
 #include <stdint.h>
 #include <stdbool.h>
 
@@ -15,6 +16,14 @@ extern "C" {
 const uint8_t MY_LIBRARY_U8 = 255;
 const float MY_LIBRARY_F32_MIN_POSITIVE = 0.000000000000000000000000000000000000011754944;
 const int32_t MY_LIBRARY_COMPUTED_I32 = -2147483647;
+const size_t MY_LIBRARY_USIZE = 1048576;
+/// Exported as its Unicode scalar value.
+const uint32_t MY_LIBRARY_CHAR = 955;
+/// Exported as a string literal.
+#define MY_LIBRARY_VERSION "1.2.3"
+const uint32_t MY_LIBRARY_COMMON_U32 = 42;
+/// Only C can express 128-bit constants, and only within the 64-bit range.
+const unsigned __int128 MY_LIBRARY_U128 = 18446744073709551615ULL;
 
 /// Documented enum.
 typedef enum my_library_enum_documented
@@ -29,6 +38,15 @@ typedef enum my_library_enum_renamed
     MY_LIBRARY_ENUM_RENAMED_X = 0,
 } my_library_enum_renamed;
 
+/// Enum stored as a single signed byte.
+typedef int8_t my_library_enum_repr_i8;
+enum
+{
+    MY_LIBRARY_ENUM_REPR_I8_NEGATIVE = -1,
+    MY_LIBRARY_ENUM_REPR_I8_ZERO = 0,
+    MY_LIBRARY_ENUM_REPR_I8_POSITIVE = 5,
+};
+
 typedef struct my_library_generic2u8 my_library_generic2u8;
 
 typedef struct my_library_generic3 my_library_generic3;
@@ -50,6 +68,21 @@ typedef enum my_library_ffi_error
     MY_LIBRARY_FFI_ERROR_FAIL = 300,
 } my_library_ffi_error;
 
+///A UTF-8 string borrowed for the duration of a call, not necessarily NUL terminated.
+typedef struct my_library_utf8_str
+{
+    const uint8_t* data;
+    uint64_t len;
+} my_library_utf8_str;
+
+///A UTF-8 string owned by this library, not NUL terminated, must be freed by the caller.
+typedef struct my_library_utf8_string
+{
+    uint8_t* data;
+    uint64_t len;
+    uint64_t capacity;
+} my_library_utf8_string;
+
 typedef struct my_library_extra_typef32
 {
     float x;
@@ -133,8 +166,47 @@ typedef struct my_library_weird1u32
     uint32_t x;
 } my_library_weird1u32;
 
+typedef uint8_t my_library_tagged_shape_tag;
+enum
+{
+    MY_LIBRARY_TAGGED_SHAPE_TAG_EMPTY = 0,
+    MY_LIBRARY_TAGGED_SHAPE_TAG_CIRCLE = 1,
+    MY_LIBRARY_TAGGED_SHAPE_TAG_RECT = 2,
+};
+
+typedef struct my_library_tagged_shape_circle
+{
+    float x0;
+} my_library_tagged_shape_circle;
+
+typedef struct my_library_tagged_shape_rect
+{
+    float width;
+    float height;
+} my_library_tagged_shape_rect;
+
+/// Enum with payloads, stored as a tag followed by a union.
+typedef union my_library_tagged_shape_payload
+{
+    my_library_tagged_shape_circle Circle;
+    my_library_tagged_shape_rect Rect;
+} my_library_tagged_shape_payload;
+
+typedef struct my_library_tagged_shape
+{
+    uint8_t tag;
+    my_library_tagged_shape_payload payload;
+} my_library_tagged_shape;
+
 typedef uint8_t (*my_library_fptr_fn_u8_rval_u8)(uint8_t x0);
 
+///Result type containing either a value or an error code.
+typedef struct my_library_resultu32_ffi_error
+{
+    uint32_t t;
+    my_library_ffi_error err;
+} my_library_resultu32_ffi_error;
+
 typedef uint8_t (*my_library_callback_u8)(uint8_t value);
 
 typedef uint32_t (*my_library_my_callback)(uint32_t value);
@@ -161,6 +233,45 @@ typedef struct my_library_weird2u8
     const uint8_t* r;
 } my_library_weird2u8;
 
+/// Union where all fields share the same four bytes.
+typedef union my_library_union_bits
+{
+    float f;
+    uint32_t u;
+    uint8_t bytes[4];
+} my_library_union_bits;
+
+typedef uint32_t my_library_tagged_inline_tag;
+enum
+{
+    MY_LIBRARY_TAGGED_INLINE_TAG_A = 0,
+    MY_LIBRARY_TAGGED_INLINE_TAG_B = 1,
+    MY_LIBRARY_TAGGED_INLINE_TAG_C = 10,
+};
+
+typedef struct my_library_tagged_inline_a
+{
+    uint32_t tag;
+    uint8_t x0;
+} my_library_tagged_inline_a;
+
+typedef struct my_library_tagged_inline_b
+{
+    uint32_t tag;
+    my_library_vec3f32 x;
+} my_library_tagged_inline_b;
+
+typedef union my_library_tagged_inline
+{
+    uint32_t tag;
+    my_library_tagged_inline_a A;
+    my_library_tagged_inline_b B;
+} my_library_tagged_inline;
+
+typedef void (*my_library_fptr_fn_pconst)(const void* x0);
+
+typedef uint32_t (*my_library_fptr_fn_u32_pconst__rval_u32)(uint32_t x0, const void* x1);
+
 ///A pointer to an array of data someone else owns which may not be modified.
 typedef struct my_library_slice_bool
 {
@@ -196,6 +307,14 @@ typedef struct my_library_slice_mutu8
     uint64_t len;
 } my_library_slice_mutu8;
 
+///An array of data owned by this library, must be freed by the caller.
+typedef struct my_library_vecu32
+{
+    uint32_t* data;
+    uint64_t len;
+    uint64_t capacity;
+} my_library_vecu32;
+
 ///Option type containing boolean flag and maybe valid data.
 typedef struct my_library_option_inner
 {
@@ -240,13 +359,34 @@ typedef struct my_library_slice_mut_vec
     uint64_t len;
 } my_library_slice_mut_vec;
 
+///An array of data owned by this library, must be freed by the caller.
+typedef struct my_library_vec_vec3f32
+{
+    my_library_vec3f32* data;
+    uint64_t len;
+    uint64_t capacity;
+} my_library_vec_vec3f32;
+
 typedef uint8_t (*my_library_callback_ffi_slice)(my_library_sliceu8 slice);
 
 typedef void (*my_library_callback_slice_mut)(my_library_slice_mutu8 slice);
 
+///Callback carrying user data, which is released once the callback is dropped.
+typedef struct my_library_my_callback_data
+{
+    my_library_fptr_fn_u32_pconst__rval_u32 callback;
+    const void* data;
+    my_library_fptr_fn_pconst destructor;
+} my_library_my_callback_data;
+
 typedef my_library_vec3f32 (*my_library_callback_huge_vec_slice)(my_library_slice_vec3f32 slice);
 
 
+static const uint8_t MY_LIBRARY_ARRAY_U8[4] = { 1, 2, 3, 4 };
+
+/// Struct constants must be written as struct literals.
+static const my_library_vec3f32 MY_LIBRARY_VEC3_ONE = { 1, 1, 1 };
+
 void primitive_void();
 
 void primitive_void2();
@@ -269,6 +409,13 @@ int32_t primitive_i32(int32_t x);
 
 int64_t primitive_i64(int64_t x);
 
+size_t primitive_usize(size_t x);
+
+intptr_t primitive_isize(intptr_t x);
+
+/// Callers must pass a valid Unicode scalar value, which is why `rustc` considers `char` not FFI-safe.
+uint32_t primitive_char(uint32_t x);
+
 int64_t many_args_5(int64_t x0, int64_t x1, int64_t x2, int64_t x3, int64_t x4);
 
 int64_t many_args_10(int64_t x0, int64_t x1, int64_t x2, int64_t x3, int64_t x4, int64_t x5, int64_t x6, int64_t x7, int64_t x8, int64_t x9);
@@ -329,6 +476,8 @@ my_library_slice_vec namespaced_inner_slice(my_library_slice_vec x);
 
 my_library_slice_mut_vec namespaced_inner_slice_mut(my_library_slice_mut_vec x);
 
+my_library_vec namespaced_function_renamed(my_library_vec x);
+
 my_library_ffi_error panics();
 
 my_library_enum_renamed renamed(my_library_struct_renamed x);
@@ -341,6 +490,14 @@ void visibility(my_library_visibility1 x, my_library_visibility2 y);
 
 my_library_tupled repr_transparent(my_library_tupled x, const my_library_tupled* r);
 
+my_library_tagged_shape tagged_union_1(my_library_tagged_shape x);
+
+uint32_t tagged_union_2(const my_library_tagged_inline* x);
+
+uint32_t union_1(my_library_union_bits x);
+
+my_library_enum_repr_i8 enum_repr_1(my_library_enum_repr_i8 x);
+
 uint32_t pattern_ascii_pointer_1(const char* x);
 
 const char* pattern_ascii_pointer_2();
@@ -367,10 +524,24 @@ uint8_t pattern_ffi_slice_delegate(my_library_callback_ffi_slice callback);
 
 my_library_vec3f32 pattern_ffi_slice_delegate_huge(my_library_callback_huge_vec_slice callback);
 
+uint32_t pattern_ffi_str_1(my_library_utf8_str x);
+
+my_library_utf8_string pattern_ffi_string_1(my_library_utf8_str x);
+
+my_library_utf8_string pattern_ffi_string_2();
+
+my_library_vecu32 pattern_ffi_vec_1(uint32_t len);
+
+my_library_vec_vec3f32 pattern_ffi_vec_2(float x, uint32_t len);
+
+uint32_t pattern_ffi_vec_3(my_library_vecu32 vec);
+
 my_library_option_inner pattern_ffi_option_1(my_library_option_inner ffi_slice);
 
 my_library_inner pattern_ffi_option_2(my_library_option_inner ffi_slice);
 
+my_library_resultu32_ffi_error pattern_ffi_result_1(uint32_t x);
+
 uint8_t pattern_ffi_bool(uint8_t ffi_bool);
 
 char pattern_ffi_cchar(char ffi_cchar);
@@ -385,6 +556,8 @@ uint32_t pattern_callback_1(my_library_my_callback callback, uint32_t x);
 
 my_library_my_callback_void pattern_callback_2(my_library_my_callback_void callback);
 
+uint32_t pattern_callback_with_data_1(my_library_my_callback_data callback, uint32_t x);
+
 /// Destroys the given instance.
 ///
 /// # Safety
@@ -406,6 +579,9 @@ my_library_ffi_error simple_service_new_failing(my_library_simple_service** cont
 /// need annotations.
 my_library_ffi_error simple_service_method_result(const my_library_simple_service* context, uint32_t anon1);
 
+/// Methods returning a Result<T, _> become an `FFIResult<T, FFIError>`.
+my_library_resultu32_ffi_error simple_service_method_result_value(const my_library_simple_service* context, uint32_t x);
+
 uint32_t simple_service_method_value(const my_library_simple_service* context, uint32_t x);
 
 /// This method should be documented.
@@ -439,6 +615,9 @@ my_library_slice_mutu32 simple_service_return_slice_mut(my_library_simple_servic
 /// This function has no panic safeguards. If it panics your host app will be in an undefined state.
 const char* simple_service_return_string(my_library_simple_service* context);
 
+/// Returns an owned copy of this service's string, followed by `suffix`.
+my_library_utf8_string simple_service_return_string_utf8(my_library_simple_service* context, my_library_utf8_str suffix);
+
 my_library_ffi_error simple_service_method_void_ffi_error(my_library_simple_service* context);
 
 my_library_ffi_error simple_service_method_callback(my_library_simple_service* context, my_library_my_callback callback);
@@ -461,6 +640,24 @@ const char* simple_service_lt_return_string_accept_slice(my_library_simple_servi
 
 my_library_ffi_error simple_service_lt_method_void_ffi_error(my_library_simple_service_lifetime* context);
 
+/// Frees a vec returned by this library.
+void pattern_ffi_vec_u32_destroy(my_library_vecu32 vec);
+
+/// Frees a vec returned by this library.
+void pattern_ffi_vec_vec3_destroy(my_library_vec_vec3f32 vec);
+
+/// Frees a string returned by this library.
+void pattern_ffi_string_destroy(my_library_utf8_string s);
+
+/// Returns the message of the last error or panic on this thread.
+///
+/// The string is valid until the next error on this thread, and empty if there was none.
+my_library_utf8_str pattern_last_error_message();
+
+unsigned __int128 primitive_u128(unsigned __int128 x);
+
+__int128 primitive_i128(__int128 x);
+
 
 #ifdef __cplusplus
 }
@@ -2,6 +2,42 @@
 
 #include "my_header.h"
 
+uint32_t add_offset(uint32_t x, const void* data) {
+    return x + *(const uint32_t*) data;
+}
+
+void reset_offset(const void* data) {
+    *(uint32_t*) data = 0;
+}
+
 int main(int argc, char *argv[]) {
+    my_library_tupled tupled_in = { 21 };
+
+    if (primitive_u32(0) != UINT32_MAX) return 1;
+    if (primitive_u128((unsigned __int128) UINT64_MAX + 1) != (unsigned __int128) UINT64_MAX * 2 + 2) return 1;
+    if (primitive_i128(-((__int128) INT64_MAX) - 2) != (__int128) INT64_MAX + 2) return 1;
+    if (primitive_char('a') != 'A') return 1;
+    if (MY_LIBRARY_U128 != UINT64_MAX) return 1;
+    if (tupled(tupled_in).x0 != 42) return 2;
+
+    my_library_vecu32 vec = pattern_ffi_vec_1(3);
+    if (vec.len != 3 || vec.data[2] != 2) return 3;
+    pattern_ffi_vec_u32_destroy(vec);
+
+    my_library_utf8str str = { (const uint8_t*) "a\0b", 3 };
+    my_library_utf8string string = pattern_ffi_string_1(str);
+    if (string.len != 3 || string.data[0] != 'A' || string.data[1] != 0) return 4;
+    pattern_ffi_string_destroy(string);
+
+    my_library_resultu32ffierror result = pattern_ffi_result_1(21);
+    if (result.err != MY_LIBRARY_FFIERROR_OK || result.t != 42) return 5;
+    if (pattern_ffi_result_1(UINT32_MAX).err != MY_LIBRARY_FFIERROR_FAIL) return 6;
+    if (pattern_last_error_message().len == 0) return 7;
+
+    uint32_t offset = 10;
+    my_library_mycallbackdata callback = { add_offset, &offset, reset_offset };
+    if (pattern_callback_with_data_1(callback, 3) != 13 || offset != 0) return 8;
+
     printf("C compiled.\n");
-}
\ No newline at end of file
+    return 0;
+}
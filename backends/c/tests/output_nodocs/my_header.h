@@ -7,6 +7,7 @@
 extern "C" {
 #endif
 
+#include <stddef.h>
 #include <stdint.h>
 #include <stdbool.h>
 
@@ -15,6 +16,11 @@ extern "C" {
 const uint8_t MY_LIBRARY_U8 = 255;
 const float MY_LIBRARY_F32_MIN_POSITIVE = 0.000000000000000000000000000000000000011754944;
 const int32_t MY_LIBRARY_COMPUTED_I32 = -2147483647;
+const size_t MY_LIBRARY_USIZE = 1048576;
+const uint32_t MY_LIBRARY_CHAR = 955;
+#define MY_LIBRARY_VERSION "1.2.3"
+const uint32_t MY_LIBRARY_COMMON_U32 = 42;
+const unsigned __int128 MY_LIBRARY_U128 = 18446744073709551615ULL;
 
 typedef enum my_library_enumdocumented
     {
@@ -28,6 +34,14 @@ typedef enum my_library_enumrenamed
     MY_LIBRARY_ENUMRENAMED_X = 0,
     } my_library_enumrenamed;
 
+typedef int8_t my_library_enumrepri8;
+enum
+    {
+    MY_LIBRARY_ENUMREPRI8_NEGATIVE = -1,
+    MY_LIBRARY_ENUMREPRI8_ZERO = 0,
+    MY_LIBRARY_ENUMREPRI8_POSITIVE = 5,
+    };
+
 typedef struct my_library_generic2u8 my_library_generic2u8;
 typedef struct my_library_generic3 my_library_generic3;
 typedef struct my_library_generic4 my_library_generic4;
@@ -42,6 +56,19 @@ typedef enum my_library_ffierror
     MY_LIBRARY_FFIERROR_FAIL = 300,
     } my_library_ffierror;
 
+typedef struct my_library_utf8str
+    {
+    const uint8_t* data;
+    uint64_t len;
+    } my_library_utf8str;
+
+typedef struct my_library_utf8string
+    {
+    uint8_t* data;
+    uint64_t len;
+    uint64_t capacity;
+    } my_library_utf8string;
+
 typedef struct my_library_extratypef32
     {
     float x;
@@ -124,8 +151,45 @@ typedef struct my_library_weird1u32
     uint32_t x;
     } my_library_weird1u32;
 
+typedef uint8_t my_library_taggedshapetag;
+enum
+    {
+    MY_LIBRARY_TAGGEDSHAPETAG_EMPTY = 0,
+    MY_LIBRARY_TAGGEDSHAPETAG_CIRCLE = 1,
+    MY_LIBRARY_TAGGEDSHAPETAG_RECT = 2,
+    };
+
+typedef struct my_library_taggedshapecircle
+    {
+    float x0;
+    } my_library_taggedshapecircle;
+
+typedef struct my_library_taggedshaperect
+    {
+    float width;
+    float height;
+    } my_library_taggedshaperect;
+
+typedef union my_library_taggedshapepayload
+    {
+    my_library_taggedshapecircle Circle;
+    my_library_taggedshaperect Rect;
+    } my_library_taggedshapepayload;
+
+typedef struct my_library_taggedshape
+    {
+    uint8_t tag;
+    my_library_taggedshapepayload payload;
+    } my_library_taggedshape;
+
 typedef uint8_t (*my_library_fptr_fn_u8_rval_u8)(uint8_t x0);
 
+typedef struct my_library_resultu32ffierror
+    {
+    uint32_t t;
+    my_library_ffierror err;
+    } my_library_resultu32ffierror;
+
 typedef uint8_t (*my_library_callbacku8)(uint8_t value);
 
 typedef uint32_t (*my_library_mycallback)(uint32_t value);
@@ -152,6 +216,44 @@ typedef struct my_library_weird2u8
     const uint8_t* r;
     } my_library_weird2u8;
 
+typedef union my_library_unionbits
+    {
+    float f;
+    uint32_t u;
+    uint8_t bytes[4];
+    } my_library_unionbits;
+
+typedef uint32_t my_library_taggedinlinetag;
+enum
+    {
+    MY_LIBRARY_TAGGEDINLINETAG_A = 0,
+    MY_LIBRARY_TAGGEDINLINETAG_B = 1,
+    MY_LIBRARY_TAGGEDINLINETAG_C = 10,
+    };
+
+typedef struct my_library_taggedinlinea
+    {
+    uint32_t tag;
+    uint8_t x0;
+    } my_library_taggedinlinea;
+
+typedef struct my_library_taggedinlineb
+    {
+    uint32_t tag;
+    my_library_vec3f32 x;
+    } my_library_taggedinlineb;
+
+typedef union my_library_taggedinline
+    {
+    uint32_t tag;
+    my_library_taggedinlinea A;
+    my_library_taggedinlineb B;
+    } my_library_taggedinline;
+
+typedef void (*my_library_fptr_fn_pconst)(const void* x0);
+
+typedef uint32_t (*my_library_fptr_fn_u32_pconst__rval_u32)(uint32_t x0, const void* x1);
+
 typedef struct my_library_slicebool
     {
     const uint8_t* data;
@@ -182,6 +284,13 @@ typedef struct my_library_slicemutu8
     uint64_t len;
     } my_library_slicemutu8;
 
+typedef struct my_library_vecu32
+    {
+    uint32_t* data;
+    uint64_t len;
+    uint64_t capacity;
+    } my_library_vecu32;
+
 typedef struct my_library_optioninner
     {
     my_library_inner t;
@@ -220,13 +329,31 @@ typedef struct my_library_slicemutvec
     uint64_t len;
     } my_library_slicemutvec;
 
+typedef struct my_library_vecvec3f32
+    {
+    my_library_vec3f32* data;
+    uint64_t len;
+    uint64_t capacity;
+    } my_library_vecvec3f32;
+
 typedef uint8_t (*my_library_callbackffislice)(my_library_sliceu8 slice);
 
 typedef void (*my_library_callbackslicemut)(my_library_slicemutu8 slice);
 
+typedef struct my_library_mycallbackdata
+    {
+    my_library_fptr_fn_u32_pconst__rval_u32 callback;
+    const void* data;
+    my_library_fptr_fn_pconst destructor;
+    } my_library_mycallbackdata;
+
 typedef my_library_vec3f32 (*my_library_callbackhugevecslice)(my_library_slicevec3f32 slice);
 
 
+static const uint8_t MY_LIBRARY_ARRAY_U8[4] = { 1, 2, 3, 4 };
+
+static const my_library_vec3f32 MY_LIBRARY_VEC3_ONE = { 1, 1, 1 };
+
 void primitive_void();
 void primitive_void2();
 bool primitive_bool(bool x);
@@ -238,6 +365,9 @@ int8_t primitive_i8(int8_t x);
 int16_t primitive_i16(int16_t x);
 int32_t primitive_i32(int32_t x);
 int64_t primitive_i64(int64_t x);
+size_t primitive_usize(size_t x);
+intptr_t primitive_isize(intptr_t x);
+uint32_t primitive_char(uint32_t x);
 int64_t many_args_5(int64_t x0, int64_t x1, int64_t x2, int64_t x3, int64_t x4);
 int64_t many_args_10(int64_t x0, int64_t x1, int64_t x2, int64_t x3, int64_t x4, int64_t x5, int64_t x6, int64_t x7, int64_t x8, int64_t x9);
 const int64_t* ptr(const int64_t* x);
@@ -266,12 +396,17 @@ my_library_vec namespaced_type(my_library_vec x);
 my_library_optionvec namespaced_inner_option(my_library_optionvec x);
 my_library_slicevec namespaced_inner_slice(my_library_slicevec x);
 my_library_slicemutvec namespaced_inner_slice_mut(my_library_slicemutvec x);
+my_library_vec namespaced_function_renamed(my_library_vec x);
 my_library_ffierror panics();
 my_library_enumrenamed renamed(my_library_structrenamed x);
 void sleep(uint64_t millis);
 bool weird_1(my_library_weird1u32 x, my_library_weird2u8 y);
 void visibility(my_library_visibility1 x, my_library_visibility2 y);
 my_library_tupled repr_transparent(my_library_tupled x, const my_library_tupled* r);
+my_library_taggedshape tagged_union_1(my_library_taggedshape x);
+uint32_t tagged_union_2(const my_library_taggedinline* x);
+uint32_t union_1(my_library_unionbits x);
+my_library_enumrepri8 enum_repr_1(my_library_enumrepri8 x);
 uint32_t pattern_ascii_pointer_1(const char* x);
 const char* pattern_ascii_pointer_2();
 uint32_t pattern_ascii_pointer_len(const char* x, my_library_useasciistringpattern y);
@@ -285,8 +420,15 @@ void pattern_ffi_slice_5(const my_library_sliceu8* slice, my_library_slicemutu8*
 void pattern_ffi_slice_6(const my_library_slicemutu8* slice, my_library_callbacku8 callback);
 uint8_t pattern_ffi_slice_delegate(my_library_callbackffislice callback);
 my_library_vec3f32 pattern_ffi_slice_delegate_huge(my_library_callbackhugevecslice callback);
+uint32_t pattern_ffi_str_1(my_library_utf8str x);
+my_library_utf8string pattern_ffi_string_1(my_library_utf8str x);
+my_library_utf8string pattern_ffi_string_2();
+my_library_vecu32 pattern_ffi_vec_1(uint32_t len);
+my_library_vecvec3f32 pattern_ffi_vec_2(float x, uint32_t len);
+uint32_t pattern_ffi_vec_3(my_library_vecu32 vec);
 my_library_optioninner pattern_ffi_option_1(my_library_optioninner ffi_slice);
 my_library_inner pattern_ffi_option_2(my_library_optioninner ffi_slice);
+my_library_resultu32ffierror pattern_ffi_result_1(uint32_t x);
 uint8_t pattern_ffi_bool(uint8_t ffi_bool);
 char pattern_ffi_cchar(char ffi_cchar);
 const char* pattern_ffi_cchar_const_pointer(const char* ffi_cchar);
@@ -294,12 +436,14 @@ char* pattern_ffi_cchar_mut_pointer(char* ffi_cchar);
 uint64_t pattern_api_guard();
 uint32_t pattern_callback_1(my_library_mycallback callback, uint32_t x);
 my_library_mycallbackvoid pattern_callback_2(my_library_mycallbackvoid callback);
+uint32_t pattern_callback_with_data_1(my_library_mycallbackdata callback, uint32_t x);
 my_library_ffierror simple_service_destroy(my_library_simpleservice** context);
 my_library_ffierror simple_service_new_with(my_library_simpleservice** context, uint32_t some_value);
 my_library_ffierror simple_service_new_without(my_library_simpleservice** context);
 my_library_ffierror simple_service_new_with_string(my_library_simpleservice** context, const char* ascii);
 my_library_ffierror simple_service_new_failing(my_library_simpleservice** context, uint8_t some_value);
 my_library_ffierror simple_service_method_result(const my_library_simpleservice* context, uint32_t anon1);
+my_library_resultu32ffierror simple_service_method_result_value(const my_library_simpleservice* context, uint32_t x);
 uint32_t simple_service_method_value(const my_library_simpleservice* context, uint32_t x);
 void simple_service_method_void(const my_library_simpleservice* context);
 uint8_t simple_service_method_mut_self(my_library_simpleservice* context, my_library_sliceu8 slice);
@@ -312,6 +456,7 @@ my_library_ffierror simple_service_method_mut_self_no_error(my_library_simpleser
 my_library_sliceu32 simple_service_return_slice(my_library_simpleservice* context);
 my_library_slicemutu32 simple_service_return_slice_mut(my_library_simpleservice* context);
 const char* simple_service_return_string(my_library_simpleservice* context);
+my_library_utf8string simple_service_return_string_utf8(my_library_simpleservice* context, my_library_utf8str suffix);
 my_library_ffierror simple_service_method_void_ffi_error(my_library_simpleservice* context);
 my_library_ffierror simple_service_method_callback(my_library_simpleservice* context, my_library_mycallback callback);
 my_library_ffierror simple_service_lt_destroy(my_library_simpleservicelifetime** context);
@@ -320,6 +465,12 @@ void simple_service_lt_method_lt(my_library_simpleservicelifetime* context, my_l
 void simple_service_lt_method_lt2(my_library_simpleservicelifetime* context, my_library_slicebool slice);
 const char* simple_service_lt_return_string_accept_slice(my_library_simpleservicelifetime* anon0, my_library_sliceu8 anon1);
 my_library_ffierror simple_service_lt_method_void_ffi_error(my_library_simpleservicelifetime* context);
+void pattern_ffi_vec_u32_destroy(my_library_vecu32 vec);
+void pattern_ffi_vec_vec3_destroy(my_library_vecvec3f32 vec);
+void pattern_ffi_string_destroy(my_library_utf8string s);
+my_library_utf8str pattern_last_error_message();
+unsigned __int128 primitive_u128(unsigned __int128 x);
+__int128 primitive_i128(__int128 x);
 
 #ifdef __cplusplus
 }
@@ -1,5 +1,9 @@
+#[cfg(feature = "serde")]
+use serde::Deserialize;
+
 /// Configures Python code generation.
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Deserialize), serde(default))]
 pub struct Config {
     /// How to name the function responsible for loading the DLL, e.g., `init_api`.
     pub init_api_function_name: String,
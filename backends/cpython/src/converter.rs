@@ -1,4 +1,4 @@
-use interoptopus::lang::c::{CType, ConstantValue, Documentation, FnPointerType, PrimitiveType, PrimitiveValue};
+use interoptopus::lang::c::{CType, ConstantValue, Documentation, EnumType, FnPointerType, PrimitiveType, PrimitiveValue};
 use interoptopus::patterns::TypePattern;
 use interoptopus::util::safe_name;
 use std::ops::Deref;
@@ -25,6 +25,11 @@ impl Converter {
                 PrimitiveType::I16 => "int".to_string(),
                 PrimitiveType::I32 => "int".to_string(),
                 PrimitiveType::I64 => "int".to_string(),
+                PrimitiveType::U128 => "int".to_string(),
+                PrimitiveType::I128 => "int".to_string(),
+                PrimitiveType::USize => "int".to_string(),
+                PrimitiveType::ISize => "int".to_string(),
+                PrimitiveType::Char => "int".to_string(),
                 PrimitiveType::F32 => "float".to_string(),
                 PrimitiveType::F64 => "float".to_string(),
             },
@@ -40,6 +45,8 @@ impl Converter {
             },
             CType::Enum(_) => "ctypes.c_int".to_string(), // is this correct?
             CType::Composite(x) => x.rust_name().to_string(),
+            CType::Union(x) => x.rust_name().to_string(),
+            CType::TaggedUnion(x) => x.rust_name().to_string(),
             CType::Pattern(x) => match x {
                 TypePattern::AsciiPointer => "str".to_string(),
                 TypePattern::Option(c) => c.rust_name().to_string(),
@@ -60,6 +67,12 @@ impl Converter {
                     }
                     res
                 }
+                TypePattern::Vec(c) => c.rust_name().to_string(),
+                TypePattern::Str(_) => "str".to_string(),
+                // Functions convert returned strings, but only the destructor accepts them.
+                TypePattern::String(c) if is_parameter => c.rust_name().to_string(),
+                TypePattern::String(_) => "str".to_string(),
+                TypePattern::Result(x) => self.to_type_hint(x.value_type(), is_parameter),
                 TypePattern::CChar => "ctypes.c_char".to_string(),
                 _ => "".to_string(),
             },
@@ -67,6 +80,14 @@ impl Converter {
         }
     }
 
+    /// Enums are a C `int` unless they have an explicit integer `#[repr()]`.
+    pub fn enum_to_ctypes_name(&self, the_enum: &EnumType) -> String {
+        match the_enum.repr() {
+            PrimitiveType::I32 => "ctypes.c_int".to_string(),
+            x => self.to_ctypes_name(&CType::Primitive(x), false),
+        }
+    }
+
     pub fn to_ctypes_name(&self, the_type: &CType, with_type_annotations: bool) -> String {
         match the_type {
             CType::Primitive(x) => match x {
@@ -80,11 +101,17 @@ impl Converter {
                 PrimitiveType::I16 => "ctypes.c_int16".to_string(),
                 PrimitiveType::I32 => "ctypes.c_int32".to_string(),
                 PrimitiveType::I64 => "ctypes.c_int64".to_string(),
+                PrimitiveType::U128 | PrimitiveType::I128 => panic!("128-bit integers can't be used from Python, ctypes has no equivalent; `validate()` reports these."),
+                PrimitiveType::USize => "ctypes.c_size_t".to_string(),
+                PrimitiveType::ISize => "ctypes.c_ssize_t".to_string(),
+                PrimitiveType::Char => "ctypes.c_uint32".to_string(),
                 PrimitiveType::F32 => "ctypes.c_float".to_string(),
                 PrimitiveType::F64 => "ctypes.c_double".to_string(),
             },
-            CType::Enum(_) => "ctypes.c_int".to_string(), // is this correct?
+            CType::Enum(x) => self.enum_to_ctypes_name(x),
             CType::Composite(x) => x.rust_name().to_string(),
+            CType::Union(x) => x.rust_name().to_string(),
+            CType::TaggedUnion(x) => x.rust_name().to_string(),
             CType::Array(x) => format!("{} * {}", self.to_ctypes_name(x.array_type(), with_type_annotations), x.len()),
             CType::Opaque(_) => "ERROR".to_string(),
             CType::FnPointer(x) => format!("callbacks.{}", safe_name(&x.internal_name())), //self.fnpointer_to_typename(x),
@@ -101,13 +128,18 @@ impl Converter {
             CType::Pattern(pattern) => match pattern {
                 TypePattern::AsciiPointer => self.to_ctypes_name(&pattern.fallback_type(), with_type_annotations),
                 TypePattern::APIVersion => "ctypes.c_uint64".to_string(),
-                TypePattern::FFIErrorEnum(_) => "ctypes.c_int".to_string(),
+                TypePattern::FFIErrorEnum(x) => self.enum_to_ctypes_name(x.the_enum()),
                 TypePattern::Slice(c) => c.rust_name().to_string(),
                 TypePattern::SliceMut(c) => c.rust_name().to_string(),
+                TypePattern::Vec(c) => c.rust_name().to_string(),
+                TypePattern::Str(c) => c.rust_name().to_string(),
+                TypePattern::String(c) => c.rust_name().to_string(),
                 TypePattern::Option(x) => x.rust_name().to_string(),
+                TypePattern::Result(x) => x.the_type().rust_name().to_string(),
                 TypePattern::Bool => "ctypes.c_uint8".to_string(),
                 TypePattern::CChar => "ctypes.c_char".to_string(),
                 TypePattern::NamedCallback(x) => format!("callbacks.{}", safe_name(&x.fnpointer().internal_name())),
+                TypePattern::CallbackWithData(x) => x.the_type().rust_name().to_string(),
             },
         }
     }
@@ -144,9 +176,43 @@ impl Converter {
                 PrimitiveValue::I16(x) => format!("{}", x),
                 PrimitiveValue::I32(x) => format!("{}", x),
                 PrimitiveValue::I64(x) => format!("{}", x),
+                PrimitiveValue::U128(x) => format!("{}", x),
+                PrimitiveValue::I128(x) => format!("{}", x),
+                PrimitiveValue::USize(x) => format!("{}", x),
+                PrimitiveValue::ISize(x) => format!("{}", x),
+                PrimitiveValue::Char(x) => format!("{}", *x as u32),
                 PrimitiveValue::F32(x) => format!("{}", x),
                 PrimitiveValue::F64(x) => format!("{}", x),
             },
+            ConstantValue::String(x) => {
+                let mut rval = String::from("\"");
+                for c in x.chars() {
+                    match c {
+                        '"' => rval.push_str("\\\""),
+                        '\\' => rval.push_str("\\\\"),
+                        '\n' => rval.push_str("\\n"),
+                        '\r' => rval.push_str("\\r"),
+                        '\t' => rval.push_str("\\t"),
+                        c if c.is_control() => rval.push_str(&format!("\\u{:04x}", c as u32)),
+                        c => rval.push(c),
+                    }
+                }
+                rval.push('"');
+                rval
+            }
+            ConstantValue::Array(the_type, values) => {
+                let values = values.iter().map(|x| self.constant_value_to_value(x)).collect::<Vec<_>>();
+                format!("({})({})", self.to_ctypes_name(&CType::Array(the_type.clone()), false), values.join(", "))
+            }
+            ConstantValue::Composite(the_type, values) => {
+                let fields = the_type
+                    .fields()
+                    .iter()
+                    .zip(values)
+                    .map(|(field, value)| format!("{}={}", field.name(), self.constant_value_to_value(value)))
+                    .collect::<Vec<_>>();
+                format!("{}({})", the_type.rust_name(), fields.join(", "))
+            }
         }
     }
 
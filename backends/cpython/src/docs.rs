@@ -47,8 +47,11 @@ impl<'a, W: PythonWriter> DocGenerator<'a, W> {
         indented!(w, r#"### Classes"#)?;
         indented!(w, r#"Methods operating on common state."#)?;
 
-        for pattern in self.inventory.patterns().iter().map(|x| match x {
-            LibraryPattern::Service(s) => s,
+        for pattern in self.inventory.patterns().iter().filter_map(|x| match x {
+            LibraryPattern::Service(s) => Some(s),
+            LibraryPattern::Vec(_) => None,
+            LibraryPattern::String(_) => None,
+            LibraryPattern::LastError(_) => None,
         }) {
             let prefix = pattern.common_prefix();
             let doc = pattern.the_type().meta().documentation().lines().first().cloned().unwrap_or_default();
@@ -98,6 +101,41 @@ impl<'a, W: PythonWriter> DocGenerator<'a, W> {
                     let c = p.fallback_type().as_composite_type().cloned().unwrap();
                     indented!(w, r#" - **[{}](#{})** - A pointer and length of un-owned elements."#, c.rust_name(), c.rust_name())?;
                 }
+                CType::Pattern(p @ TypePattern::Vec(_)) => {
+                    let c = p.fallback_type().as_composite_type().cloned().unwrap();
+                    indented!(
+                        w,
+                        r#" - **[{}](#{})** - Elements owned by the library, freed when collected."#,
+                        c.rust_name(),
+                        c.rust_name()
+                    )?;
+                }
+                CType::Pattern(p @ TypePattern::Str(_)) => {
+                    let c = p.fallback_type().as_composite_type().cloned().unwrap();
+                    indented!(w, r#" - **[{}](#{})** - A UTF-8 `str` passed to the library."#, c.rust_name(), c.rust_name())?;
+                }
+                CType::Pattern(p @ TypePattern::String(_)) => {
+                    let c = p.fallback_type().as_composite_type().cloned().unwrap();
+                    indented!(
+                        w,
+                        r#" - **[{}](#{})** - A UTF-8 `str` owned by the library, freed once converted."#,
+                        c.rust_name(),
+                        c.rust_name()
+                    )?;
+                }
+                CType::Pattern(p @ TypePattern::Result(_)) => {
+                    let c = p.fallback_type().as_composite_type().cloned().unwrap();
+                    indented!(w, r#" - **[{}](#{})** - A value or an error, unwrapped when returned."#, c.rust_name(), c.rust_name())?;
+                }
+                CType::Pattern(p @ TypePattern::CallbackWithData(_)) => {
+                    let c = p.fallback_type().as_composite_type().cloned().unwrap();
+                    indented!(
+                        w,
+                        r#" - **[{}](#{})** - A callback carrying user data, created from a Python callable."#,
+                        c.rust_name(),
+                        c.rust_name()
+                    )?;
+                }
                 _ => continue,
             }
         }
@@ -113,6 +151,11 @@ impl<'a, W: PythonWriter> DocGenerator<'a, W> {
                 CType::Composite(e) => self.write_composite(w, e)?,
                 CType::Pattern(p @ TypePattern::Option(_)) => self.write_composite(w, p.fallback_type().as_composite_type().unwrap())?,
                 CType::Pattern(p @ TypePattern::Slice(_)) => self.write_composite(w, p.fallback_type().as_composite_type().unwrap())?,
+                CType::Pattern(p @ TypePattern::Vec(_)) => self.write_composite(w, p.fallback_type().as_composite_type().unwrap())?,
+                CType::Pattern(p @ TypePattern::Str(_)) => self.write_composite(w, p.fallback_type().as_composite_type().unwrap())?,
+                CType::Pattern(p @ TypePattern::String(_)) => self.write_composite(w, p.fallback_type().as_composite_type().unwrap())?,
+                CType::Pattern(p @ TypePattern::Result(_)) => self.write_composite(w, p.fallback_type().as_composite_type().unwrap())?,
+                CType::Pattern(p @ TypePattern::CallbackWithData(_)) => self.write_composite(w, p.fallback_type().as_composite_type().unwrap())?,
                 _ => continue,
             };
 
@@ -227,8 +270,11 @@ impl<'a, W: PythonWriter> DocGenerator<'a, W> {
     pub fn write_services(&self, w: &mut IndentWriter) -> Result<(), Error> {
         indented!(w, r#"# Services"#)?;
 
-        for pattern in self.inventory.patterns().iter().map(|x| match x {
-            LibraryPattern::Service(s) => s,
+        for pattern in self.inventory.patterns().iter().filter_map(|x| match x {
+            LibraryPattern::Service(s) => Some(s),
+            LibraryPattern::Vec(_) => None,
+            LibraryPattern::String(_) => None,
+            LibraryPattern::LastError(_) => None,
         }) {
             let prefix = pattern.common_prefix();
             let doc = pattern.the_type().meta().documentation().lines();
@@ -133,14 +133,16 @@
 //!
 //! ```
 
+use interoptopus::validate::Validation;
 use interoptopus::writer::IndentWriter;
 use interoptopus::Interop;
-use interoptopus::{Error, Inventory};
+use interoptopus::{Error, ErrorContext, Inventory};
 
 mod config;
 mod converter;
 mod docs;
 mod testing;
+mod validate;
 mod writer;
 
 pub use config::{Config, DocConfig};
@@ -168,7 +170,14 @@ impl Generator {
 
 impl Interop for Generator {
     fn write_to(&self, w: &mut IndentWriter) -> Result<(), Error> {
-        self.write_all(w)
+        self.validate()
+            .into_result()
+            .and_then(|_| self.write_all(w))
+            .map_err(|e| e.with_context(ErrorContext::Backend("Python".to_string())))
+    }
+
+    fn validate(&self) -> Validation {
+        validate::validate(self)
     }
 }
 
@@ -7,7 +7,7 @@ use std::process::Command;
 
 /// If `python` is installed, run the given file from `path`, ignore and succeed otherwise.
 pub fn run_python_if_installed<P: AsRef<Path>>(path: P, file: &str) -> Result<String, Error> {
-    let child = match Command::new("python").arg(file).current_dir(path).spawn() {
+    let output = match Command::new("python").arg(file).current_dir(path).output() {
         Ok(x) => x,
         Err(x @ std::io::Error { .. }) if x.kind() == ErrorKind::NotFound => {
             return Ok("Python not found, skipped".to_string());
@@ -15,11 +15,12 @@ pub fn run_python_if_installed<P: AsRef<Path>>(path: P, file: &str) -> Result<St
         Err(x) => return Err(Error::IO(x)),
     };
 
-    let output = child.wait_with_output()?;
-
     if output.status.success() {
         Ok(String::from_utf8(output.stdout)?)
     } else {
-        Err(Error::TestFailed)
+        Err(Error::TestFailed {
+            command: format!("python {}", file),
+            output: String::from_utf8_lossy(&output.stderr).to_string(),
+        })
     }
 }
@@ -0,0 +1,19 @@
+use crate::{Generator, PythonWriter};
+use interoptopus::lang::c::PrimitiveType;
+use interoptopus::validate::Validation;
+
+/// Keywords of Python 3, which can't be used as parameter or attribute names.
+const RESERVED: &[&str] = &[
+    "False", "None", "True", "and", "as", "assert", "async", "await", "break", "class", "continue", "def", "del", "elif", "else", "except", "finally", "for", "from",
+    "global", "if", "import", "in", "is", "lambda", "nonlocal", "not", "or", "pass", "raise", "return", "try", "while", "with", "yield",
+];
+
+pub(crate) fn validate(generator: &Generator) -> Validation {
+    let inventory = generator.inventory();
+    let mut validation = inventory.validate();
+
+    validation.check_reserved_words(inventory, "Python", RESERVED);
+    validation.check_unsupported_primitives(inventory, "Python", &[PrimitiveType::U128, PrimitiveType::I128]);
+
+    validation
+}
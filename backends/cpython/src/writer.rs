@@ -1,12 +1,19 @@
 use crate::config::Config;
 use crate::converter::Converter;
-use interoptopus::lang::c::{CType, CompositeType, EnumType, Function, PrimitiveType};
+use interoptopus::lang::c::{CType, CompositeType, Constant, ConstantValue, EnumType, Field, Function, Meta, PrimitiveType, TagPlacement, TaggedUnionType, UnionType};
+use interoptopus::patterns::api_guard::APIVersion;
+use interoptopus::patterns::callbacks::CallbackWithData;
 use interoptopus::patterns::service::Service;
+use interoptopus::patterns::vec::vec_element_type;
 use interoptopus::patterns::{LibraryPattern, TypePattern};
 use interoptopus::util::{longest_common_prefix, safe_name, sort_types_by_dependencies};
 use interoptopus::writer::{IndentWriter, WriteFor};
 use interoptopus::{indented, non_service_functions, Error, Inventory};
 
+fn is_aggregate_constant(constant: &Constant) -> bool {
+    matches!(constant.value(), ConstantValue::Array(_, _) | ConstantValue::Composite(_, _))
+}
+
 /// Writes the Python file format, `impl` this trait to customize output.
 pub trait PythonWriter {
     /// Returns the user config.
@@ -57,18 +64,92 @@ pub trait PythonWriter {
 
         w.newline()?;
         for f in self.inventory().functions() {
-            if let CType::Pattern(TypePattern::FFIErrorEnum(e)) = f.signature().rval() {
-                let value = e.success_variant().value();
-                indented!(w, [_], r#"c_lib.{}.errcheck = lambda rval, _fptr, _args: _errcheck(rval, {})"#, f.name(), value)?;
+            match f.signature().rval() {
+                CType::Pattern(TypePattern::FFIErrorEnum(e)) => {
+                    let value = e.success_variant().value();
+                    indented!(w, [_], r#"c_lib.{}.errcheck = lambda rval, _fptr, _args: _errcheck(rval, {})"#, f.name(), value)?;
+                }
+                CType::Pattern(TypePattern::Result(x)) => {
+                    let value = x.error().success_variant().value();
+                    indented!(
+                        w,
+                        [_],
+                        r#"c_lib.{}.errcheck = lambda rval, _fptr, _args: _errcheck_result(rval, {})"#,
+                        f.name(),
+                        value
+                    )?;
+                }
+                _ => {}
             }
         }
 
+        self.write_api_guard(w)?;
+
+        Ok(())
+    }
+
+    /// Checks the library's API version when loading it, if it has a guard function.
+    fn write_api_guard(&self, w: &mut IndentWriter) -> Result<(), Error> {
+        let api_guard = match self
+            .inventory()
+            .functions()
+            .iter()
+            .find(|x| matches!(x.signature().rval(), CType::Pattern(TypePattern::APIVersion)))
+        {
+            Some(x) => x,
+            None => return Ok(()),
+        };
+
+        let version = APIVersion::from_inventory(self.inventory());
+        let (major, minor, hash) = (version.major(), version.minor(), version.hash());
+
+        w.newline()?;
+        indented!(w, [_], r#"api_version = c_lib.{}()"#, api_guard.name())?;
+        indented!(w, [_], r#"api_major, api_minor = api_version >> 48, (api_version >> 32) & 0xFFFF"#)?;
+        // Minor versions can't be below 0, so we leave out that check for minor version 0.
+        let minor_older = match minor {
+            0 => String::new(),
+            _ => format!("api_minor < {} or ", minor),
+        };
+        indented!(
+            w,
+            [_],
+            r#"if api_major != {} or {}(api_minor == {} and api_version != 0x{:016x}):"#,
+            major,
+            minor_older,
+            minor,
+            version.bits()
+        )?;
+        indented!(w, [_ _], r#"if (api_major, api_minor) < ({}, {}):"#, major, minor)?;
+        indented!(w, [_ _ _], r#"reason = "the library is older""#)?;
+        indented!(w, [_ _], r#"elif (api_major, api_minor) == ({}, {}):"#, major, minor)?;
+        indented!(w, [_ _ _], r#"reason = "both report the same version but differ""#)?;
+        indented!(w, [_ _], r#"else:"#)?;
+        indented!(w, [_ _ _], r#"reason = "the bindings are older""#)?;
+        indented!(w, [_ _], r#"raise ImportError(f"Library has API version {{api_major}}.{{api_minor}} (hash {{api_version & 0xFFFFFFFF}}), bindings need {}.{} (hash {}); {{reason}}. You probably forgot to update / copy either the bindings or the library.")"#, major, minor, hash)?;
+
         Ok(())
     }
 
     fn write_constants(&self, w: &mut IndentWriter) -> Result<(), Error> {
-        for c in self.inventory().constants() {
-            indented!(w, r#"{} = {}"#, c.name(), self.converter().constant_value_to_value(c.value()))?;
+        for c in self.inventory().constants().iter().filter(|x| !is_aggregate_constant(x)) {
+            indented!(w, r#"{} = {}"#, c.name(), self.converter().constant_value_to_value(c.value())).map_err(|e| e.with_item(format!("const {}", c.name())))?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes struct and array constants, which need the classes of their types to exist.
+    fn write_aggregate_constants(&self, w: &mut IndentWriter) -> Result<(), Error> {
+        let constants = self.inventory().constants().iter().filter(|x| is_aggregate_constant(x)).collect::<Vec<_>>();
+
+        for c in &constants {
+            indented!(w, r#"{} = {}"#, c.name(), self.converter().constant_value_to_value(c.value())).map_err(|e| e.with_item(format!("const {}", c.name())))?;
+        }
+
+        if !constants.is_empty() {
+            w.newline()?;
+            w.newline()?;
         }
 
         Ok(())
@@ -79,20 +160,27 @@ pub trait PythonWriter {
         let sorted_types = sort_types_by_dependencies(all_types);
 
         for t in &sorted_types {
-            match t {
-                CType::Composite(c) => self.write_struct(w, c, WriteFor::Code)?,
-                CType::Enum(e) => self.write_enum(w, e, WriteFor::Code)?,
+            let written = match t {
+                CType::Composite(c) => self.write_struct(w, c, WriteFor::Code),
+                CType::Enum(e) => self.write_enum(w, e, WriteFor::Code),
+                CType::Union(u) => self.write_union(w, u, WriteFor::Code),
+                CType::TaggedUnion(t) => self.write_tagged_union(w, t),
                 CType::Pattern(p) => match p {
-                    TypePattern::FFIErrorEnum(e) => self.write_enum(w, e.the_enum(), WriteFor::Code)?,
-                    TypePattern::Slice(c) => self.write_slice(w, c, false)?,
-                    TypePattern::SliceMut(c) => self.write_slice(w, c, true)?,
-                    TypePattern::Option(c) => {
-                        self.write_option(w, c)?;
-                    }
+                    TypePattern::FFIErrorEnum(e) => self.write_enum(w, e.the_enum(), WriteFor::Code),
+                    TypePattern::Slice(c) => self.write_slice(w, c, false),
+                    TypePattern::SliceMut(c) => self.write_slice(w, c, true),
+                    TypePattern::Vec(c) => self.write_vec(w, c),
+                    TypePattern::Str(c) => self.write_str(w, c),
+                    TypePattern::String(c) => self.write_string(w, c),
+                    TypePattern::Option(c) => self.write_option(w, c),
+                    TypePattern::Result(x) => self.write_struct(w, x.the_type(), WriteFor::Code),
+                    TypePattern::CallbackWithData(x) => self.write_callback_with_data(w, x),
                     _ => continue,
                 },
                 _ => continue,
-            }
+            };
+
+            written.map_err(|e| e.with_item(format!("type {}", t.name_within_lib())))?;
 
             w.newline()?;
             w.newline()?;
@@ -102,9 +190,18 @@ pub trait PythonWriter {
     }
 
     fn write_struct(&self, w: &mut IndentWriter, c: &CompositeType, write_for: WriteFor) -> Result<(), Error> {
-        let documentation = c.meta().documentation().lines().join("\n");
+        self.write_fields_class(w, "ctypes.Structure", c.rust_name(), c.meta(), c.fields(), write_for)
+    }
 
-        indented!(w, r#"class {}(ctypes.Structure):"#, c.rust_name())?;
+    fn write_union(&self, w: &mut IndentWriter, u: &UnionType, write_for: WriteFor) -> Result<(), Error> {
+        self.write_fields_class(w, "ctypes.Union", u.rust_name(), u.meta(), u.fields(), write_for)
+    }
+
+    /// Writes a class deriving from the ctypes `base` with the given fields, shared by structs and unions.
+    fn write_fields_class(&self, w: &mut IndentWriter, base: &str, name: &str, meta: &Meta, fields: &[Field], write_for: WriteFor) -> Result<(), Error> {
+        let documentation = meta.documentation().lines().join("\n");
+
+        indented!(w, r#"class {}({}):"#, name, base)?;
         if !documentation.is_empty() && write_for == WriteFor::Code {
             indented!(w, [_], r#""""{}""""#, documentation)?;
         }
@@ -114,15 +211,14 @@ pub trait PythonWriter {
             indented!(w, [_], r#"# These fields represent the underlying C data layout"#)?;
         }
         indented!(w, [_], r#"_fields_ = ["#)?;
-        for f in c.fields() {
+        for f in fields {
             let type_name = self.converter().to_ctypes_name(f.the_type(), true);
             indented!(w, [_ _], r#"("{}", {}),"#, f.name(), type_name)?;
         }
         indented!(w, [_], r#"]"#)?;
 
         // Ctor
-        let extra_args = c
-            .fields()
+        let extra_args = fields
             .iter()
             .map(|x| {
                 let type_hint_in = self.converter().to_type_hint_in(x.the_type(), false);
@@ -132,12 +228,12 @@ pub trait PythonWriter {
             .collect::<Vec<_>>()
             .join(", ");
 
-        if !c.fields().is_empty() {
+        if !fields.is_empty() {
             w.newline()?;
             indented!(w, [_], r#"def __init__(self, {}):"#, extra_args)?;
 
             if write_for == WriteFor::Code {
-                for field in c.fields().iter() {
+                for field in fields.iter() {
                     indented!(w, [_ _], r#"if {} is not None:"#, field.name())?;
                     indented!(w, [_ _ _], r#"self.{} = {}"#, field.name(), field.name())?;
                 }
@@ -151,7 +247,7 @@ pub trait PythonWriter {
         }
 
         // Fields
-        for f in c.fields() {
+        for f in fields {
             let documentation = f.documentation().lines().join("\n");
 
             w.newline()?;
@@ -167,8 +263,8 @@ pub trait PythonWriter {
             }
 
             match f.the_type() {
-                CType::Pattern(_) => indented!(w, [_ _], r#"return ctypes.Structure.__get__(self, "{}")"#, f.name())?,
-                _ => indented!(w, [_ _], r#"return ctypes.Structure.__get__(self, "{}")"#, f.name())?,
+                CType::Pattern(_) => indented!(w, [_ _], r#"return {}.__get__(self, "{}")"#, base, f.name())?,
+                _ => indented!(w, [_ _], r#"return {}.__get__(self, "{}")"#, base, f.name())?,
             }
 
             w.newline()?;
@@ -178,7 +274,7 @@ pub trait PythonWriter {
             if !documentation.is_empty() {
                 indented!(w, [_ _], r#""""{}""""#, documentation)?;
             }
-            indented!(w, [_ _], r#"return ctypes.Structure.__set__(self, "{}", value)"#, f.name())?;
+            indented!(w, [_ _], r#"return {}.__set__(self, "{}", value)"#, base, f.name())?;
         }
 
         Ok(())
@@ -204,6 +300,108 @@ pub trait PythonWriter {
         Ok(())
     }
 
+    fn write_tagged_union(&self, w: &mut IndentWriter, t: &TaggedUnionType) -> Result<(), Error> {
+        let name = t.rust_name();
+        let tag_enum = t.tag_enum();
+        let tag_type = self.converter().to_ctypes_name(&CType::Primitive(t.tag()), true);
+        let documentation = t.meta().documentation().lines().join("\n");
+
+        self.write_enum(w, &tag_enum, WriteFor::Code)?;
+        w.newline()?;
+        w.newline()?;
+
+        for variant in t.payload_variants() {
+            self.write_struct(w, &t.variant_composite(variant), WriteFor::Code)?;
+            w.newline()?;
+            w.newline()?;
+        }
+
+        // Payloads live in a separate union for `#[repr(C, u8)]`, but share a union with the tag for `#[repr(u8)]`.
+        let payload_access = match t.placement() {
+            TagPlacement::Separate => {
+                indented!(w, r#"class {}Payload(ctypes.Union):"#, name)?;
+                indented!(w, [_], r#"_fields_ = ["#)?;
+                for variant in t.payload_variants() {
+                    indented!(w, [_ _], r#"("{}", {}),"#, variant.name(), t.variant_composite(variant).rust_name())?;
+                }
+                indented!(w, [_], r#"]"#)?;
+                w.newline()?;
+                w.newline()?;
+
+                indented!(w, r#"class {}(ctypes.Structure):"#, name)?;
+                if !documentation.is_empty() {
+                    indented!(w, [_], r#""""{}""""#, documentation)?;
+                }
+                w.newline()?;
+                indented!(w, [_], r#"# These fields represent the underlying C data layout"#)?;
+                indented!(w, [_], r#"_fields_ = ["#)?;
+                indented!(w, [_ _], r#"("_tag", {}),"#, tag_type)?;
+                indented!(w, [_ _], r#"("_payload", {}Payload),"#, name)?;
+                indented!(w, [_], r#"]"#)?;
+                "_payload."
+            }
+            TagPlacement::Inline => {
+                indented!(w, r#"class {}(ctypes.Union):"#, name)?;
+                if !documentation.is_empty() {
+                    indented!(w, [_], r#""""{}""""#, documentation)?;
+                }
+                w.newline()?;
+                indented!(w, [_], r#"# These fields represent the underlying C data layout"#)?;
+                indented!(w, [_], r#"_fields_ = ["#)?;
+                indented!(w, [_ _], r#"("_tag", {}),"#, tag_type)?;
+                for variant in t.payload_variants() {
+                    indented!(w, [_ _], r#"("_{}", {}),"#, variant.name(), t.variant_composite(variant).rust_name())?;
+                }
+                indented!(w, [_], r#"]"#)?;
+                "_"
+            }
+        };
+
+        w.newline()?;
+        indented!(w, [_], r#"@property"#)?;
+        indented!(w, [_], r#"def tag(self) -> int:"#)?;
+        indented!(w, [_ _], r#""""Returns the discriminant, one of the values in `{}`.""""#, tag_enum.rust_name())?;
+        indented!(w, [_ _], r#"return self._tag"#)?;
+
+        for variant in t.variants() {
+            let variant_name = variant.name();
+            let snake_name = variant_name.to_lowercase();
+
+            w.newline()?;
+            indented!(w, [_], r#"def is_{}(self) -> bool:"#, snake_name)?;
+            indented!(w, [_ _], r#"return self._tag == {}.{}"#, tag_enum.rust_name(), variant_name)?;
+
+            if variant.fields().is_empty() {
+                w.newline()?;
+                indented!(w, [_], r#"@staticmethod"#)?;
+                indented!(w, [_], r#"def {}() -> {}:"#, snake_name, name)?;
+                indented!(w, [_ _], r#"rval = {}()"#, name)?;
+                indented!(w, [_ _], r#"rval._tag = {}.{}"#, tag_enum.rust_name(), variant_name)?;
+                indented!(w, [_ _], r#"return rval"#)?;
+                continue;
+            }
+
+            let variant_type = t.variant_composite(variant).rust_name().to_string();
+
+            w.newline()?;
+            indented!(w, [_], r#"def as_{}(self) -> {}:"#, snake_name, variant_type)?;
+            indented!(w, [_ _], r#""""Returns the payload, or raises a `ValueError` if this is a different variant.""""#)?;
+            indented!(w, [_ _], r#"if self._tag != {}.{}:"#, tag_enum.rust_name(), variant_name)?;
+            indented!(w, [_ _ _], r#"raise ValueError(f"Variant is {{self._tag}}, not {}.")"#, variant_name)?;
+            indented!(w, [_ _], r#"return self.{}{}"#, payload_access, variant_name)?;
+
+            w.newline()?;
+            indented!(w, [_], r#"@staticmethod"#)?;
+            indented!(w, [_], r#"def {}(value: {}) -> {}:"#, snake_name, variant_type, name)?;
+            indented!(w, [_ _], r#"rval = {}()"#, name)?;
+            indented!(w, [_ _], r#"rval.{}{} = value"#, payload_access, variant_name)?;
+            indented!(w, [_ _], r#"rval._tag = {}.{}"#, tag_enum.rust_name(), variant_name)?;
+            indented!(w, [_ _], r#"return rval"#)?;
+        }
+
+        Ok(())
+    }
+
     fn write_callback_helpers(&self, w: &mut IndentWriter) -> Result<(), Error> {
         indented!(w, r#"class {}:"#, self.config().callback_namespace)?;
         indented!(w, [_], r#""""Helpers to define callbacks.""""#)?;
@@ -227,7 +425,8 @@ pub trait PythonWriter {
 
     fn write_function_proxies(&self, w: &mut IndentWriter) -> Result<(), Error> {
         for function in non_service_functions(self.inventory()) {
-            self.write_function(w, function, WriteFor::Code)?;
+            self.write_function(w, function, WriteFor::Code)
+                .map_err(|e| e.with_item(format!("fn {}", function.name())))?;
         }
 
         Ok(())
@@ -270,10 +469,21 @@ pub trait PythonWriter {
                         indented!(w, [_ _], r#"{} = callbacks.{}({})"#, arg.name(), safe_name(&x.internal_name()), arg.name())?;
                         w.newline()?;
                     }
+                    TypePattern::CallbackWithData(x) => {
+                        indented!(w, [_], r#"if not isinstance({}, {}):"#, arg.name(), x.the_type().rust_name())?;
+                        indented!(w, [_ _], r#"{} = {}({})"#, arg.name(), x.the_type().rust_name(), arg.name())?;
+                        indented!(w, [_], r#"{}._hand_over()"#, arg.name())?;
+                        w.newline()?;
+                    }
                     TypePattern::AsciiPointer => {
                         indented!(w, [_], r#"if not hasattr({}, "__ctypes_from_outparam__"):"#, arg.name())?;
                         indented!(w, [_ _], r#"{} = ctypes.cast({}, ctypes.POINTER(ctypes.c_char))"#, arg.name(), arg.name())?;
                     }
+                    TypePattern::Str(t) => {
+                        indented!(w, [_], r#"if isinstance({}, str):"#, arg.name())?;
+                        indented!(w, [_ _], r#"{} = {}.from_str({})"#, arg.name(), t.rust_name(), arg.name())?;
+                        w.newline()?;
+                    }
                     TypePattern::Slice(t) | TypePattern::SliceMut(t) => {
                         let inner = self.converter().to_ctypes_name(
                             t.fields()
@@ -399,6 +609,203 @@ pub trait PythonWriter {
         Ok(())
     }
 
+    /// Writes an owned vec, which frees itself once collected or when leaving a `with` block.
+    fn write_vec(&self, w: &mut IndentWriter, c: &CompositeType) -> Result<(), Error> {
+        let data_type = vec_element_type(c);
+        let data_type_python = self.converter().to_ctypes_name(data_type, true);
+        let hint_out = self.converter().to_type_hint_out(data_type);
+        let list_hint = match self.converter().to_type_hint(data_type, false) {
+            x if x.is_empty() => "list".to_string(),
+            x => format!("typing.List[{}]", x),
+        };
+
+        let destructor = self.inventory().patterns().iter().find_map(|x| match x {
+            LibraryPattern::Vec(x) if x.the_type() == c => Some(x.destructor()),
+            _ => None,
+        });
+
+        indented!(w, r#"class {}(ctypes.Structure):"#, c.rust_name())?;
+        indented!(
+            w,
+            [_],
+            r#""""Elements owned by the library, freed once this is collected or its `with` block ends.""""#
+        )?;
+        w.newline()?;
+        indented!(w, [_], r#"# These fields represent the underlying C data layout"#)?;
+        indented!(w, [_], r#"_fields_ = ["#)?;
+        indented!(w, [_], r#"    ("data", ctypes.POINTER({})),"#, data_type_python)?;
+        indented!(w, [_], r#"    ("len", ctypes.c_uint64),"#)?;
+        indented!(w, [_], r#"    ("capacity", ctypes.c_uint64),"#)?;
+        indented!(w, [_], r#"]"#)?;
+        w.newline()?;
+        indented!(w, [_], r#"def __len__(self):"#)?;
+        indented!(w, [_ _], r#"return self.len"#)?;
+        w.newline()?;
+        indented!(w, [_], r#"def __getitem__(self, i){}:"#, hint_out)?;
+        indented!(w, [_ _], r#"if i < 0:"#)?;
+        indented!(w, [_ _ _], r#"index = self.len+i"#)?;
+        indented!(w, [_ _], r#"else:"#)?;
+        indented!(w, [_ _ _], r#"index = i"#)?;
+        w.newline()?;
+        indented!(w, [_ _], r#"if index >= self.len:"#)?;
+        indented!(w, [_ _ _], r#"raise IndexError("Index out of range")"#)?;
+        w.newline()?;
+        indented!(w, [_ _], r#"return self.data[index]"#)?;
+        w.newline()?;
+        indented!(w, [_], r#"def __iter__(self) -> typing.Iterable[{}]:"#, data_type_python)?;
+        indented!(w, [_ _], r#"return _Iter(self)"#)?;
+        w.newline()?;
+        indented!(w, [_], r#"def to_list(self) -> {}:"#, list_hint)?;
+        indented!(w, [_ _], r#""""Returns a copy of all elements, which stays valid after this vec was freed.""""#)?;
+        indented!(w, [_ _], r#"if not self.data:"#)?;
+        indented!(w, [_ _ _], r#"return []"#)?;
+
+        // Only simple types like `ctypes.c_uint32` are read as Python values, anything else would be a view into our buffer.
+        if data_type_python.starts_with("ctypes.c_") {
+            indented!(w, [_ _], r#"return self.data[:self.len]"#)?;
+        } else {
+            indented!(w, [_ _], r#"return [{}.from_buffer_copy(self.data[i]) for i in range(self.len)]"#, data_type_python)?;
+        }
+
+        w.newline()?;
+        indented!(w, [_], r#"def _forget(self):"#)?;
+        indented!(w, [_ _], r#""""Empties this vec without freeing its elements, e.g., once they were passed to the library.""""#)?;
+        indented!(w, [_ _], r#"self.data = None"#)?;
+        indented!(w, [_ _], r#"self.len = 0"#)?;
+        indented!(w, [_ _], r#"self.capacity = 0"#)?;
+
+        if let Some(destructor) = destructor {
+            w.newline()?;
+            indented!(w, [_], r#"def free(self):"#)?;
+            indented!(w, [_ _], r#""""Returns the elements to the library, afterwards this vec is empty.""""#)?;
+            indented!(w, [_ _], r#"if self.data and c_lib is not None:"#)?;
+            indented!(w, [_ _ _], r#"c_lib.{}(self)"#, destructor.name())?;
+            indented!(w, [_ _], r#"self._forget()"#)?;
+            w.newline()?;
+            indented!(w, [_], r#"def __enter__(self) -> {}:"#, c.rust_name())?;
+            indented!(w, [_ _], r#"return self"#)?;
+            w.newline()?;
+            indented!(w, [_], r#"def __exit__(self, *args):"#)?;
+            indented!(w, [_ _], r#"self.free()"#)?;
+            w.newline()?;
+            indented!(w, [_], r#"def __del__(self):"#)?;
+            indented!(w, [_ _], r#"# Views into other objects (e.g., struct fields) don't own their elements."#)?;
+            indented!(w, [_ _], r#"if self._b_needsfree_:"#)?;
+            indented!(w, [_ _ _], r#"self.free()"#)?;
+        }
+
+        Ok(())
+    }
+
+    fn write_callback_with_data(&self, w: &mut IndentWriter, x: &CallbackWithData) -> Result<(), Error> {
+        let raw = self.converter().to_ctypes_name(&CType::FnPointer(x.raw_fnpointer().clone()), false);
+
+        indented!(w, r#"class {}(ctypes.Structure):"#, x.the_type().rust_name())?;
+        indented!(w, [_], r#""""A callback carrying user data, can be created from a Python callable.""""#)?;
+        w.newline()?;
+        indented!(w, [_], r#"# These fields represent the underlying C data layout"#)?;
+        indented!(w, [_], r#"_fields_ = ["#)?;
+        indented!(w, [_], r#"    ("callback", ctypes.c_void_p),"#)?;
+        indented!(w, [_], r#"    ("data", ctypes.c_void_p),"#)?;
+        indented!(w, [_], r#"    ("destructor", ctypes.c_void_p),"#)?;
+        indented!(w, [_], r#"]"#)?;
+        w.newline()?;
+        indented!(w, [_], r#"_boxed = None"#)?;
+        w.newline()?;
+        indented!(w, [_], r#"def __init__(self, f = None):"#)?;
+        indented!(w, [_ _], r#""""Wraps `f`, which is kept alive by this and every copy handed to the library.""""#)?;
+        indented!(w, [_ _], r#"super().__init__()"#)?;
+        indented!(w, [_ _], r#"if f is not None:"#)?;
+        indented!(w, [_ _ _], r#"self._boxed = {}(lambda *args: f(*args[:-1]))"#, raw)?;
+        indented!(w, [_ _ _], r#"self.callback = ctypes.cast(self._boxed, ctypes.c_void_p)"#)?;
+        indented!(w, [_ _ _], r#"self.data = id(self._boxed)"#)?;
+        indented!(w, [_ _ _], r#"self.destructor = ctypes.cast(_CallbackData.release_fn, ctypes.c_void_p)"#)?;
+        w.newline()?;
+        indented!(w, [_], r#"def _hand_over(self):"#)?;
+        indented!(w, [_ _], r#""""Called before each call taking this, as the library releases every copy it got.""""#)?;
+        indented!(w, [_ _], r#"if self._boxed is not None:"#)?;
+        indented!(w, [_ _ _], r#"_CallbackData.box(self._boxed)"#)?;
+
+        Ok(())
+    }
+
+    fn write_str(&self, w: &mut IndentWriter, c: &CompositeType) -> Result<(), Error> {
+        indented!(w, r#"class {}(ctypes.Structure):"#, c.rust_name())?;
+        indented!(w, [_], r#""""A UTF-8 string borrowed by the library, can be created from a `str`.""""#)?;
+        w.newline()?;
+        indented!(w, [_], r#"# These fields represent the underlying C data layout"#)?;
+        indented!(w, [_], r#"_fields_ = ["#)?;
+        indented!(w, [_], r#"    ("data", ctypes.POINTER(ctypes.c_uint8)),"#)?;
+        indented!(w, [_], r#"    ("len", ctypes.c_uint64),"#)?;
+        indented!(w, [_], r#"]"#)?;
+        w.newline()?;
+        indented!(w, [_], r#"@staticmethod"#)?;
+        indented!(w, [_], r#"def from_str(s: str) -> "{}":"#, c.rust_name())?;
+        indented!(w, [_ _], r#""""Encodes `s`, which is kept alive as long as the result.""""#)?;
+        indented!(w, [_ _], r#"data = s.encode("utf-8")"#)?;
+        indented!(w, [_ _], r#"buffer = ctypes.create_string_buffer(data, len(data))"#)?;
+        indented!(w, [_ _], r#"rval = {}(data=ctypes.cast(buffer, ctypes.POINTER(ctypes.c_uint8)), len=len(data))"#, c.rust_name())?;
+        indented!(w, [_ _], r#"rval._buffer = buffer"#)?;
+        indented!(w, [_ _], r#"return rval"#)?;
+        w.newline()?;
+        self.write_string_to_str(w)
+    }
+
+    fn write_string(&self, w: &mut IndentWriter, c: &CompositeType) -> Result<(), Error> {
+        indented!(w, r#"class {}(ctypes.Structure):"#, c.rust_name())?;
+        indented!(w, [_], r#""""A UTF-8 string owned by the library, functions returning it convert it to a `str`.""""#)?;
+        w.newline()?;
+        indented!(w, [_], r#"# These fields represent the underlying C data layout"#)?;
+        indented!(w, [_], r#"_fields_ = ["#)?;
+        indented!(w, [_], r#"    ("data", ctypes.POINTER(ctypes.c_uint8)),"#)?;
+        indented!(w, [_], r#"    ("len", ctypes.c_uint64),"#)?;
+        indented!(w, [_], r#"    ("capacity", ctypes.c_uint64),"#)?;
+        indented!(w, [_], r#"]"#)?;
+        w.newline()?;
+        self.write_string_to_str(w)?;
+
+        w.newline()?;
+        indented!(w, [_], r#"def _forget(self):"#)?;
+        indented!(w, [_ _], r#""""Empties this string without freeing it, e.g., once it was passed to the library.""""#)?;
+        indented!(w, [_ _], r#"self.data = None"#)?;
+        indented!(w, [_ _], r#"self.len = 0"#)?;
+        indented!(w, [_ _], r#"self.capacity = 0"#)?;
+
+        if let Some(destructor) = self.string_destructor() {
+            w.newline()?;
+            indented!(w, [_], r#"def free(self):"#)?;
+            indented!(w, [_ _], r#""""Returns the string to the library, afterwards it is empty.""""#)?;
+            indented!(w, [_ _], r#"if self.data and c_lib is not None:"#)?;
+            indented!(w, [_ _ _], r#"c_lib.{}(self)"#, destructor.name())?;
+            indented!(w, [_ _], r#"self._forget()"#)?;
+        }
+
+        Ok(())
+    }
+
+    /// Decodes `data` and `len`, which may contain NUL.
+    fn write_string_to_str(&self, w: &mut IndentWriter) -> Result<(), Error> {
+        indented!(w, [_], r#"def to_str(self) -> str:"#)?;
+        indented!(w, [_ _], r#""""Returns a decoded copy of the string.""""#)?;
+        indented!(w, [_ _], r#"if not self.len:"#)?;
+        indented!(w, [_ _ _], r#"return """#)?;
+        indented!(w, [_ _], r#"return ctypes.string_at(self.data, self.len).decode("utf-8")"#)
+    }
+
+    fn string_destructor(&self) -> Option<&Function> {
+        self.inventory().patterns().iter().find_map(|x| match x {
+            LibraryPattern::String(x) => Some(x.destructor()),
+            _ => None,
+        })
+    }
+
+    fn last_error_function(&self) -> Option<&Function> {
+        self.inventory().patterns().iter().find_map(|x| match x {
+            LibraryPattern::LastError(x) => Some(x.function()),
+            _ => None,
+        })
+    }
+
     fn write_option(&self, w: &mut IndentWriter, c: &CompositeType) -> Result<(), Error> {
         let data_type = c
             .fields()
@@ -439,7 +846,13 @@ pub trait PythonWriter {
     fn write_patterns(&self, w: &mut IndentWriter) -> Result<(), Error> {
         for pattern in self.inventory().patterns() {
             match pattern {
-                LibraryPattern::Service(x) => self.write_pattern_class(w, x)?,
+                LibraryPattern::Service(x) => self
+                    .write_pattern_class(w, x)
+                    .map_err(|e| e.with_item(format!("service {}", x.the_type().rust_name())))?,
+                // Written along with their type.
+                LibraryPattern::Vec(_) => {}
+                LibraryPattern::String(_) => {}
+                LibraryPattern::LastError(_) => {}
             }
         }
 
@@ -558,12 +971,28 @@ pub trait PythonWriter {
             }
         };
 
-        match function.signature().rval() {
-            CType::Pattern(TypePattern::AsciiPointer) => {
-                indented!(w, [_], r#"rval = c_lib.{}({})"#, function.name(), &args)?;
-                indented!(w, [_], r#"return ctypes.string_at(rval)"#)?;
+        // Vecs and strings passed by value now belong to the library, so ours must not free them again.
+        let moved = function
+            .signature()
+            .params()
+            .iter()
+            .filter(|x| matches!(x.the_type(), CType::Pattern(TypePattern::Vec(_) | TypePattern::String(_))))
+            .map(|x| x.name())
+            .collect::<Vec<_>>();
+
+        if self.is_destructor(function) {
+            indented!(w, [_], r#"{}.free()"#, moved[0])?;
+        } else if moved.is_empty() {
+            self.write_library_call_rval(w, function, &args)?;
+        } else {
+            indented!(w, [_], r#"try:"#)?;
+            w.indent();
+            self.write_library_call_rval(w, function, &args)?;
+            w.unindent();
+            indented!(w, [_], r#"finally:"#)?;
+            for x in moved {
+                indented!(w, [_ _], r#"{}._forget()"#, x)?;
             }
-            _ => self.write_success_enum_aware_rval(w, function, &args, true)?,
         }
 
         if class_str.is_some() {
@@ -573,6 +1002,36 @@ pub trait PythonWriter {
         Ok(())
     }
 
+    fn is_destructor(&self, function: &Function) -> bool {
+        self.inventory().patterns().iter().any(|x| match x {
+            LibraryPattern::Vec(x) => x.destructor() == function,
+            LibraryPattern::String(x) => x.destructor() == function,
+            _ => false,
+        })
+    }
+
+    fn write_library_call_rval(&self, w: &mut IndentWriter, function: &Function, args: &str) -> Result<(), Error> {
+        match function.signature().rval() {
+            CType::Pattern(TypePattern::AsciiPointer) => {
+                indented!(w, [_], r#"rval = c_lib.{}({})"#, function.name(), &args)?;
+                indented!(w, [_], r#"return ctypes.string_at(rval)"#)?;
+            }
+            CType::Pattern(TypePattern::String(_)) if self.string_destructor().is_some() => {
+                indented!(w, [_], r#"rval = c_lib.{}({})"#, function.name(), &args)?;
+                indented!(w, [_], r#"try:"#)?;
+                indented!(w, [_ _], r#"return rval.to_str()"#)?;
+                indented!(w, [_], r#"finally:"#)?;
+                indented!(w, [_ _], r#"rval.free()"#)?;
+            }
+            CType::Pattern(TypePattern::Str(_) | TypePattern::String(_)) => {
+                indented!(w, [_], r#"return c_lib.{}({}).to_str()"#, function.name(), &args)?;
+            }
+            _ => self.write_success_enum_aware_rval(w, function, args, true)?,
+        }
+
+        Ok(())
+    }
+
     fn function_args_to_string(&self, function: &Function, type_hints: bool, skip_first: bool) -> String {
         let skip = if skip_first { 1 } else { 0 };
         function
@@ -637,13 +1096,55 @@ pub trait PythonWriter {
         w.newline()?;
         w.newline()?;
 
+        // Include the library's last error message, if it exposes one.
+        let message = match self.last_error_function() {
+            Some(f) => format!(" ({{c_lib.{}().to_str()}})", f.name()),
+            None => "".to_string(),
+        };
+
         indented!(w, r#"def _errcheck(returned, success):"#)?;
         indented!(w, [_], r#""""Checks for FFIErrors and converts them to an exception.""""#)?;
         indented!(w, [_], r#"if returned == success: return"#)?;
-        indented!(w, [_], r#"else: raise Exception(f"Function returned error: {{returned}}")"#)?;
+        indented!(w, [_], r#"else: raise Exception(f"Function returned error: {{returned}}{}")"#, message)?;
+        w.newline()?;
+        w.newline()?;
+
+        indented!(w, r#"def _errcheck_result(returned, success):"#)?;
+        indented!(w, [_], r#""""Unwraps results, converting FFIErrors to an exception.""""#)?;
+        indented!(w, [_], r#"if returned.err == success: return returned.t"#)?;
+        indented!(w, [_], r#"else: raise Exception(f"Function returned error: {{returned.err}}{}")"#, message)?;
         w.newline()?;
         w.newline()?;
 
+        let has_callback_with_data = self
+            .inventory()
+            .ctypes()
+            .iter()
+            .any(|x| matches!(x, CType::Pattern(TypePattern::CallbackWithData(_))));
+
+        if has_callback_with_data {
+            indented!(w, r#"class _CallbackData(object):"#)?;
+            indented!(w, [_], r#""""Keeps callbacks handed to the library alive until it released each copy.""""#)?;
+            indented!(w, [_], r#"boxes = {{}}"#)?;
+            w.newline()?;
+            indented!(w, [_], r#"@staticmethod"#)?;
+            indented!(w, [_], r#"def box(x):"#)?;
+            indented!(w, [_ _], r#"_, count = _CallbackData.boxes.get(id(x), (x, 0))"#)?;
+            indented!(w, [_ _], r#"_CallbackData.boxes[id(x)] = (x, count + 1)"#)?;
+            w.newline()?;
+            indented!(w, [_], r#"@staticmethod"#)?;
+            indented!(w, [_], r#"def release(key):"#)?;
+            indented!(w, [_ _], r#"# Copies passed to `c_lib` directly were never boxed, their wrapper keeps them alive."#)?;
+            indented!(w, [_ _], r#"x, count = _CallbackData.boxes.pop(key, (None, 0))"#)?;
+            indented!(w, [_ _], r#"if count > 1:"#)?;
+            indented!(w, [_ _ _], r#"_CallbackData.boxes[key] = (x, count - 1)"#)?;
+            w.newline()?;
+            w.newline()?;
+            indented!(w, r#"_CallbackData.release_fn = ctypes.CFUNCTYPE(None, ctypes.c_void_p)(_CallbackData.release)"#)?;
+            w.newline()?;
+            w.newline()?;
+        }
+
         indented!(w, r#"class CallbackVars(object):"#)?;
         indented!(
             w,
@@ -703,6 +1204,8 @@ pub trait PythonWriter {
         w.newline()?;
         w.newline()?;
 
+        self.write_aggregate_constants(w)?;
+
         self.write_callback_helpers(w)?;
         w.newline()?;
         w.newline()?;
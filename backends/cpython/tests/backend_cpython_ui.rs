@@ -1,4 +1,5 @@
-use interoptopus::testing::assert_file_matches_generated;
+use interoptopus::testing::snapshot::assert_generated_matches_snapshot;
+use interoptopus::writer::IndentWriter;
 use interoptopus::{Error, Interop};
 use interoptopus_backend_cpython::{run_python_if_installed, DocConfig, DocGenerator};
 
@@ -20,10 +21,12 @@ fn generate_documentation(output: &str) -> Result<(), Error> {
 #[test]
 #[cfg_attr(miri, ignore)]
 fn bindings_match_reference() -> Result<(), Error> {
-    generate_bindings("tests/output/reference_project.py")?;
+    use interoptopus_backend_cpython::{Config, Generator};
+
+    let generator = Generator::new(Config::default(), interoptopus_reference_project::ffi_inventory());
     generate_documentation("tests/output/reference_project.md")?;
 
-    assert_file_matches_generated("tests/output/reference_project.py");
+    assert_generated_matches_snapshot(&generator, "tests/output/reference_project.py.expected");
 
     Ok(())
 }
@@ -37,3 +40,26 @@ fn bindings_work() -> Result<(), Error> {
 
     Ok(())
 }
+
+#[test]
+fn reference_project_is_valid() {
+    use interoptopus_backend_cpython::{Config, Generator};
+
+    let generator = Generator::new(Config::default(), interoptopus_reference_project::ffi_inventory());
+    let validation = generator.validate();
+
+    assert!(!validation.has_errors(), "{}", validation);
+}
+
+#[test]
+fn unsupported_primitives_are_errors() {
+    use interoptopus_backend_cpython::{Config, Generator};
+
+    let generator = Generator::new(Config::default(), interoptopus_reference_project::ffi_inventory_with_128bit());
+    let error = generator.write_to(&mut IndentWriter::new(&mut Vec::new())).unwrap_err();
+
+    let cause = error.root_cause();
+
+    assert!(matches!(cause, Error::Invalid(_)));
+    assert!(cause.to_string().contains("error: fn primitive_u128: parameter `x` uses `u128`"), "{}", cause);
+}
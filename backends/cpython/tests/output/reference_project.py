@@ -21,6 +21,9 @@ def init_lib(path):
     c_lib.primitive_i16.argtypes = [ctypes.c_int16]
     c_lib.primitive_i32.argtypes = [ctypes.c_int32]
     c_lib.primitive_i64.argtypes = [ctypes.c_int64]
+    c_lib.primitive_usize.argtypes = [ctypes.c_size_t]
+    c_lib.primitive_isize.argtypes = [ctypes.c_ssize_t]
+    c_lib.primitive_char.argtypes = [ctypes.c_uint32]
     c_lib.many_args_5.argtypes = [ctypes.c_int64, ctypes.c_int64, ctypes.c_int64, ctypes.c_int64, ctypes.c_int64]
     c_lib.many_args_10.argtypes = [ctypes.c_int64, ctypes.c_int64, ctypes.c_int64, ctypes.c_int64, ctypes.c_int64, ctypes.c_int64, ctypes.c_int64, ctypes.c_int64, ctypes.c_int64, ctypes.c_int64]
     c_lib.ptr.argtypes = [ctypes.POINTER(ctypes.c_int64)]
@@ -49,12 +52,17 @@ def init_lib(path):
     c_lib.namespaced_inner_option.argtypes = [OptionVec]
     c_lib.namespaced_inner_slice.argtypes = [SliceVec]
     c_lib.namespaced_inner_slice_mut.argtypes = [SliceMutVec]
+    c_lib.namespaced_function_renamed.argtypes = [Vec]
     c_lib.panics.argtypes = []
     c_lib.renamed.argtypes = [StructRenamed]
     c_lib.sleep.argtypes = [ctypes.c_uint64]
     c_lib.weird_1.argtypes = [Weird1u32, Weird2u8]
     c_lib.visibility.argtypes = [Visibility1, Visibility2]
     c_lib.repr_transparent.argtypes = [Tupled, ctypes.POINTER(Tupled)]
+    c_lib.tagged_union_1.argtypes = [TaggedShape]
+    c_lib.tagged_union_2.argtypes = [ctypes.POINTER(TaggedInline)]
+    c_lib.union_1.argtypes = [UnionBits]
+    c_lib.enum_repr_1.argtypes = [ctypes.c_int8]
     c_lib.pattern_ascii_pointer_1.argtypes = [ctypes.POINTER(ctypes.c_char)]
     c_lib.pattern_ascii_pointer_2.argtypes = []
     c_lib.pattern_ascii_pointer_len.argtypes = [ctypes.POINTER(ctypes.c_char), UseAsciiStringPattern]
@@ -68,8 +76,15 @@ def init_lib(path):
     c_lib.pattern_ffi_slice_6.argtypes = [ctypes.POINTER(SliceMutu8), callbacks.fn_u8_rval_u8]
     c_lib.pattern_ffi_slice_delegate.argtypes = [callbacks.fn_Sliceu8_rval_u8]
     c_lib.pattern_ffi_slice_delegate_huge.argtypes = [callbacks.fn_SliceVec3f32_rval_Vec3f32]
+    c_lib.pattern_ffi_str_1.argtypes = [Utf8Str]
+    c_lib.pattern_ffi_string_1.argtypes = [Utf8Str]
+    c_lib.pattern_ffi_string_2.argtypes = []
+    c_lib.pattern_ffi_vec_1.argtypes = [ctypes.c_uint32]
+    c_lib.pattern_ffi_vec_2.argtypes = [ctypes.c_float, ctypes.c_uint32]
+    c_lib.pattern_ffi_vec_3.argtypes = [Vecu32]
     c_lib.pattern_ffi_option_1.argtypes = [OptionInner]
     c_lib.pattern_ffi_option_2.argtypes = [OptionInner]
+    c_lib.pattern_ffi_result_1.argtypes = [ctypes.c_uint32]
     c_lib.pattern_ffi_bool.argtypes = [ctypes.c_uint8]
     c_lib.pattern_ffi_cchar.argtypes = [ctypes.c_char]
     c_lib.pattern_ffi_cchar_const_pointer.argtypes = [ctypes.POINTER(ctypes.c_char)]
@@ -77,12 +92,14 @@ def init_lib(path):
     c_lib.pattern_api_guard.argtypes = []
     c_lib.pattern_callback_1.argtypes = [callbacks.fn_u32_rval_u32, ctypes.c_uint32]
     c_lib.pattern_callback_2.argtypes = [callbacks.fn_pconst]
+    c_lib.pattern_callback_with_data_1.argtypes = [MyCallbackData, ctypes.c_uint32]
     c_lib.simple_service_destroy.argtypes = [ctypes.POINTER(ctypes.c_void_p)]
     c_lib.simple_service_new_with.argtypes = [ctypes.POINTER(ctypes.c_void_p), ctypes.c_uint32]
     c_lib.simple_service_new_without.argtypes = [ctypes.POINTER(ctypes.c_void_p)]
     c_lib.simple_service_new_with_string.argtypes = [ctypes.POINTER(ctypes.c_void_p), ctypes.POINTER(ctypes.c_char)]
     c_lib.simple_service_new_failing.argtypes = [ctypes.POINTER(ctypes.c_void_p), ctypes.c_uint8]
     c_lib.simple_service_method_result.argtypes = [ctypes.c_void_p, ctypes.c_uint32]
+    c_lib.simple_service_method_result_value.argtypes = [ctypes.c_void_p, ctypes.c_uint32]
     c_lib.simple_service_method_value.argtypes = [ctypes.c_void_p, ctypes.c_uint32]
     c_lib.simple_service_method_void.argtypes = [ctypes.c_void_p]
     c_lib.simple_service_method_mut_self.argtypes = [ctypes.c_void_p, Sliceu8]
@@ -95,6 +112,7 @@ def init_lib(path):
     c_lib.simple_service_return_slice.argtypes = [ctypes.c_void_p]
     c_lib.simple_service_return_slice_mut.argtypes = [ctypes.c_void_p]
     c_lib.simple_service_return_string.argtypes = [ctypes.c_void_p]
+    c_lib.simple_service_return_string_utf8.argtypes = [ctypes.c_void_p, Utf8Str]
     c_lib.simple_service_method_void_ffi_error.argtypes = [ctypes.c_void_p]
     c_lib.simple_service_method_callback.argtypes = [ctypes.c_void_p, callbacks.fn_u32_rval_u32]
     c_lib.simple_service_lt_destroy.argtypes = [ctypes.POINTER(ctypes.c_void_p)]
@@ -103,6 +121,10 @@ def init_lib(path):
     c_lib.simple_service_lt_method_lt2.argtypes = [ctypes.c_void_p, SliceBool]
     c_lib.simple_service_lt_return_string_accept_slice.argtypes = [ctypes.c_void_p, Sliceu8]
     c_lib.simple_service_lt_method_void_ffi_error.argtypes = [ctypes.c_void_p]
+    c_lib.pattern_ffi_vec_u32_destroy.argtypes = [Vecu32]
+    c_lib.pattern_ffi_vec_vec3_destroy.argtypes = [VecVec3f32]
+    c_lib.pattern_ffi_string_destroy.argtypes = [Utf8String]
+    c_lib.pattern_last_error_message.argtypes = []
 
     c_lib.primitive_bool.restype = ctypes.c_bool
     c_lib.primitive_u8.restype = ctypes.c_uint8
@@ -113,6 +135,9 @@ def init_lib(path):
     c_lib.primitive_i16.restype = ctypes.c_int16
     c_lib.primitive_i32.restype = ctypes.c_int32
     c_lib.primitive_i64.restype = ctypes.c_int64
+    c_lib.primitive_usize.restype = ctypes.c_size_t
+    c_lib.primitive_isize.restype = ctypes.c_ssize_t
+    c_lib.primitive_char.restype = ctypes.c_uint32
     c_lib.many_args_5.restype = ctypes.c_int64
     c_lib.many_args_10.restype = ctypes.c_int64
     c_lib.ptr.restype = ctypes.POINTER(ctypes.c_int64)
@@ -141,10 +166,15 @@ def init_lib(path):
     c_lib.namespaced_inner_option.restype = OptionVec
     c_lib.namespaced_inner_slice.restype = SliceVec
     c_lib.namespaced_inner_slice_mut.restype = SliceMutVec
+    c_lib.namespaced_function_renamed.restype = Vec
     c_lib.panics.restype = ctypes.c_int
     c_lib.renamed.restype = ctypes.c_int
     c_lib.weird_1.restype = ctypes.c_bool
     c_lib.repr_transparent.restype = Tupled
+    c_lib.tagged_union_1.restype = TaggedShape
+    c_lib.tagged_union_2.restype = ctypes.c_uint32
+    c_lib.union_1.restype = ctypes.c_uint32
+    c_lib.enum_repr_1.restype = ctypes.c_int8
     c_lib.pattern_ascii_pointer_1.restype = ctypes.c_uint32
     c_lib.pattern_ascii_pointer_2.restype = ctypes.POINTER(ctypes.c_char)
     c_lib.pattern_ascii_pointer_len.restype = ctypes.c_uint32
@@ -154,8 +184,15 @@ def init_lib(path):
     c_lib.pattern_ffi_slice_2.restype = Vec3f32
     c_lib.pattern_ffi_slice_delegate.restype = ctypes.c_uint8
     c_lib.pattern_ffi_slice_delegate_huge.restype = Vec3f32
+    c_lib.pattern_ffi_str_1.restype = ctypes.c_uint32
+    c_lib.pattern_ffi_string_1.restype = Utf8String
+    c_lib.pattern_ffi_string_2.restype = Utf8String
+    c_lib.pattern_ffi_vec_1.restype = Vecu32
+    c_lib.pattern_ffi_vec_2.restype = VecVec3f32
+    c_lib.pattern_ffi_vec_3.restype = ctypes.c_uint32
     c_lib.pattern_ffi_option_1.restype = OptionInner
     c_lib.pattern_ffi_option_2.restype = Inner
+    c_lib.pattern_ffi_result_1.restype = Resultu32FFIError
     c_lib.pattern_ffi_bool.restype = ctypes.c_uint8
     c_lib.pattern_ffi_cchar.restype = ctypes.c_char
     c_lib.pattern_ffi_cchar_const_pointer.restype = ctypes.POINTER(ctypes.c_char)
@@ -163,12 +200,14 @@ def init_lib(path):
     c_lib.pattern_api_guard.restype = ctypes.c_uint64
     c_lib.pattern_callback_1.restype = ctypes.c_uint32
     c_lib.pattern_callback_2.restype = callbacks.fn_pconst
+    c_lib.pattern_callback_with_data_1.restype = ctypes.c_uint32
     c_lib.simple_service_destroy.restype = ctypes.c_int
     c_lib.simple_service_new_with.restype = ctypes.c_int
     c_lib.simple_service_new_without.restype = ctypes.c_int
     c_lib.simple_service_new_with_string.restype = ctypes.c_int
     c_lib.simple_service_new_failing.restype = ctypes.c_int
     c_lib.simple_service_method_result.restype = ctypes.c_int
+    c_lib.simple_service_method_result_value.restype = Resultu32FFIError
     c_lib.simple_service_method_value.restype = ctypes.c_uint32
     c_lib.simple_service_method_mut_self.restype = ctypes.c_uint8
     c_lib.simple_service_method_mut_self_ref.restype = ctypes.c_uint8
@@ -179,21 +218,25 @@ def init_lib(path):
     c_lib.simple_service_return_slice.restype = Sliceu32
     c_lib.simple_service_return_slice_mut.restype = SliceMutu32
     c_lib.simple_service_return_string.restype = ctypes.POINTER(ctypes.c_char)
+    c_lib.simple_service_return_string_utf8.restype = Utf8String
     c_lib.simple_service_method_void_ffi_error.restype = ctypes.c_int
     c_lib.simple_service_method_callback.restype = ctypes.c_int
     c_lib.simple_service_lt_destroy.restype = ctypes.c_int
     c_lib.simple_service_lt_new_with.restype = ctypes.c_int
     c_lib.simple_service_lt_return_string_accept_slice.restype = ctypes.POINTER(ctypes.c_char)
     c_lib.simple_service_lt_method_void_ffi_error.restype = ctypes.c_int
+    c_lib.pattern_last_error_message.restype = Utf8Str
 
     c_lib.complex_args_1.errcheck = lambda rval, _fptr, _args: _errcheck(rval, 0)
     c_lib.panics.errcheck = lambda rval, _fptr, _args: _errcheck(rval, 0)
+    c_lib.pattern_ffi_result_1.errcheck = lambda rval, _fptr, _args: _errcheck_result(rval, 0)
     c_lib.simple_service_destroy.errcheck = lambda rval, _fptr, _args: _errcheck(rval, 0)
     c_lib.simple_service_new_with.errcheck = lambda rval, _fptr, _args: _errcheck(rval, 0)
     c_lib.simple_service_new_without.errcheck = lambda rval, _fptr, _args: _errcheck(rval, 0)
     c_lib.simple_service_new_with_string.errcheck = lambda rval, _fptr, _args: _errcheck(rval, 0)
     c_lib.simple_service_new_failing.errcheck = lambda rval, _fptr, _args: _errcheck(rval, 0)
     c_lib.simple_service_method_result.errcheck = lambda rval, _fptr, _args: _errcheck(rval, 0)
+    c_lib.simple_service_method_result_value.errcheck = lambda rval, _fptr, _args: _errcheck_result(rval, 0)
     c_lib.simple_service_method_mut_self_ffi_error.errcheck = lambda rval, _fptr, _args: _errcheck(rval, 0)
     c_lib.simple_service_method_mut_self_no_error.errcheck = lambda rval, _fptr, _args: _errcheck(rval, 0)
     c_lib.simple_service_method_void_ffi_error.errcheck = lambda rval, _fptr, _args: _errcheck(rval, 0)
@@ -202,6 +245,17 @@ def init_lib(path):
     c_lib.simple_service_lt_new_with.errcheck = lambda rval, _fptr, _args: _errcheck(rval, 0)
     c_lib.simple_service_lt_method_void_ffi_error.errcheck = lambda rval, _fptr, _args: _errcheck(rval, 0)
 
+    api_version = c_lib.pattern_api_guard()
+    api_major, api_minor = api_version >> 48, (api_version >> 32) & 0xFFFF
+    if api_major != 1 or (api_minor == 0 and api_version != 0x000100006f474943):
+        if (api_major, api_minor) < (1, 0):
+            reason = "the library is older"
+        elif (api_major, api_minor) == (1, 0):
+            reason = "both report the same version but differ"
+        else:
+            reason = "the bindings are older"
+        raise ImportError(f"Library has API version {api_major}.{api_minor} (hash {api_version & 0xFFFFFFFF}), bindings need 1.0 (hash 1866942787); {reason}. You probably forgot to update / copy either the bindings or the library.")
+
 
 def primitive_void():
     return c_lib.primitive_void()
@@ -236,6 +290,16 @@ def primitive_i32(x: int) -> int:
 def primitive_i64(x: int) -> int:
     return c_lib.primitive_i64(x)
 
+def primitive_usize(x: int) -> int:
+    return c_lib.primitive_usize(x)
+
+def primitive_isize(x: int) -> int:
+    return c_lib.primitive_isize(x)
+
+def primitive_char(x: int) -> int:
+    """ Callers must pass a valid Unicode scalar value, which is why `rustc` considers `char` not FFI-safe."""
+    return c_lib.primitive_char(x)
+
 def many_args_5(x0: int, x1: int, x2: int, x3: int, x4: int) -> int:
     return c_lib.many_args_5(x0, x1, x2, x3, x4)
 
@@ -333,6 +397,9 @@ def namespaced_inner_slice_mut(x: SliceMutVec | ctypes.Array[Vec]) -> SliceMutVe
 
     return c_lib.namespaced_inner_slice_mut(x)
 
+def namespaced_function_renamed(x: Vec) -> Vec:
+    return c_lib.namespaced_function_renamed(x)
+
 def panics():
     return c_lib.panics()
 
@@ -351,6 +418,18 @@ def visibility(x: Visibility1, y: Visibility2):
 def repr_transparent(x: Tupled, r: ctypes.POINTER(Tupled)) -> Tupled:
     return c_lib.repr_transparent(x, r)
 
+def tagged_union_1(x: TaggedShape) -> TaggedShape:
+    return c_lib.tagged_union_1(x)
+
+def tagged_union_2(x: ctypes.POINTER(TaggedInline)) -> int:
+    return c_lib.tagged_union_2(x)
+
+def union_1(x: UnionBits) -> int:
+    return c_lib.union_1(x)
+
+def enum_repr_1(x: ctypes.c_int) -> ctypes.c_int:
+    return c_lib.enum_repr_1(x)
+
 def pattern_ascii_pointer_1(x: str) -> int:
     if not hasattr(x, "__ctypes_from_outparam__"):
         x = ctypes.cast(x, ctypes.POINTER(ctypes.c_char))
@@ -425,12 +504,50 @@ def pattern_ffi_slice_delegate_huge(callback) -> Vec3f32:
 
     return c_lib.pattern_ffi_slice_delegate_huge(callback)
 
+def pattern_ffi_str_1(x: str) -> int:
+    if isinstance(x, str):
+        x = Utf8Str.from_str(x)
+
+    return c_lib.pattern_ffi_str_1(x)
+
+def pattern_ffi_string_1(x: str) -> str:
+    if isinstance(x, str):
+        x = Utf8Str.from_str(x)
+
+    rval = c_lib.pattern_ffi_string_1(x)
+    try:
+        return rval.to_str()
+    finally:
+        rval.free()
+
+def pattern_ffi_string_2() -> str:
+    rval = c_lib.pattern_ffi_string_2()
+    try:
+        return rval.to_str()
+    finally:
+        rval.free()
+
+def pattern_ffi_vec_1(len: int) -> Vecu32:
+    return c_lib.pattern_ffi_vec_1(len)
+
+def pattern_ffi_vec_2(x: float, len: int) -> VecVec3f32:
+    return c_lib.pattern_ffi_vec_2(x, len)
+
+def pattern_ffi_vec_3(vec: Vecu32) -> int:
+    try:
+        return c_lib.pattern_ffi_vec_3(vec)
+    finally:
+        vec._forget()
+
 def pattern_ffi_option_1(ffi_slice: OptionInner) -> OptionInner:
     return c_lib.pattern_ffi_option_1(ffi_slice)
 
 def pattern_ffi_option_2(ffi_slice: OptionInner) -> Inner:
     return c_lib.pattern_ffi_option_2(ffi_slice)
 
+def pattern_ffi_result_1(x: int) -> int:
+    return c_lib.pattern_ffi_result_1(x)
+
 def pattern_ffi_bool(ffi_bool):
     return c_lib.pattern_ffi_bool(ffi_bool)
 
@@ -458,11 +575,40 @@ def pattern_callback_2(callback):
 
     return c_lib.pattern_callback_2(callback)
 
+def pattern_callback_with_data_1(callback, x: int) -> int:
+    if not isinstance(callback, MyCallbackData):
+        callback = MyCallbackData(callback)
+    callback._hand_over()
+
+    return c_lib.pattern_callback_with_data_1(callback, x)
+
+def pattern_ffi_vec_u32_destroy(vec: Vecu32):
+    """ Frees a vec returned by this library."""
+    vec.free()
+
+def pattern_ffi_vec_vec3_destroy(vec: VecVec3f32):
+    """ Frees a vec returned by this library."""
+    vec.free()
+
+def pattern_ffi_string_destroy(s: Utf8String):
+    """ Frees a string returned by this library."""
+    s.free()
+
+def pattern_last_error_message() -> str:
+    """ Returns the message of the last error or panic on this thread.
+
+ The string is valid until the next error on this thread, and empty if there was none."""
+    return c_lib.pattern_last_error_message().to_str()
+
 
 
 U8 = 255
 F32_MIN_POSITIVE = 0.000000000000000000000000000000000000011754944
 COMPUTED_I32 = -2147483647
+USIZE = 1048576
+CHAR = 955
+VERSION = "1.2.3"
+COMMON_U32 = 42
 
 
 TRUE = ctypes.c_uint8(1)
@@ -472,7 +618,33 @@ FALSE = ctypes.c_uint8(0)
 def _errcheck(returned, success):
     """Checks for FFIErrors and converts them to an exception."""
     if returned == success: return
-    else: raise Exception(f"Function returned error: {returned}")
+    else: raise Exception(f"Function returned error: {returned} ({c_lib.pattern_last_error_message().to_str()})")
+
+
+def _errcheck_result(returned, success):
+    """Unwraps results, converting FFIErrors to an exception."""
+    if returned.err == success: return returned.t
+    else: raise Exception(f"Function returned error: {returned.err} ({c_lib.pattern_last_error_message().to_str()})")
+
+
+class _CallbackData(object):
+    """Keeps callbacks handed to the library alive until it released each copy."""
+    boxes = {}
+
+    @staticmethod
+    def box(x):
+        _, count = _CallbackData.boxes.get(id(x), (x, 0))
+        _CallbackData.boxes[id(x)] = (x, count + 1)
+
+    @staticmethod
+    def release(key):
+        # Copies passed to `c_lib` directly were never boxed, their wrapper keeps them alive.
+        x, count = _CallbackData.boxes.pop(key, (None, 0))
+        if count > 1:
+            _CallbackData.boxes[key] = (x, count - 1)
+
+
+_CallbackData.release_fn = ctypes.CFUNCTYPE(None, ctypes.c_void_p)(_CallbackData.release)
 
 
 class CallbackVars(object):
@@ -516,6 +688,13 @@ class EnumRenamed:
     X = 0
 
 
+class EnumReprI8:
+    """ Enum stored as a single signed byte."""
+    Negative = -1
+    Zero = 0
+    Positive = 5
+
+
 class FFIError:
     Ok = 0
     Null = 100
@@ -523,6 +702,60 @@ class FFIError:
     Fail = 300
 
 
+class Utf8Str(ctypes.Structure):
+    """A UTF-8 string borrowed by the library, can be created from a `str`."""
+
+    # These fields represent the underlying C data layout
+    _fields_ = [
+        ("data", ctypes.POINTER(ctypes.c_uint8)),
+        ("len", ctypes.c_uint64),
+    ]
+
+    @staticmethod
+    def from_str(s: str) -> "Utf8Str":
+        """Encodes `s`, which is kept alive as long as the result."""
+        data = s.encode("utf-8")
+        buffer = ctypes.create_string_buffer(data, len(data))
+        rval = Utf8Str(data=ctypes.cast(buffer, ctypes.POINTER(ctypes.c_uint8)), len=len(data))
+        rval._buffer = buffer
+        return rval
+
+    def to_str(self) -> str:
+        """Returns a decoded copy of the string."""
+        if not self.len:
+            return ""
+        return ctypes.string_at(self.data, self.len).decode("utf-8")
+
+
+class Utf8String(ctypes.Structure):
+    """A UTF-8 string owned by the library, functions returning it convert it to a `str`."""
+
+    # These fields represent the underlying C data layout
+    _fields_ = [
+        ("data", ctypes.POINTER(ctypes.c_uint8)),
+        ("len", ctypes.c_uint64),
+        ("capacity", ctypes.c_uint64),
+    ]
+
+    def to_str(self) -> str:
+        """Returns a decoded copy of the string."""
+        if not self.len:
+            return ""
+        return ctypes.string_at(self.data, self.len).decode("utf-8")
+
+    def _forget(self):
+        """Empties this string without freeing it, e.g., once it was passed to the library."""
+        self.data = None
+        self.len = 0
+        self.capacity = 0
+
+    def free(self):
+        """Returns the string to the library, afterwards it is empty."""
+        if self.data and c_lib is not None:
+            c_lib.pattern_ffi_string_destroy(self)
+        self._forget()
+
+
 class ExtraTypef32(ctypes.Structure):
 
     # These fields represent the underlying C data layout
@@ -903,6 +1136,163 @@ class Weird1u32(ctypes.Structure):
         return ctypes.Structure.__set__(self, "x", value)
 
 
+class TaggedShapeTag:
+    #  Has no payload.
+    Empty = 0
+    Circle = 1
+    Rect = 2
+
+
+class TaggedShapeCircle(ctypes.Structure):
+
+    # These fields represent the underlying C data layout
+    _fields_ = [
+        ("x0", ctypes.c_float),
+    ]
+
+    def __init__(self, x0: float = None):
+        if x0 is not None:
+            self.x0 = x0
+
+    @property
+    def x0(self) -> float:
+        return ctypes.Structure.__get__(self, "x0")
+
+    @x0.setter
+    def x0(self, value: float):
+        return ctypes.Structure.__set__(self, "x0", value)
+
+
+class TaggedShapeRect(ctypes.Structure):
+
+    # These fields represent the underlying C data layout
+    _fields_ = [
+        ("width", ctypes.c_float),
+        ("height", ctypes.c_float),
+    ]
+
+    def __init__(self, width: float = None, height: float = None):
+        if width is not None:
+            self.width = width
+        if height is not None:
+            self.height = height
+
+    @property
+    def width(self) -> float:
+        return ctypes.Structure.__get__(self, "width")
+
+    @width.setter
+    def width(self, value: float):
+        return ctypes.Structure.__set__(self, "width", value)
+
+    @property
+    def height(self) -> float:
+        return ctypes.Structure.__get__(self, "height")
+
+    @height.setter
+    def height(self, value: float):
+        return ctypes.Structure.__set__(self, "height", value)
+
+
+class TaggedShapePayload(ctypes.Union):
+    _fields_ = [
+        ("Circle", TaggedShapeCircle),
+        ("Rect", TaggedShapeRect),
+    ]
+
+
+class TaggedShape(ctypes.Structure):
+    """ Enum with payloads, stored as a tag followed by a union."""
+
+    # These fields represent the underlying C data layout
+    _fields_ = [
+        ("_tag", ctypes.c_uint8),
+        ("_payload", TaggedShapePayload),
+    ]
+
+    @property
+    def tag(self) -> int:
+        """Returns the discriminant, one of the values in `TaggedShapeTag`."""
+        return self._tag
+
+    def is_empty(self) -> bool:
+        return self._tag == TaggedShapeTag.Empty
+
+    @staticmethod
+    def empty() -> TaggedShape:
+        rval = TaggedShape()
+        rval._tag = TaggedShapeTag.Empty
+        return rval
+
+    def is_circle(self) -> bool:
+        return self._tag == TaggedShapeTag.Circle
+
+    def as_circle(self) -> TaggedShapeCircle:
+        """Returns the payload, or raises a `ValueError` if this is a different variant."""
+        if self._tag != TaggedShapeTag.Circle:
+            raise ValueError(f"Variant is {self._tag}, not Circle.")
+        return self._payload.Circle
+
+    @staticmethod
+    def circle(value: TaggedShapeCircle) -> TaggedShape:
+        rval = TaggedShape()
+        rval._payload.Circle = value
+        rval._tag = TaggedShapeTag.Circle
+        return rval
+
+    def is_rect(self) -> bool:
+        return self._tag == TaggedShapeTag.Rect
+
+    def as_rect(self) -> TaggedShapeRect:
+        """Returns the payload, or raises a `ValueError` if this is a different variant."""
+        if self._tag != TaggedShapeTag.Rect:
+            raise ValueError(f"Variant is {self._tag}, not Rect.")
+        return self._payload.Rect
+
+    @staticmethod
+    def rect(value: TaggedShapeRect) -> TaggedShape:
+        rval = TaggedShape()
+        rval._payload.Rect = value
+        rval._tag = TaggedShapeTag.Rect
+        return rval
+
+
+class Resultu32FFIError(ctypes.Structure):
+    """Result type containing either a value or an error code."""
+
+    # These fields represent the underlying C data layout
+    _fields_ = [
+        ("t", ctypes.c_uint32),
+        ("err", ctypes.c_int),
+    ]
+
+    def __init__(self, t: int = None, err = None):
+        if t is not None:
+            self.t = t
+        if err is not None:
+            self.err = err
+
+    @property
+    def t(self) -> int:
+        """Element that is only valid if `err` signals success."""
+        return ctypes.Structure.__get__(self, "t")
+
+    @t.setter
+    def t(self, value: int):
+        """Element that is only valid if `err` signals success."""
+        return ctypes.Structure.__set__(self, "t", value)
+
+    @property
+    def err(self):
+        """Error code, success variant if `t` is valid."""
+        return ctypes.Structure.__get__(self, "err")
+
+    @err.setter
+    def err(self, value):
+        """Error code, success variant if `t` is valid."""
+        return ctypes.Structure.__set__(self, "err", value)
+
+
 class Array(ctypes.Structure):
 
     # These fields represent the underlying C data layout
@@ -1005,6 +1395,175 @@ class Weird2u8(ctypes.Structure):
         return ctypes.Structure.__set__(self, "r", value)
 
 
+class UnionBits(ctypes.Union):
+    """ Union where all fields share the same four bytes."""
+
+    # These fields represent the underlying C data layout
+    _fields_ = [
+        ("f", ctypes.c_float),
+        ("u", ctypes.c_uint32),
+        ("bytes", ctypes.c_uint8 * 4),
+    ]
+
+    def __init__(self, f: float = None, u: int = None, bytes = None):
+        if f is not None:
+            self.f = f
+        if u is not None:
+            self.u = u
+        if bytes is not None:
+            self.bytes = bytes
+
+    @property
+    def f(self) -> float:
+        return ctypes.Union.__get__(self, "f")
+
+    @f.setter
+    def f(self, value: float):
+        return ctypes.Union.__set__(self, "f", value)
+
+    @property
+    def u(self) -> int:
+        return ctypes.Union.__get__(self, "u")
+
+    @u.setter
+    def u(self, value: int):
+        return ctypes.Union.__set__(self, "u", value)
+
+    @property
+    def bytes(self):
+        """ The raw bytes, in native byte order."""
+        return ctypes.Union.__get__(self, "bytes")
+
+    @bytes.setter
+    def bytes(self, value):
+        """ The raw bytes, in native byte order."""
+        return ctypes.Union.__set__(self, "bytes", value)
+
+
+class TaggedInlineTag:
+    A = 0
+    B = 1
+    C = 10
+
+
+class TaggedInlineA(ctypes.Structure):
+
+    # These fields represent the underlying C data layout
+    _fields_ = [
+        ("tag", ctypes.c_uint32),
+        ("x0", ctypes.c_uint8),
+    ]
+
+    def __init__(self, tag: int = None, x0: int = None):
+        if tag is not None:
+            self.tag = tag
+        if x0 is not None:
+            self.x0 = x0
+
+    @property
+    def tag(self) -> int:
+        return ctypes.Structure.__get__(self, "tag")
+
+    @tag.setter
+    def tag(self, value: int):
+        return ctypes.Structure.__set__(self, "tag", value)
+
+    @property
+    def x0(self) -> int:
+        return ctypes.Structure.__get__(self, "x0")
+
+    @x0.setter
+    def x0(self, value: int):
+        return ctypes.Structure.__set__(self, "x0", value)
+
+
+class TaggedInlineB(ctypes.Structure):
+
+    # These fields represent the underlying C data layout
+    _fields_ = [
+        ("tag", ctypes.c_uint32),
+        ("x", Vec3f32),
+    ]
+
+    def __init__(self, tag: int = None, x: Vec3f32 = None):
+        if tag is not None:
+            self.tag = tag
+        if x is not None:
+            self.x = x
+
+    @property
+    def tag(self) -> int:
+        return ctypes.Structure.__get__(self, "tag")
+
+    @tag.setter
+    def tag(self, value: int):
+        return ctypes.Structure.__set__(self, "tag", value)
+
+    @property
+    def x(self) -> Vec3f32:
+        return ctypes.Structure.__get__(self, "x")
+
+    @x.setter
+    def x(self, value: Vec3f32):
+        return ctypes.Structure.__set__(self, "x", value)
+
+
+class TaggedInline(ctypes.Union):
+
+    # These fields represent the underlying C data layout
+    _fields_ = [
+        ("_tag", ctypes.c_uint32),
+        ("_A", TaggedInlineA),
+        ("_B", TaggedInlineB),
+    ]
+
+    @property
+    def tag(self) -> int:
+        """Returns the discriminant, one of the values in `TaggedInlineTag`."""
+        return self._tag
+
+    def is_a(self) -> bool:
+        return self._tag == TaggedInlineTag.A
+
+    def as_a(self) -> TaggedInlineA:
+        """Returns the payload, or raises a `ValueError` if this is a different variant."""
+        if self._tag != TaggedInlineTag.A:
+            raise ValueError(f"Variant is {self._tag}, not A.")
+        return self._A
+
+    @staticmethod
+    def a(value: TaggedInlineA) -> TaggedInline:
+        rval = TaggedInline()
+        rval._A = value
+        rval._tag = TaggedInlineTag.A
+        return rval
+
+    def is_b(self) -> bool:
+        return self._tag == TaggedInlineTag.B
+
+    def as_b(self) -> TaggedInlineB:
+        """Returns the payload, or raises a `ValueError` if this is a different variant."""
+        if self._tag != TaggedInlineTag.B:
+            raise ValueError(f"Variant is {self._tag}, not B.")
+        return self._B
+
+    @staticmethod
+    def b(value: TaggedInlineB) -> TaggedInline:
+        rval = TaggedInline()
+        rval._B = value
+        rval._tag = TaggedInlineTag.B
+        return rval
+
+    def is_c(self) -> bool:
+        return self._tag == TaggedInlineTag.C
+
+    @staticmethod
+    def c() -> TaggedInline:
+        rval = TaggedInline()
+        rval._tag = TaggedInlineTag.C
+        return rval
+
+
 class SliceBool(ctypes.Structure):
     # These fields represent the underlying C data layout
     _fields_ = [
@@ -1054,6 +1613,13 @@ class SliceBool(ctypes.Structure):
         """Returns the last element of this slice."""
         return self[len(self)-1]
 
+    def bytearray(self):
+        """Returns a bytearray with the content of this slice."""
+        rval = bytearray(len(self))
+        for i in range(len(self)):
+            rval[i] = self[i]
+        return rval
+
 
 class Sliceu32(ctypes.Structure):
     # These fields represent the underlying C data layout
@@ -1291,6 +1857,63 @@ class SliceMutu8(ctypes.Structure):
         return rval
 
 
+class Vecu32(ctypes.Structure):
+    """Elements owned by the library, freed once this is collected or its `with` block ends."""
+
+    # These fields represent the underlying C data layout
+    _fields_ = [
+        ("data", ctypes.POINTER(ctypes.c_uint32)),
+        ("len", ctypes.c_uint64),
+        ("capacity", ctypes.c_uint64),
+    ]
+
+    def __len__(self):
+        return self.len
+
+    def __getitem__(self, i) -> int:
+        if i < 0:
+            index = self.len+i
+        else:
+            index = i
+
+        if index >= self.len:
+            raise IndexError("Index out of range")
+
+        return self.data[index]
+
+    def __iter__(self) -> typing.Iterable[ctypes.c_uint32]:
+        return _Iter(self)
+
+    def to_list(self) -> typing.List[int]:
+        """Returns a copy of all elements, which stays valid after this vec was freed."""
+        if not self.data:
+            return []
+        return self.data[:self.len]
+
+    def _forget(self):
+        """Empties this vec without freeing its elements, e.g., once they were passed to the library."""
+        self.data = None
+        self.len = 0
+        self.capacity = 0
+
+    def free(self):
+        """Returns the elements to the library, afterwards this vec is empty."""
+        if self.data and c_lib is not None:
+            c_lib.pattern_ffi_vec_u32_destroy(self)
+        self._forget()
+
+    def __enter__(self) -> Vecu32:
+        return self
+
+    def __exit__(self, *args):
+        self.free()
+
+    def __del__(self):
+        # Views into other objects (e.g., struct fields) don't own their elements.
+        if self._b_needsfree_:
+            self.free()
+
+
 class OptionInner(ctypes.Structure):
     """May optionally hold a value."""
 
@@ -1552,10 +2175,100 @@ class SliceMutVec(ctypes.Structure):
         return self[len(self)-1]
 
 
+class VecVec3f32(ctypes.Structure):
+    """Elements owned by the library, freed once this is collected or its `with` block ends."""
+
+    # These fields represent the underlying C data layout
+    _fields_ = [
+        ("data", ctypes.POINTER(Vec3f32)),
+        ("len", ctypes.c_uint64),
+        ("capacity", ctypes.c_uint64),
+    ]
+
+    def __len__(self):
+        return self.len
+
+    def __getitem__(self, i) -> Vec3f32:
+        if i < 0:
+            index = self.len+i
+        else:
+            index = i
+
+        if index >= self.len:
+            raise IndexError("Index out of range")
+
+        return self.data[index]
+
+    def __iter__(self) -> typing.Iterable[Vec3f32]:
+        return _Iter(self)
+
+    def to_list(self) -> typing.List[Vec3f32]:
+        """Returns a copy of all elements, which stays valid after this vec was freed."""
+        if not self.data:
+            return []
+        return [Vec3f32.from_buffer_copy(self.data[i]) for i in range(self.len)]
+
+    def _forget(self):
+        """Empties this vec without freeing its elements, e.g., once they were passed to the library."""
+        self.data = None
+        self.len = 0
+        self.capacity = 0
+
+    def free(self):
+        """Returns the elements to the library, afterwards this vec is empty."""
+        if self.data and c_lib is not None:
+            c_lib.pattern_ffi_vec_vec3_destroy(self)
+        self._forget()
+
+    def __enter__(self) -> VecVec3f32:
+        return self
+
+    def __exit__(self, *args):
+        self.free()
+
+    def __del__(self):
+        # Views into other objects (e.g., struct fields) don't own their elements.
+        if self._b_needsfree_:
+            self.free()
+
+
+class MyCallbackData(ctypes.Structure):
+    """A callback carrying user data, can be created from a Python callable."""
+
+    # These fields represent the underlying C data layout
+    _fields_ = [
+        ("callback", ctypes.c_void_p),
+        ("data", ctypes.c_void_p),
+        ("destructor", ctypes.c_void_p),
+    ]
+
+    _boxed = None
+
+    def __init__(self, f = None):
+        """Wraps `f`, which is kept alive by this and every copy handed to the library."""
+        super().__init__()
+        if f is not None:
+            self._boxed = callbacks.fn_u32_pconst__rval_u32(lambda *args: f(*args[:-1]))
+            self.callback = ctypes.cast(self._boxed, ctypes.c_void_p)
+            self.data = id(self._boxed)
+            self.destructor = ctypes.cast(_CallbackData.release_fn, ctypes.c_void_p)
+
+    def _hand_over(self):
+        """Called before each call taking this, as the library releases every copy it got."""
+        if self._boxed is not None:
+            _CallbackData.box(self._boxed)
+
+
+
+
+ARRAY_U8 = (ctypes.c_uint8 * 4)(1, 2, 3, 4)
+VEC3_ONE = Vec3f32(x=1, y=1, z=1)
 
 
 class callbacks:
     """Helpers to define callbacks."""
+    fn_pconst = ctypes.CFUNCTYPE(None, ctypes.c_void_p)
+    fn_u32_pconst__rval_u32 = ctypes.CFUNCTYPE(ctypes.c_uint32, ctypes.c_uint32, ctypes.c_void_p)
     fn_u8_rval_u8 = ctypes.CFUNCTYPE(ctypes.c_uint8, ctypes.c_uint8)
     fn_Sliceu8_rval_u8 = ctypes.CFUNCTYPE(ctypes.c_uint8, Sliceu8)
     fn_SliceVec3f32_rval_Vec3f32 = ctypes.CFUNCTYPE(Vec3f32, SliceVec3f32)
@@ -1618,6 +2331,10 @@ class SimpleService:
  need annotations."""
         return c_lib.simple_service_method_result(self._ctx, anon1)
 
+    def method_result_value(self, x: int) -> int:
+        """ Methods returning a Result<T, _> become an `FFIResult<T, FFIError>`."""
+        return c_lib.simple_service_method_result_value(self._ctx, x)
+
     def method_value(self, x: int) -> int:
         """"""
         return c_lib.simple_service_method_value(self._ctx, x)
@@ -1692,6 +2409,17 @@ class SimpleService:
         rval = c_lib.simple_service_return_string(self._ctx, )
         return ctypes.string_at(rval)
 
+    def return_string_utf8(self, suffix: str) -> str:
+        """ Returns an owned copy of this service's string, followed by `suffix`."""
+        if isinstance(suffix, str):
+            suffix = Utf8Str.from_str(suffix)
+
+        rval = c_lib.simple_service_return_string_utf8(self._ctx, suffix)
+        try:
+            return rval.to_str()
+        finally:
+            rval.free()
+
     def method_void_ffi_error(self, ):
         """"""
         return c_lib.simple_service_method_void_ffi_error(self._ctx, )
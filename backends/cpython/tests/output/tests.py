@@ -48,6 +48,8 @@ class TestFunctions(unittest.TestCase):
         self.assertEqual(-i32_max, r.primitive_i32(i32_max))
         self.assertEqual(-i64_max, r.primitive_i64(i64_max))
 
+        self.assertEqual(ord("A"), r.primitive_char(ord("a")))
+
     def test_ptr(self):
         ptr = (ctypes.c_int64 * 100)(100, 2, 3)
         ptr_ptr = ctypes.POINTER(ctypes.POINTER(ctypes.c_int64))()
@@ -84,6 +86,26 @@ class TestFunctions(unittest.TestCase):
 
         self.assertEqual(9, r.callback(my_callback, 3))
 
+    def test_callback_with_data(self):
+        offset = 10
+
+        self.assertEqual(13, r.pattern_callback_with_data_1(lambda x: x + offset, 3))
+        self.assertEqual({}, r._CallbackData.boxes)
+
+    def test_callback_with_data_reused(self):
+        callback = r.MyCallbackData(lambda x: x * 2)
+        errors = []
+        hook, sys.unraisablehook = sys.unraisablehook, errors.append  # Errors in callbacks are only printed
+
+        try:
+            self.assertEqual(6, r.pattern_callback_with_data_1(callback, 3))
+            self.assertEqual(8, r.pattern_callback_with_data_1(callback, 4))
+        finally:
+            sys.unraisablehook = hook
+
+        self.assertEqual([], errors)
+        self.assertEqual({}, r._CallbackData.boxes)
+
     def test_generic(self):
         uint32 = (ctypes.c_uint32 * 10)(123)
 
@@ -174,6 +196,67 @@ class TestPatterns(unittest.TestCase):
 
         r.pattern_ffi_slice_delegate(callback)
 
+    def test_vec(self):
+        with r.pattern_ffi_vec_1(5) as vec:
+            self.assertEqual(5, len(vec))
+            self.assertEqual([0, 1, 2, 3, 4], vec.to_list())
+
+        self.assertEqual([], vec.to_list())
+
+        vec3 = r.pattern_ffi_vec_2(1.5, 3).to_list()
+        self.assertEqual(1.5, vec3[2].z)
+
+    def test_vec_elements_outlive_vec(self):
+        with r.pattern_ffi_vec_2(1.5, 3) as vec:
+            elements = vec.to_list()
+
+        other = r.pattern_ffi_vec_2(9.0, 3)  # Likely reuses the freed memory
+        self.assertEqual([1.5, 1.5, 1.5], [x.z for x in elements])
+        self.assertEqual(9.0, other[0].z)
+
+    def test_vec_passed_to_library_is_consumed(self):
+        with r.pattern_ffi_vec_1(5) as vec:
+            self.assertEqual(10, r.pattern_ffi_vec_3(vec))
+            self.assertEqual(0, len(vec))
+
+        vec = r.pattern_ffi_vec_1(5)
+        r.pattern_ffi_vec_u32_destroy(vec)
+        self.assertEqual(0, len(vec))
+        vec.free()
+
+    def test_string(self):
+        self.assertEqual(5, r.pattern_ffi_str_1("grüße"))
+        self.assertEqual("GRÜSSE\0", r.pattern_ffi_string_1("grüße\0"))
+        self.assertEqual("hello\0wörld", r.pattern_ffi_string_2())
+        self.assertEqual("", r.pattern_ffi_string_1(""))
+
+    def test_string_passed_to_library_is_consumed(self):
+        s = r.c_lib.pattern_ffi_string_2()
+        r.pattern_ffi_string_destroy(s)
+
+        self.assertEqual("", s.to_str())
+        s.free()
+
+    def test_result(self):
+        self.assertEqual(20, r.pattern_ffi_result_1(10))
+        self.assertRaises(Exception, r.pattern_ffi_result_1, 0xFFFFFFFF)
+
+        service = r.SimpleService.new_with(123)
+        self.assertEqual(133, service.method_result_value(10))
+        self.assertRaises(Exception, service.method_result_value, 0xFFFFFFFF)
+
+    def test_last_error(self):
+        with self.assertRaisesRegex(Exception, "Oh no"):
+            r.panics()
+
+        self.assertIn("Oh no", r.pattern_last_error_message())
+
+        with self.assertRaisesRegex(Exception, "Error in .*Bad"):
+            r.pattern_ffi_result_1(0xFFFFFFFF)
+
+        with self.assertRaisesRegex(Exception, "simple_service_new_failing"):
+            r.SimpleService.new_failing(1)
+
 
 if __name__ == '__main__':
     unittest.main()
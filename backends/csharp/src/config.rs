@@ -1,7 +1,10 @@
 use interoptopus::util::NamespaceMappings;
+#[cfg(feature = "serde")]
+use serde::Deserialize;
 
 /// The types to write for the given recorder.
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize))]
 pub enum WriteTypes {
     /// Only write items defined in the library for this namespace.
     Namespace,
@@ -23,6 +26,7 @@ impl WriteTypes {
 
 /// The access modifiers for generated CSharp types
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize))]
 pub enum CSharpVisibility {
     /// Mimics Rust visibility.
     AsDeclared,
@@ -46,6 +50,7 @@ impl CSharpVisibility {
 
 /// Whether and how `unsafe` in generated C# should be emitted.
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize))]
 pub enum Unsafe {
     /// Do not use C# `unsafe`.
     None,
@@ -67,6 +72,7 @@ impl Unsafe {
 
 /// Configures C# code generation.
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Deserialize), serde(default))]
 pub struct Config {
     /// The file header, e.g., `// (c) My Company`.
     pub file_header_comment: String,
@@ -1,6 +1,7 @@
 use heck::{ToLowerCamelCase, ToUpperCamelCase};
 use interoptopus::lang::c::{
     CType, CompositeType, ConstantValue, EnumType, Field, FnPointerType, Function, FunctionSignature, OpaqueType, Parameter, PrimitiveType, PrimitiveValue,
+    TaggedUnionType, UnionType,
 };
 use interoptopus::patterns::callbacks::NamedCallback;
 use interoptopus::patterns::TypePattern;
@@ -35,6 +36,11 @@ pub trait CSharpTypeConverter {
             PrimitiveType::I16 => "short".to_string(),
             PrimitiveType::I32 => "int".to_string(),
             PrimitiveType::I64 => "long".to_string(),
+            PrimitiveType::U128 | PrimitiveType::I128 => panic!("128-bit integers can't be used from C#, there is no blittable equivalent; `validate()` reports these."),
+            PrimitiveType::USize => "nuint".to_string(),
+            PrimitiveType::ISize => "nint".to_string(),
+            // C# `char` is a 16-bit UTF-16 code unit, a Rust `char` is a 32-bit scalar.
+            PrimitiveType::Char => "uint".to_string(),
             PrimitiveType::F32 => "float".to_string(),
             PrimitiveType::F64 => "double".to_string(),
         }
@@ -60,21 +66,38 @@ pub trait CSharpTypeConverter {
         x.rust_name().to_string()
     }
 
+    /// Converts a Rust union name `Bits` to a C# struct name `Bits`.
+    fn union_to_typename(&self, x: &UnionType) -> String {
+        x.rust_name().to_string()
+    }
+
+    /// Converts a Rust data-carrying enum `Shape` to a C# struct name `Shape`.
+    fn tagged_union_to_typename(&self, x: &TaggedUnionType) -> String {
+        x.rust_name().to_string()
+    }
+
     /// Checks if the type is on the C# side blittable, in particular, if it can be accessed via raw pointers and memcopied.
     fn is_blittable(&self, x: &CType) -> bool {
         match x {
             CType::Primitive(_) => true,
             CType::Composite(c) => c.fields().iter().all(|x| self.is_blittable(x.the_type())),
+            CType::Union(u) => u.fields().iter().all(|x| self.is_blittable(x.the_type())),
+            CType::TaggedUnion(t) => t.variants().iter().flat_map(|x| x.fields()).all(|x| self.is_blittable(x.the_type())),
             CType::Pattern(x) => match x {
                 TypePattern::AsciiPointer => false,
                 TypePattern::APIVersion => true,
                 TypePattern::FFIErrorEnum(_) => true,
                 TypePattern::Slice(_) => false,
                 TypePattern::SliceMut(_) => false,
+                TypePattern::Vec(_) => false,
+                TypePattern::Str(_) => false,
+                TypePattern::String(_) => false,
                 TypePattern::Option(_) => true,
+                TypePattern::Result(x) => x.the_type().fields().iter().all(|x| self.is_blittable(x.the_type())),
                 TypePattern::Bool => true,
                 TypePattern::CChar => true,
                 TypePattern::NamedCallback(_) => false,
+                TypePattern::CallbackWithData(_) => false,
             },
             CType::Array(_) => false, // TODO: should check inner and maybe return true
             CType::Enum(_) => true,
@@ -102,6 +125,8 @@ pub trait CSharpTypeConverter {
             CType::Enum(x) => self.enum_to_typename(x),
             CType::Opaque(x) => self.opaque_to_typename(x),
             CType::Composite(x) => self.composite_to_typename(x),
+            CType::Union(x) => self.union_to_typename(x),
+            CType::TaggedUnion(x) => self.tagged_union_to_typename(x),
             CType::ReadPointer(_) => "IntPtr".to_string(),
             CType::ReadWritePointer(_) => "IntPtr".to_string(),
             CType::FnPointer(x) => self.fnpointer_to_typename(x),
@@ -110,8 +135,13 @@ pub trait CSharpTypeConverter {
                 TypePattern::FFIErrorEnum(e) => self.enum_to_typename(e.the_enum()),
                 TypePattern::Slice(e) => self.composite_to_typename(e),
                 TypePattern::SliceMut(e) => self.composite_to_typename(e),
+                TypePattern::Vec(e) => self.composite_to_typename(e),
+                TypePattern::Str(e) => self.composite_to_typename(e),
+                TypePattern::String(e) => self.composite_to_typename(e),
                 TypePattern::Option(e) => self.composite_to_typename(e),
+                TypePattern::Result(e) => self.composite_to_typename(e.the_type()),
                 TypePattern::NamedCallback(e) => self.named_callback_to_typename(e),
+                TypePattern::CallbackWithData(e) => self.composite_to_typename(e.the_type()),
                 TypePattern::Bool => "Bool".to_string(),
                 TypePattern::CChar => "sbyte".to_string(),
                 TypePattern::APIVersion => self.to_typespecifier_in_field(&x.fallback_type(), field, composite),
@@ -127,6 +157,8 @@ pub trait CSharpTypeConverter {
             CType::Enum(x) => self.enum_to_typename(x),
             CType::Opaque(x) => self.opaque_to_typename(x),
             CType::Composite(x) => self.composite_to_typename(x),
+            CType::Union(x) => self.union_to_typename(x),
+            CType::TaggedUnion(x) => self.tagged_union_to_typename(x),
             CType::ReadPointer(z) => match **z {
                 CType::Opaque(_) => "IntPtr".to_string(),
                 CType::Primitive(PrimitiveType::Void) => "IntPtr".to_string(),
@@ -153,8 +185,13 @@ pub trait CSharpTypeConverter {
                 TypePattern::FFIErrorEnum(e) => self.enum_to_typename(e.the_enum()),
                 TypePattern::Slice(x) => self.composite_to_typename(x),
                 TypePattern::SliceMut(x) => self.composite_to_typename(x),
+                TypePattern::Vec(x) => self.composite_to_typename(x),
+                TypePattern::Str(x) => self.composite_to_typename(x),
+                TypePattern::String(x) => self.composite_to_typename(x),
                 TypePattern::Option(x) => self.composite_to_typename(x),
+                TypePattern::Result(x) => self.composite_to_typename(x.the_type()),
                 TypePattern::NamedCallback(x) => self.named_callback_to_typename(x),
+                TypePattern::CallbackWithData(x) => self.composite_to_typename(x.the_type()),
                 TypePattern::Bool => "Bool".to_string(),
                 TypePattern::CChar => "sbyte".to_string(),
                 TypePattern::APIVersion => self.to_typespecifier_in_param(&x.fallback_type()),
@@ -169,6 +206,8 @@ pub trait CSharpTypeConverter {
             CType::Enum(x) => self.enum_to_typename(x),
             CType::Opaque(x) => self.opaque_to_typename(x),
             CType::Composite(x) => self.composite_to_typename(x),
+            CType::Union(x) => self.union_to_typename(x),
+            CType::TaggedUnion(x) => self.tagged_union_to_typename(x),
             CType::ReadPointer(_) => "IntPtr".to_string(),
             CType::ReadWritePointer(_) => "IntPtr".to_string(),
             CType::FnPointer(x) => self.fnpointer_to_typename(x),
@@ -177,8 +216,13 @@ pub trait CSharpTypeConverter {
                 TypePattern::FFIErrorEnum(e) => self.enum_to_typename(e.the_enum()),
                 TypePattern::Slice(x) => self.composite_to_typename(x),
                 TypePattern::SliceMut(x) => self.composite_to_typename(x),
+                TypePattern::Vec(x) => self.composite_to_typename(x),
+                TypePattern::Str(x) => self.composite_to_typename(x),
+                TypePattern::String(x) => self.composite_to_typename(x),
                 TypePattern::Option(x) => self.composite_to_typename(x),
+                TypePattern::Result(x) => self.composite_to_typename(x.the_type()),
                 TypePattern::NamedCallback(x) => self.named_callback_to_typename(x),
+                TypePattern::CallbackWithData(x) => self.composite_to_typename(x.the_type()),
                 TypePattern::Bool => "Bool".to_string(),
                 TypePattern::CChar => "sbyte".to_string(),
                 TypePattern::APIVersion => self.to_typespecifier_in_rval(&x.fallback_type()),
@@ -198,9 +242,33 @@ pub trait CSharpTypeConverter {
                 PrimitiveValue::I16(x) => format!("{}", x),
                 PrimitiveValue::I32(x) => format!("{}", x),
                 PrimitiveValue::I64(x) => format!("{}", x),
+                PrimitiveValue::U128(_) | PrimitiveValue::I128(_) => {
+                    panic!("128-bit constants can't be used from C#, there is no blittable equivalent; `validate()` reports these.")
+                }
+                PrimitiveValue::USize(x) => format!("{}", x),
+                PrimitiveValue::ISize(x) => format!("{}", x),
+                PrimitiveValue::Char(x) => format!("{}", *x as u32),
                 PrimitiveValue::F32(x) => format!("{}", x),
                 PrimitiveValue::F64(x) => format!("{}", x),
             },
+            ConstantValue::String(x) => {
+                let mut rval = String::from("\"");
+                for c in x.chars() {
+                    match c {
+                        '"' => rval.push_str("\\\""),
+                        '\\' => rval.push_str("\\\\"),
+                        '\n' => rval.push_str("\\n"),
+                        '\r' => rval.push_str("\\r"),
+                        '\t' => rval.push_str("\\t"),
+                        c if c.is_control() => rval.push_str(&format!("\\u{:04x}", c as u32)),
+                        c => rval.push(c),
+                    }
+                }
+                rval.push('"');
+                rval
+            }
+            ConstantValue::Array(_, _) => panic!("Needs special handling in the writer."),
+            ConstantValue::Composite(_, _) => panic!("Needs special handling in the writer."),
         }
     }
 
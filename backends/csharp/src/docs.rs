@@ -49,8 +49,11 @@ impl<'a, W: CSharpWriter> DocGenerator<'a, W> {
         indented!(w, r#"### Classes"#)?;
         indented!(w, r#"Methods operating on common state."#)?;
 
-        for pattern in self.inventory.patterns().iter().map(|x| match x {
-            LibraryPattern::Service(s) => s,
+        for pattern in self.inventory.patterns().iter().filter_map(|x| match x {
+            LibraryPattern::Service(s) => Some(s),
+            LibraryPattern::Vec(_) => None,
+            LibraryPattern::String(_) => None,
+            LibraryPattern::LastError(_) => None,
         }) {
             let prefix = pattern.common_prefix();
             let doc = pattern.the_type().meta().documentation().lines().first().cloned().unwrap_or_default();
@@ -108,6 +111,42 @@ impl<'a, W: CSharpWriter> DocGenerator<'a, W> {
                     let c = p.fallback_type().as_composite_type().cloned().unwrap();
                     indented!(w, r#" - **[{}](#{})** - A pointer and length of un-owned elements."#, c.rust_name(), c.rust_name())?;
                 }
+                CType::Pattern(p @ TypePattern::Vec(_)) => {
+                    let c = p.fallback_type().as_composite_type().cloned().unwrap();
+                    indented!(
+                        w,
+                        r#" - **[{}](#{})** - Elements owned by the library, functions returning them copy them into an array."#,
+                        c.rust_name(),
+                        c.rust_name()
+                    )?;
+                }
+                CType::Pattern(p @ TypePattern::Str(_)) => {
+                    let c = p.fallback_type().as_composite_type().cloned().unwrap();
+                    indented!(w, r#" - **[{}](#{})** - A UTF-8 `string` passed to the library."#, c.rust_name(), c.rust_name())?;
+                }
+                CType::Pattern(p @ TypePattern::String(_)) => {
+                    let c = p.fallback_type().as_composite_type().cloned().unwrap();
+                    indented!(
+                        w,
+                        r#" - **[{}](#{})** - A UTF-8 `string` owned by the library, functions returning it convert it to a `string`."#,
+                        c.rust_name(),
+                        c.rust_name()
+                    )?;
+                }
+                CType::Pattern(p @ TypePattern::Result(_)) => {
+                    let c = p.fallback_type().as_composite_type().cloned().unwrap();
+                    indented!(w, r#" - **[{}](#{})** - A value or an error, unwrapped via `Ok()`."#, c.rust_name(), c.rust_name())?;
+                }
+                CType::Pattern(p @ TypePattern::CallbackWithData(_)) => {
+                    let c = p.fallback_type().as_composite_type().cloned().unwrap();
+                    indented!(
+                        w,
+                        r#" - **[{}](#{})** - A callback carrying user data, created from a `{}Delegate`."#,
+                        c.rust_name(),
+                        c.rust_name(),
+                        c.rust_name()
+                    )?;
+                }
                 _ => continue,
             }
         }
@@ -127,6 +166,11 @@ impl<'a, W: CSharpWriter> DocGenerator<'a, W> {
                 CType::Composite(e) => self.write_composite(w, e)?,
                 CType::Pattern(p @ TypePattern::Option(_)) => self.write_composite(w, p.fallback_type().as_composite_type().unwrap())?,
                 CType::Pattern(p @ TypePattern::Slice(_)) => self.write_composite(w, p.fallback_type().as_composite_type().unwrap())?,
+                CType::Pattern(p @ TypePattern::Vec(_)) => self.write_composite(w, p.fallback_type().as_composite_type().unwrap())?,
+                CType::Pattern(p @ TypePattern::Str(_)) => self.write_composite(w, p.fallback_type().as_composite_type().unwrap())?,
+                CType::Pattern(p @ TypePattern::String(_)) => self.write_composite(w, p.fallback_type().as_composite_type().unwrap())?,
+                CType::Pattern(p @ TypePattern::Result(_)) => self.write_composite(w, p.fallback_type().as_composite_type().unwrap())?,
+                CType::Pattern(p @ TypePattern::CallbackWithData(_)) => self.write_composite(w, p.fallback_type().as_composite_type().unwrap())?,
                 _ => continue,
             };
 
@@ -245,8 +289,11 @@ impl<'a, W: CSharpWriter> DocGenerator<'a, W> {
     pub fn write_services(&self, w: &mut IndentWriter) -> Result<(), Error> {
         indented!(w, r#"# Classes"#)?;
 
-        for pattern in self.inventory.patterns().iter().map(|x| match x {
-            LibraryPattern::Service(s) => s,
+        for pattern in self.inventory.patterns().iter().filter_map(|x| match x {
+            LibraryPattern::Service(s) => Some(s),
+            LibraryPattern::Vec(_) => None,
+            LibraryPattern::String(_) => None,
+            LibraryPattern::LastError(_) => None,
         }) {
             let prefix = pattern.common_prefix();
             let doc = pattern.the_type().meta().documentation().lines();
@@ -295,11 +342,7 @@ impl<'a, W: CSharpWriter> DocGenerator<'a, W> {
                     .function_name_to_csharp_name(x, FunctionNameFlavor::CSharpMethodNameWithoutClass(&prefix));
                 let target = format!("{}", fname);
 
-                let rval = match x.signature().rval() {
-                    CType::Pattern(TypePattern::FFIErrorEnum(_)) => "void".to_string(),
-                    CType::Pattern(TypePattern::AsciiPointer) => "string".to_string(),
-                    _ => self.csharp_writer.converter().to_typespecifier_in_rval(x.signature().rval()),
-                };
+                let rval = self.csharp_writer.helper().converted_rval(x.signature().rval());
 
                 indented!(w, r#"### <a name="{}">**{}**</a>"#, target, target)?;
 
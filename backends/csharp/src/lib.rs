@@ -114,9 +114,10 @@
 //! }
 //! ```
 
+use interoptopus::validate::Validation;
 use interoptopus::writer::IndentWriter;
 use interoptopus::Interop;
-use interoptopus::{Error, Inventory};
+use interoptopus::{Error, ErrorContext, Inventory};
 use overloads::OverloadWriter;
 
 mod config;
@@ -124,6 +125,7 @@ mod converter;
 mod docs;
 pub mod overloads;
 mod testing;
+mod validate;
 mod writer;
 
 pub use config::{CSharpVisibility, Config, DocConfig, Unsafe, WriteTypes};
@@ -158,7 +160,14 @@ impl Generator {
 
 impl Interop for Generator {
     fn write_to(&self, w: &mut IndentWriter) -> Result<(), Error> {
-        self.write_all(w)
+        self.validate()
+            .into_result()
+            .and_then(|_| self.write_all(w))
+            .map_err(|e| e.with_context(ErrorContext::Backend("C#".to_string())))
+    }
+
+    fn validate(&self) -> Validation {
+        validate::validate(self)
     }
 }
 
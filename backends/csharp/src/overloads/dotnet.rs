@@ -13,6 +13,7 @@ use std::ops::Deref;
 /// In most cases adding this overload provider is the right thing to do, as it generates
 ///
 /// - `my_array[]` support for slices,
+/// - delegates in place of callbacks with data,
 /// - much faster (up to 150x) .NET Core slice copies (with [`Unsafe::UnsafePlatformMemCpy`](crate::Unsafe::UnsafePlatformMemCpy)),
 /// - service overloads.
 pub struct DotNet {}
@@ -29,11 +30,22 @@ impl DotNet {
                 CType::Pattern(x) => matches!(x, TypePattern::Slice(_) | TypePattern::SliceMut(_)),
                 _ => false,
             },
-            CType::Pattern(x) => matches!(x, TypePattern::Slice(_) | TypePattern::SliceMut(_)),
+            CType::Pattern(x) => matches!(
+                x,
+                TypePattern::Slice(_) | TypePattern::SliceMut(_) | TypePattern::Str(_) | TypePattern::CallbackWithData(_)
+            ),
             _ => false,
         })
     }
 
+    /// If the function returns a UTF-8 string we can convert to a `string`, a vec we can copy, or a result we can unwrap.
+    fn has_converted_rval(&self, h: &Helper, signature: &FunctionSignature) -> bool {
+        match signature.rval() {
+            CType::Pattern(TypePattern::Str(_) | TypePattern::Result(_)) => true,
+            rval => h.owned_rval(rval).is_some(),
+        }
+    }
+
     fn pattern_to_native_in_signature(&self, h: &Helper, param: &Parameter, _signature: &FunctionSignature) -> String {
         match param.the_type() {
             CType::Pattern(p) => match p {
@@ -58,6 +70,8 @@ impl DotNet {
                         .expect("Must be pointer");
                     format!("{}[]", h.converter.to_typespecifier_in_param(element_type))
                 }
+                TypePattern::Str(_) => "string".to_string(),
+                TypePattern::CallbackWithData(x) => format!("{}Delegate", x.the_type().rust_name()),
                 _ => h.converter.to_typespecifier_in_param(param.the_type()),
             },
             CType::ReadPointer(x) | CType::ReadWritePointer(x) => match x.deref() {
@@ -109,12 +123,14 @@ impl OverloadWriter for DotNet {
     fn write_function_overload(&self, w: &mut IndentWriter, h: Helper, function: &Function, write_for: WriteFor) -> Result<(), Error> {
         let has_overload = self.has_overloadable(function.signature());
         let has_error_enum = h.converter.has_ffi_error_rval(function.signature());
+        let has_converted_rval = self.has_converted_rval(&h, function.signature());
 
         // If there is nothing to write, don't do it
-        if !has_overload && !has_error_enum {
+        if !has_overload && !has_error_enum && !has_converted_rval {
             return Ok(());
         }
 
+        let mut to_encode = Vec::new();
         let mut to_pin_name = Vec::new();
         let mut to_pin_slice_type = Vec::new();
         let mut to_invoke = Vec::new();
@@ -125,17 +141,13 @@ impl OverloadWriter for DotNet {
                 false => FunctionNameFlavor::RawFFIName,
             },
         );
-        let this_name = if has_error_enum && !has_overload {
+        let this_name = if (has_error_enum || has_converted_rval) && !has_overload {
             format!("{}_checked", raw_name)
         } else {
             raw_name
         };
 
-        let rval = match function.signature().rval() {
-            CType::Pattern(TypePattern::FFIErrorEnum(_)) => "void".to_string(),
-            CType::Pattern(TypePattern::AsciiPointer) => "string".to_string(),
-            _ => h.converter.to_typespecifier_in_rval(function.signature().rval()),
-        };
+        let rval = h.converted_rval(function.signature().rval());
 
         let mut params = Vec::new();
         for (_, p) in function.signature().params().iter().enumerate() {
@@ -155,19 +167,29 @@ impl OverloadWriter for DotNet {
 
             match p.the_type() {
                 CType::Pattern(TypePattern::Slice(_) | TypePattern::SliceMut(_)) => {
-                    to_pin_name.push(name);
+                    to_pin_name.push(name.to_string());
                     to_pin_slice_type.push(the_type);
                     to_invoke.push(format!("{}_slice", name));
                 }
+                CType::Pattern(TypePattern::Str(_)) => {
+                    to_encode.push(name);
+                    to_pin_name.push(format!("{}_utf8", name));
+                    to_pin_slice_type.push(the_type);
+                    to_invoke.push(format!("{}_utf8_slice", name));
+                }
+                // Rust frees the boxed delegate once per call, so each call gets its own box.
+                CType::Pattern(TypePattern::CallbackWithData(x)) => {
+                    to_invoke.push(format!("new {}({})", x.the_type().rust_name(), name));
+                }
                 CType::ReadPointer(x) | CType::ReadWritePointer(x) => match x.deref() {
                     CType::Pattern(x) => match x {
                         TypePattern::Slice(_) => {
-                            to_pin_name.push(name);
+                            to_pin_name.push(name.to_string());
                             to_pin_slice_type.push(the_type.replace("ref ", ""));
                             to_invoke.push(format!("ref {}_slice", name));
                         }
                         TypePattern::SliceMut(_) => {
-                            to_pin_name.push(name);
+                            to_pin_name.push(name.to_string());
                             to_pin_slice_type.push(the_type.replace("ref ", ""));
                             to_invoke.push(format!("ref {}_slice", name));
                         }
@@ -196,6 +218,10 @@ impl OverloadWriter for DotNet {
         indented!(w, "{}", signature)?;
         indented!(w, r#"{{"#)?;
 
+        for name in &to_encode {
+            indented!(w, [_], r#"var {}_utf8 = System.Text.Encoding.UTF8.GetBytes({});"#, name, name)?;
+        }
+
         if h.config.use_unsafe.any_unsafe() {
             if !to_pin_name.is_empty() {
                 indented!(w, [_], r#"unsafe"#)?;
@@ -219,7 +245,7 @@ impl OverloadWriter for DotNet {
             );
             let call = format!(r#"{}({});"#, fn_name, to_invoke.join(", "));
 
-            write_function_overloaded_invoke_with_error_handling(w, function, &call)?;
+            write_function_overloaded_invoke_with_error_handling(w, &h, function, &call)?;
 
             if !to_pin_name.is_empty() {
                 for _ in to_pin_name.iter() {
@@ -259,7 +285,7 @@ impl OverloadWriter for DotNet {
                 },
             );
             let call = format!(r#"{}({});"#, fn_name, to_invoke.join(", "));
-            write_function_overloaded_invoke_with_error_handling(w, function, &call)?;
+            write_function_overloaded_invoke_with_error_handling(w, &h, function, &call)?;
 
             if !to_pin_name.is_empty() {
                 w.unindent();
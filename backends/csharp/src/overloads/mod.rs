@@ -51,10 +51,12 @@
 //!
 
 use interoptopus::lang::c::{CType, CompositeType, Documentation, Field, Function, Parameter, PrimitiveType};
+use interoptopus::patterns::result::FFIErrorEnum;
 use interoptopus::patterns::service::Service;
-use interoptopus::patterns::TypePattern;
+use interoptopus::patterns::vec::vec_element_type;
+use interoptopus::patterns::{LibraryPattern, TypePattern};
 use interoptopus::writer::{IndentWriter, WriteFor};
-use interoptopus::{indented, Error};
+use interoptopus::{indented, Error, Inventory};
 
 mod dotnet;
 mod unity;
@@ -68,6 +70,71 @@ pub use unity::Unity;
 pub struct Helper<'a> {
     pub config: &'a Config,
     pub converter: &'a dyn CSharpTypeConverter,
+    pub inventory: &'a Inventory,
+}
+
+impl<'a> Helper<'a> {
+    /// The function freeing returned UTF-8 strings, if one was registered.
+    pub fn string_destructor(&self) -> Option<&'a Function> {
+        self.inventory.patterns().iter().find_map(|x| match x {
+            LibraryPattern::String(x) => Some(x.destructor()),
+            _ => None,
+        })
+    }
+
+    /// The function freeing returned vecs of `the_type`, if one was registered.
+    pub fn vec_destructor(&self, the_type: &CompositeType) -> Option<&'a Function> {
+        self.inventory.patterns().iter().find_map(|x| match x {
+            LibraryPattern::Vec(x) if x.the_type() == the_type => Some(x.destructor()),
+            _ => None,
+        })
+    }
+
+    /// For an `rval` owned by the caller, the function freeing it and the method copying it into a managed value.
+    pub fn owned_rval(&self, rval: &CType) -> Option<(&'a Function, &'static str)> {
+        match rval {
+            CType::Pattern(TypePattern::String(_)) => self.string_destructor().map(|x| (x, "ToString()")),
+            CType::Pattern(TypePattern::Vec(x)) => self.vec_destructor(x).map(|x| (x, "ToArray()")),
+            _ => None,
+        }
+    }
+
+    /// The function returning the last error message, if one was registered.
+    pub fn last_error_function(&self) -> Option<&'a Function> {
+        self.inventory.patterns().iter().find_map(|x| match x {
+            LibraryPattern::LastError(x) => Some(x.function()),
+            _ => None,
+        })
+    }
+
+    /// Creates an `InteropException` for the error `rval`, with the last error message if available.
+    pub fn new_exception(&self, e: &FFIErrorEnum, rval: &str) -> String {
+        let error_name = e.the_enum().rust_name();
+
+        match self.last_error_function() {
+            Some(f) => {
+                let flavor = match self.config.rename_symbols {
+                    true => FunctionNameFlavor::CSharpMethodNameWithClass,
+                    false => FunctionNameFlavor::RawFFIName,
+                };
+                let last_error = self.converter.function_name_to_csharp_name(f, flavor);
+                format!("new InteropException<{}>({}, {}.{}().ToString())", error_name, rval, self.config.class, last_error)
+            }
+            None => format!("new InteropException<{}>({})", error_name, rval),
+        }
+    }
+
+    /// The type convenience methods return instead of the raw `rval`, e.g., `string` for strings.
+    pub fn converted_rval(&self, rval: &CType) -> String {
+        match rval {
+            CType::Pattern(TypePattern::FFIErrorEnum(_)) => "void".to_string(),
+            CType::Pattern(TypePattern::AsciiPointer | TypePattern::Str(_)) => "string".to_string(),
+            CType::Pattern(TypePattern::String(_)) if self.string_destructor().is_some() => "string".to_string(),
+            CType::Pattern(TypePattern::Vec(x)) if self.vec_destructor(x).is_some() => format!("{}[]", self.converter.to_typespecifier_in_rval(vec_element_type(x))),
+            CType::Pattern(TypePattern::Result(x)) => self.converter.to_typespecifier_in_rval(x.value_type()),
+            _ => self.converter.to_typespecifier_in_rval(rval),
+        }
+    }
 }
 
 #[doc(hidden)]
@@ -105,20 +172,29 @@ pub trait OverloadWriter {
 
 /// Writes common error handling based on a call's return type.
 #[rustfmt::skip]
-fn write_function_overloaded_invoke_with_error_handling(w: &mut IndentWriter, function: &Function, fn_call: &str) -> Result<(), Error> {
+fn write_function_overloaded_invoke_with_error_handling(w: &mut IndentWriter, h: &Helper, function: &Function, fn_call: &str) -> Result<(), Error> {
 
     match function.signature().rval() {
         CType::Pattern(TypePattern::FFIErrorEnum(e)) => {
             indented!(w, [_], r#"var rval = {};"#, fn_call)?;
             indented!(w, [_], r#"if (rval != {}.{})"#, e.the_enum().rust_name(), e.success_variant().name())?;
             indented!(w, [_], r#"{{"#)?;
-            indented!(w, [_ _], r#"throw new InteropException<{}>(rval);"#, e.the_enum().rust_name())?;
+            indented!(w, [_ _], r#"throw {};"#, h.new_exception(e, "rval"))?;
             indented!(w, [_], r#"}}"#)?;
         }
         CType::Pattern(TypePattern::AsciiPointer) => {
             indented!(w, [_], r#"var s = {};"#, fn_call)?;
             indented!(w, [_], r#"return Marshal.PtrToStringAnsi(s);"#)?;
         }
+        CType::Pattern(TypePattern::Str(_)) => {
+            indented!(w, [_], r#"var rval = {};"#, fn_call)?;
+            indented!(w, [_], r#"return rval.ToString();"#)?;
+        }
+        CType::Pattern(TypePattern::Result(_)) => {
+            indented!(w, [_], r#"var rval = {};"#, fn_call)?;
+            indented!(w, [_], r#"return rval.Ok();"#)?;
+        }
+        rval if h.owned_rval(rval).is_some() => write_owned_rval_copy(w, h, rval, fn_call)?,
         CType::Primitive(PrimitiveType::Void) => {
             indented!(w, [_], r#"{};"#, fn_call)?;
         }
@@ -130,6 +206,30 @@ fn write_function_overloaded_invoke_with_error_handling(w: &mut IndentWriter, fu
     Ok(())
 }
 
+/// Writes a call returning an owned `rval`, which is copied into a managed value and then freed.
+pub(crate) fn write_owned_rval_copy(w: &mut IndentWriter, h: &Helper, rval: &CType, fn_call: &str) -> Result<(), Error> {
+    let (destructor, copy) = h.owned_rval(rval).expect("rval must be owned");
+    let destructor = h.converter.function_name_to_csharp_name(
+        destructor,
+        match h.config.rename_symbols {
+            true => FunctionNameFlavor::CSharpMethodNameWithClass,
+            false => FunctionNameFlavor::RawFFIName,
+        },
+    );
+
+    indented!(w, [_], r#"var rval = {};"#, fn_call)?;
+    indented!(w, [_], r#"try"#)?;
+    indented!(w, [_], r#"{{"#)?;
+    indented!(w, [_ _], r#"return rval.{};"#, copy)?;
+    indented!(w, [_], r#"}}"#)?;
+    indented!(w, [_], r#"finally"#)?;
+    indented!(w, [_], r#"{{"#)?;
+    indented!(w, [_ _], r#"{}.{}(rval);"#, h.config.class, destructor)?;
+    indented!(w, [_], r#"}}"#)?;
+
+    Ok(())
+}
+
 /// Writes common service overload code
 fn write_common_service_method_overload<FPatternMap: Fn(&Helper, &Parameter) -> String>(
     w: &mut IndentWriter,
@@ -145,11 +245,7 @@ fn write_common_service_method_overload<FPatternMap: Fn(&Helper, &Parameter) ->
 
     // Write checked method. These are "normal" methods that accept
     // common C# types.
-    let rval = match function.signature().rval() {
-        CType::Pattern(TypePattern::FFIErrorEnum(_)) => "void".to_string(),
-        CType::Pattern(TypePattern::AsciiPointer) => "string".to_string(),
-        _ => h.converter.to_typespecifier_in_rval(function.signature().rval()),
-    };
+    let rval = h.converted_rval(function.signature().rval());
 
     // For every parameter except the first, figure out how we should forward
     // it to the invocation we perform.
@@ -206,6 +302,9 @@ fn write_common_service_method_overload<FPatternMap: Fn(&Helper, &Parameter) ->
         CType::Pattern(TypePattern::FFIErrorEnum(_)) => {
             indented!(w, [_], r#"{};"#, fn_call)?;
         }
+        CType::Pattern(TypePattern::Result(_)) => {
+            indented!(w, [_], r#"return {}.Ok();"#, fn_call)?;
+        }
         CType::Primitive(PrimitiveType::Void) => {
             indented!(w, [_], r#"{};"#, fn_call)?;
         }
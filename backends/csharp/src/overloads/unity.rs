@@ -226,11 +226,7 @@ impl OverloadWriter for Unity {
             raw_name
         };
 
-        let rval = match function.signature().rval() {
-            CType::Pattern(TypePattern::FFIErrorEnum(_)) => "void".to_string(),
-            CType::Pattern(TypePattern::AsciiPointer) => "string".to_string(),
-            _ => h.converter.to_typespecifier_in_rval(function.signature().rval()),
-        };
+        let rval = h.converted_rval(function.signature().rval());
 
         let mut params = Vec::new();
         for (_, p) in function.signature().params().iter().enumerate() {
@@ -300,7 +296,7 @@ impl OverloadWriter for Unity {
             },
         );
         let call = format!(r#"{}({});"#, fn_name, to_invoke.join(", "));
-        write_function_overloaded_invoke_with_error_handling(w, function, &call)?;
+        write_function_overloaded_invoke_with_error_handling(w, &h, function, &call)?;
 
         indented!(w, r#"}}"#)?;
         indented!(w, r#"#endif"#)?;
@@ -7,7 +7,7 @@ use std::process::Command;
 
 /// If `dotnet` is installed, run the command as `dotnet command` from `path`, ignore and succeed otherwise.
 pub fn run_dotnet_command_if_installed(path: impl AsRef<Path>, command: &str) -> Result<String, Error> {
-    let child = match Command::new("dotnet").arg(command).current_dir(path).spawn() {
+    let output = match Command::new("dotnet").arg(command).current_dir(path).output() {
         Ok(x) => x,
         Err(x @ std::io::Error { .. }) if x.kind() == ErrorKind::NotFound => {
             return Ok("dotnet not found, skipped".to_string());
@@ -15,11 +15,13 @@ pub fn run_dotnet_command_if_installed(path: impl AsRef<Path>, command: &str) ->
         Err(x) => return Err(Error::IO(x)),
     };
 
-    let output = child.wait_with_output()?;
-
     if output.status.success() {
         Ok(output.status.to_string())
     } else {
-        Err(Error::TestFailed)
+        Err(Error::TestFailed {
+            command: format!("dotnet {}", command),
+            // `dotnet test` reports build errors and failed tests on `stdout`.
+            output: format!("{}{}", String::from_utf8_lossy(&output.stdout), String::from_utf8_lossy(&output.stderr)),
+        })
     }
 }
@@ -0,0 +1,141 @@
+use crate::converter::CSharpTypeConverter;
+use crate::{CSharpWriter, Generator};
+use interoptopus::lang::c::{CType, PrimitiveType};
+use interoptopus::patterns::TypePattern;
+use interoptopus::validate::{Level, Validation};
+
+/// Keywords of C#, none of which we escape with `@`.
+const RESERVED: &[&str] = &[
+    "abstract",
+    "as",
+    "base",
+    "bool",
+    "break",
+    "byte",
+    "case",
+    "catch",
+    "char",
+    "checked",
+    "class",
+    "const",
+    "continue",
+    "decimal",
+    "default",
+    "delegate",
+    "do",
+    "double",
+    "else",
+    "enum",
+    "event",
+    "explicit",
+    "extern",
+    "false",
+    "finally",
+    "fixed",
+    "float",
+    "for",
+    "foreach",
+    "goto",
+    "if",
+    "implicit",
+    "in",
+    "int",
+    "interface",
+    "internal",
+    "is",
+    "lock",
+    "long",
+    "namespace",
+    "new",
+    "null",
+    "object",
+    "operator",
+    "out",
+    "override",
+    "params",
+    "private",
+    "protected",
+    "public",
+    "readonly",
+    "ref",
+    "return",
+    "sbyte",
+    "sealed",
+    "short",
+    "sizeof",
+    "stackalloc",
+    "static",
+    "string",
+    "struct",
+    "switch",
+    "this",
+    "throw",
+    "true",
+    "try",
+    "typeof",
+    "uint",
+    "ulong",
+    "unchecked",
+    "unsafe",
+    "ushort",
+    "using",
+    "virtual",
+    "void",
+    "volatile",
+    "while",
+];
+
+pub(crate) fn validate(generator: &Generator) -> Validation {
+    let inventory = generator.inventory();
+    let config = generator.config();
+    let mut validation = inventory.validate();
+
+    validation.check_reserved_words(inventory, "C#", RESERVED);
+    validation.check_unsupported_primitives(inventory, "C#", &[PrimitiveType::U128, PrimitiveType::I128]);
+
+    for namespace in inventory.namespaces() {
+        if config.namespace_mappings.get(namespace).is_none() {
+            let message = "has no entry in `namespace_mappings`";
+            validation.push(Level::Error, format!("namespace `{}`", namespace), message);
+        }
+    }
+
+    for t in inventory.ctypes() {
+        match t {
+            CType::Composite(x) if !config.unroll_struct_arrays => {
+                for field in x.fields().iter().filter(|x| matches!(x.the_type(), CType::Array(_))) {
+                    let message = format!("field `{}` is an array, which requires `unroll_struct_arrays`", field.name());
+                    validation.push(Level::Error, format!("struct {}", x.rust_name()), message);
+                }
+            }
+            CType::Pattern(TypePattern::Slice(x) | TypePattern::SliceMut(x)) if config.use_unsafe.any_unsafe() => {
+                let element = x.fields().iter().find(|x| x.name() == "data").and_then(|x| x.the_type().deref_pointer());
+
+                // Slices of blittable elements are read via pointers, which doesn't work if C# considers a type
+                // blittable that contains a `string` or delegate, e.g., `FFISlice<FFIOption<AsciiPointer>>`.
+                if let Some(element) = element.filter(|x| generator.converter().is_blittable(x) && contains_managed(x)) {
+                    let message = format!(
+                        "elements `{}` contain strings or delegates, which can't be read with `use_unsafe` enabled",
+                        element.name_within_lib()
+                    );
+                    validation.push(Level::Error, format!("slice {}", x.rust_name()), message);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    validation
+}
+
+/// Whether C# represents this type with, or embeds, a managed type.
+fn contains_managed(t: &CType) -> bool {
+    match t {
+        CType::Composite(x) => x.fields().iter().any(|x| contains_managed(x.the_type())),
+        CType::Union(x) => x.fields().iter().any(|x| contains_managed(x.the_type())),
+        CType::TaggedUnion(x) => x.variants().iter().flat_map(|x| x.fields()).any(|x| contains_managed(x.the_type())),
+        CType::Pattern(TypePattern::AsciiPointer | TypePattern::NamedCallback(_) | TypePattern::CallbackWithData(_)) => true,
+        CType::Pattern(TypePattern::Option(x)) => x.fields().iter().any(|x| contains_managed(x.the_type())),
+        _ => false,
+    }
+}
@@ -1,10 +1,15 @@
 use crate::config::{Config, Unsafe, WriteTypes};
 use crate::converter::{CSharpTypeConverter, Converter, FunctionNameFlavor};
-use crate::overloads::{Helper, OverloadWriter};
-use interoptopus::lang::c::{CType, CompositeType, Constant, Documentation, EnumType, Field, FnPointerType, Function, Meta, PrimitiveType, Variant, Visibility};
-use interoptopus::patterns::api_guard::inventory_hash;
-use interoptopus::patterns::callbacks::NamedCallback;
+use crate::overloads::{write_owned_rval_copy, Helper, OverloadWriter};
+use interoptopus::lang::c::{
+    CType, CompositeType, Constant, ConstantValue, Documentation, EnumType, Field, FnPointerType, Function, Meta, PrimitiveType, TagPlacement, TaggedUnionType, UnionType, Variant,
+    Visibility,
+};
+use interoptopus::patterns::api_guard::APIVersion;
+use interoptopus::patterns::callbacks::{CallbackWithData, NamedCallback};
+use interoptopus::patterns::result::FFIResultType;
 use interoptopus::patterns::service::Service;
+use interoptopus::patterns::vec::vec_element_type;
 use interoptopus::patterns::{LibraryPattern, TypePattern};
 use interoptopus::util::{is_global_type, longest_common_prefix};
 use interoptopus::writer::{IndentWriter, WriteFor};
@@ -26,6 +31,7 @@ pub trait CSharpWriter {
         Helper {
             config: self.config(),
             converter: self.converter(),
+            inventory: self.inventory(),
         }
     }
 
@@ -80,23 +86,32 @@ pub trait CSharpWriter {
         indented!(w, r#"static {}()"#, self.config().class)?;
         indented!(w, r#"{{"#)?;
 
-        // Check if there is a API version marker for us to write
+        // Check if there is a API version marker for us to write; it only exists in the class of its own namespace.
         if let Some(api_guard) = self
             .inventory()
             .functions()
             .iter()
-            .find(|x| matches!(x.signature().rval(), CType::Pattern(TypePattern::APIVersion)))
+            .find(|x| matches!(x.signature().rval(), CType::Pattern(TypePattern::APIVersion)) && self.should_emit_by_meta(x.meta()))
         {
-            let version = inventory_hash(self.inventory());
+            let version = APIVersion::from_inventory(self.inventory());
             let flavor = match self.config().rename_symbols {
                 true => FunctionNameFlavor::CSharpMethodNameWithClass,
                 false => FunctionNameFlavor::RawFFIName,
             };
             let fn_call = self.converter().function_name_to_csharp_name(api_guard, flavor);
+            let (major, minor, hash) = (version.major(), version.minor(), version.hash());
             indented!(w, [_], r#"var api_version = {}.{}();"#, self.config().class, fn_call)?;
-            indented!(w, [_], r#"if (api_version != {}ul)"#, version)?;
+            indented!(w, [_], r#"var api_major = api_version >> 48;"#)?;
+            indented!(w, [_], r#"var api_minor = (api_version >> 32) & 0xFFFF;"#)?;
+            // C# warns about `api_minor < 0` on unsigned values, so we leave out that check for minor version 0.
+            let (minor_older, library_older) = match minor {
+                0 => (String::new(), format!("api_major < {}", major)),
+                _ => (format!("api_minor < {} || ", minor), format!("api_major < {} || (api_major == {} && api_minor < {})", major, major, minor)),
+            };
+            indented!(w, [_], r#"if (api_major != {} || {}(api_minor == {} && api_version != 0x{:016X}ul))"#, major, minor_older, minor, version.bits())?;
             indented!(w, [_], r#"{{"#)?;
-            indented!(w, [_ _], r#"throw new TypeLoadException($"API reports hash {{api_version}} which differs from hash in bindings ({}). You probably forgot to update / copy either the bindings or the library.");"#, version)?;
+            indented!(w, [_ _], r#"var reason = {} ? "the library is older" : api_major == {} && api_minor == {} ? "both report the same version but differ" : "the bindings are older";"#, library_older, major, minor)?;
+            indented!(w, [_ _], r#"throw new TypeLoadException($"Library has API version {{api_major}}.{{api_minor}} (hash {{api_version & 0xFFFFFFFF}}), bindings need {}.{} (hash {}); {{reason}}. You probably forgot to update / copy either the bindings or the library.");"#, major, minor, hash)?;
             indented!(w, [_], r#"}}"#)?;
         }
 
@@ -108,7 +123,7 @@ pub trait CSharpWriter {
     fn write_constants(&self, w: &mut IndentWriter) -> Result<(), Error> {
         for constant in self.inventory().constants() {
             if self.should_emit_by_meta(constant.meta()) {
-                self.write_constant(w, constant)?;
+                self.write_constant(w, constant).map_err(|e| e.with_item(format!("const {}", constant.name())))?;
                 w.newline()?;
             }
         }
@@ -118,18 +133,75 @@ pub trait CSharpWriter {
 
     fn write_constant(&self, w: &mut IndentWriter, constant: &Constant) -> Result<(), Error> {
         self.debug(w, "write_constant")?;
-        let rval = self.converter().to_typespecifier_in_rval(&constant.the_type());
         let name = constant.name();
-        let value = self.converter().constant_value_to_value(constant.value());
 
         self.write_documentation(w, constant.meta().documentation())?;
-        indented!(w, r#"public const {} {} = ({}) {};"#, rval, name, rval, value)
+
+        match constant.value() {
+            ConstantValue::Primitive(_) => {
+                let rval = self.converter().to_typespecifier_in_rval(&constant.the_type());
+                let value = self.converter().constant_value_to_value(constant.value());
+                indented!(w, r#"public const {} {} = ({}) {};"#, rval, name, rval, value)
+            }
+            ConstantValue::String(_) => {
+                let value = self.converter().constant_value_to_value(constant.value());
+                indented!(w, r#"public const string {} = {};"#, name, value)
+            }
+            // Arrays and structs can't be `const` in C#.
+            ConstantValue::Array(_, _) | ConstantValue::Composite(_, _) => {
+                let rval = self.constant_type_name(&constant.the_type());
+                let value = self.constant_initializer(constant.value());
+                indented!(w, r#"public static readonly {} {} = {};"#, rval, name, value)
+            }
+        }
+    }
+
+    /// Type of a `static readonly` constant, arrays become C# arrays instead of being unrolled.
+    fn constant_type_name(&self, the_type: &CType) -> String {
+        match the_type {
+            CType::Array(x) => format!("{}[]", self.constant_type_name(x.array_type())),
+            x => self.converter().to_typespecifier_in_rval(x),
+        }
+    }
+
+    /// Expression producing the value of a `static readonly` constant.
+    fn constant_initializer(&self, value: &ConstantValue) -> String {
+        match value {
+            ConstantValue::Array(the_type, values) => {
+                let values = values.iter().map(|x| self.constant_initializer(x)).collect::<Vec<_>>();
+                format!("new {} {{ {} }}", self.constant_type_name(&CType::Array(the_type.clone())), values.join(", "))
+            }
+            ConstantValue::Composite(the_type, values) => {
+                let mut assignments = Vec::new();
+
+                for (field, value) in the_type.fields().iter().zip(values) {
+                    let field_name = self.converter().field_name_to_csharp_name(field, self.config().rename_symbols);
+
+                    match value {
+                        // Struct arrays are unrolled into one field per element.
+                        ConstantValue::Array(_, elements) => {
+                            for (i, element) in elements.iter().enumerate() {
+                                assignments.push(format!("{}{} = {}", field_name, i, self.constant_initializer(element)));
+                            }
+                        }
+                        _ => assignments.push(format!("{} = {}", field_name, self.constant_initializer(value))),
+                    }
+                }
+
+                format!("new {} {{ {} }}", self.converter().composite_to_typename(the_type), assignments.join(", "))
+            }
+            ConstantValue::Primitive(_) => {
+                let rval = self.converter().to_typespecifier_in_rval(&value.the_type());
+                format!("({}) {}", rval, self.converter().constant_value_to_value(value))
+            }
+            ConstantValue::String(_) => self.converter().constant_value_to_value(value),
+        }
     }
 
     fn write_functions(&self, w: &mut IndentWriter) -> Result<(), Error> {
         for function in self.inventory().functions() {
             if self.should_emit_by_meta(function.meta()) {
-                self.write_function(w, function, WriteFor::Code)?;
+                self.write_function(w, function, WriteFor::Code).map_err(|e| e.with_item(format!("fn {}", function.name())))?;
                 w.newline()?;
             }
         }
@@ -191,7 +263,7 @@ pub trait CSharpWriter {
 
     fn write_type_definitions(&self, w: &mut IndentWriter) -> Result<(), Error> {
         for the_type in self.inventory().ctypes() {
-            self.write_type_definition(w, the_type)?;
+            self.write_type_definition(w, the_type).map_err(|e| e.with_item(format!("type {}", the_type.name_within_lib())))?;
         }
 
         Ok(())
@@ -214,6 +286,14 @@ pub trait CSharpWriter {
                 self.write_type_definition_composite(w, c)?;
                 w.newline()?;
             }
+            CType::Union(u) => {
+                self.write_type_definition_union(w, u)?;
+                w.newline()?;
+            }
+            CType::TaggedUnion(t) => {
+                self.write_type_definition_tagged_union(w, t)?;
+                w.newline()?;
+            }
             CType::FnPointer(f) => {
                 self.write_type_definition_fn_pointer(w, f)?;
                 w.newline()?;
@@ -238,17 +318,47 @@ pub trait CSharpWriter {
                     self.write_pattern_slice_mut(w, x)?;
                     w.newline()?;
                 }
+                TypePattern::Vec(x) => {
+                    self.write_type_definition_composite(w, x)?;
+                    w.newline()?;
+                    self.write_pattern_vec(w, x)?;
+                    w.newline()?;
+                }
+                TypePattern::Str(x) => {
+                    self.write_type_definition_composite(w, x)?;
+                    w.newline()?;
+                    self.write_pattern_str(w, x)?;
+                    w.newline()?;
+                }
+                TypePattern::String(x) => {
+                    self.write_type_definition_composite(w, x)?;
+                    w.newline()?;
+                    self.write_pattern_string(w, x)?;
+                    w.newline()?;
+                }
                 TypePattern::Option(x) => {
                     self.write_type_definition_composite(w, x)?;
                     w.newline()?;
                     self.write_pattern_option(w, x)?;
                     w.newline()?;
                 }
+                TypePattern::Result(x) => {
+                    self.write_type_definition_composite(w, x.the_type())?;
+                    w.newline()?;
+                    self.write_pattern_result(w, x)?;
+                    w.newline()?;
+                }
                 TypePattern::NamedCallback(x) => {
                     // Handle this better way
                     self.write_type_definition_named_callback(w, x)?;
                     w.newline()?;
                 }
+                TypePattern::CallbackWithData(x) => {
+                    self.write_type_definition_composite(w, x.the_type())?;
+                    w.newline()?;
+                    self.write_pattern_callback_with_data(w, x)?;
+                    w.newline()?;
+                }
                 TypePattern::Bool => {
                     self.write_type_definition_ffibool(w)?;
                     w.newline()?;
@@ -337,7 +447,12 @@ pub trait CSharpWriter {
         if write_for == WriteFor::Code {
             self.write_documentation(w, the_type.meta().documentation())?;
         }
-        indented!(w, r#"public enum {}"#, the_type.rust_name())?;
+        if the_type.repr() == PrimitiveType::I32 {
+            indented!(w, r#"public enum {}"#, the_type.rust_name())?;
+        } else {
+            let repr = self.converter().primitive_to_typename(&the_type.repr());
+            indented!(w, r#"public enum {} : {}"#, the_type.rust_name(), repr)?;
+        }
         indented!(w, r#"{{"#)?;
         w.indent();
 
@@ -429,6 +544,183 @@ pub trait CSharpWriter {
         }
     }
 
+    fn write_type_definition_union(&self, w: &mut IndentWriter, the_type: &UnionType) -> Result<(), Error> {
+        self.debug(w, "write_type_definition_union")?;
+        self.write_documentation(w, the_type.meta().documentation())?;
+        indented!(w, r#"[Serializable]"#)?;
+        indented!(w, r#"[StructLayout(LayoutKind.Explicit)]"#)?;
+        self.write_type_definition_union_body(w, the_type)
+    }
+
+    fn write_type_definition_union_body(&self, w: &mut IndentWriter, the_type: &UnionType) -> Result<(), Error> {
+        indented!(
+            w,
+            r#"{} partial struct {}"#,
+            self.config().visibility_types.to_access_modifier(),
+            self.converter().union_to_typename(the_type)
+        )?;
+        indented!(w, r#"{{"#)?;
+        w.indent();
+
+        // Field conversion is defined in terms of composites, so we hand it a struct with the same fields.
+        let as_composite = CompositeType::with_meta(the_type.rust_name().to_string(), the_type.fields().to_vec(), the_type.meta().clone());
+
+        for field in the_type.fields() {
+            self.write_documentation(w, field.documentation())?;
+
+            let field_name = self.converter().field_name_to_csharp_name(field, self.config().rename_symbols);
+            let visibility = match field.visibility() {
+                Visibility::Public => "public ",
+                Visibility::Private => "",
+            };
+
+            match field.the_type() {
+                CType::Array(a) => {
+                    if !self.config().unroll_struct_arrays {
+                        panic!("Unable to generate bindings for arrays in fields if `unroll_struct_arrays` is not enabled.");
+                    }
+
+                    let type_name = self.converter().to_typespecifier_in_field(a.array_type(), field, &as_composite);
+                    let element_size = a.array_type().size_of();
+
+                    for i in 0..a.len() {
+                        indented!(w, r#"[FieldOffset({})]"#, i * element_size)?;
+                        indented!(w, r#"{}{} {}{};"#, visibility, type_name, field_name, i)?;
+                    }
+                }
+                _ => {
+                    let type_name = self.converter().to_typespecifier_in_field(field.the_type(), field, &as_composite);
+                    indented!(w, r#"[FieldOffset(0)]"#)?;
+                    indented!(w, r#"{}{} {};"#, visibility, type_name, field_name)?;
+                }
+            }
+        }
+
+        w.unindent();
+        indented!(w, r#"}}"#)
+    }
+
+    fn write_type_definition_tagged_union(&self, w: &mut IndentWriter, the_type: &TaggedUnionType) -> Result<(), Error> {
+        self.debug(w, "write_type_definition_tagged_union")?;
+        self.write_type_definition_enum(w, &the_type.tag_enum(), WriteFor::Code)?;
+        w.newline()?;
+
+        for variant in the_type.payload_variants() {
+            self.write_type_definition_composite(w, &the_type.variant_composite(variant))?;
+            w.newline()?;
+        }
+
+        if the_type.placement() == TagPlacement::Separate && the_type.payload_variants().next().is_some() {
+            self.write_type_definition_tagged_union_payload(w, the_type)?;
+            w.newline()?;
+        }
+
+        self.write_documentation(w, the_type.meta().documentation())?;
+        self.write_type_definition_tagged_union_body(w, the_type)
+    }
+
+    /// Writes the union of all variant payloads for [`TagPlacement::Separate`], named `{Name}Payload`.
+    fn write_type_definition_tagged_union_payload(&self, w: &mut IndentWriter, the_type: &TaggedUnionType) -> Result<(), Error> {
+        let name = format!("{}Payload", self.converter().tagged_union_to_typename(the_type));
+
+        indented!(w, r#"[Serializable]"#)?;
+        indented!(w, r#"[StructLayout(LayoutKind.Explicit)]"#)?;
+        indented!(w, r#"{} partial struct {}"#, self.config().visibility_types.to_access_modifier(), name)?;
+        indented!(w, r#"{{"#)?;
+        w.indent();
+
+        for variant in the_type.payload_variants() {
+            let variant_type = self.converter().composite_to_typename(&the_type.variant_composite(variant));
+            indented!(w, r#"[FieldOffset(0)]"#)?;
+            indented!(w, r#"public {} {};"#, variant_type, variant.name())?;
+        }
+
+        w.unindent();
+        indented!(w, r#"}}"#)
+    }
+
+    fn write_type_definition_tagged_union_body(&self, w: &mut IndentWriter, the_type: &TaggedUnionType) -> Result<(), Error> {
+        let name = self.converter().tagged_union_to_typename(the_type);
+        let tag_enum = the_type.tag_enum();
+        let tag_enum_name = self.converter().enum_to_typename(&tag_enum);
+        let tag_type = self.converter().primitive_to_typename(&the_type.tag());
+
+        indented!(w, r#"[Serializable]"#)?;
+        match the_type.placement() {
+            TagPlacement::Separate => indented!(w, r#"[StructLayout(LayoutKind.Sequential)]"#)?,
+            TagPlacement::Inline => indented!(w, r#"[StructLayout(LayoutKind.Explicit)]"#)?,
+        }
+        indented!(w, r#"{} partial struct {}"#, self.config().visibility_types.to_access_modifier(), name)?;
+        indented!(w, r#"{{"#)?;
+        w.indent();
+
+        // For inline tags every variant struct starts with its own copy of the tag, so all of them overlap.
+        let payload_access = match the_type.placement() {
+            TagPlacement::Separate => {
+                indented!(w, r#"{} tag;"#, tag_type)?;
+                if the_type.payload_variants().next().is_some() {
+                    indented!(w, r#"{}Payload payload;"#, name)?;
+                }
+                "payload."
+            }
+            TagPlacement::Inline => {
+                indented!(w, r#"[FieldOffset(0)]"#)?;
+                indented!(w, r#"{} tag;"#, tag_type)?;
+                for variant in the_type.payload_variants() {
+                    let variant_type = self.converter().composite_to_typename(&the_type.variant_composite(variant));
+                    indented!(w, r#"[FieldOffset(0)]"#)?;
+                    indented!(w, r#"{} {};"#, variant_type, variant.name())?;
+                }
+                ""
+            }
+        };
+
+        w.newline()?;
+        indented!(w, r#"public {} Tag => ({}) tag;"#, tag_enum_name, tag_enum_name)?;
+
+        for variant in the_type.variants() {
+            let variant_name = variant.name();
+
+            w.newline()?;
+            indented!(w, r#"public bool Is{} => Tag == {}.{};"#, variant_name, tag_enum_name, variant_name)?;
+
+            if variant.fields().is_empty() {
+                w.newline()?;
+                indented!(w, r#"public static {} From{}()"#, name, variant_name)?;
+                indented!(w, r#"{{"#)?;
+                indented!(w, [_], r#"var rval = new {}();"#, name)?;
+                indented!(w, [_], r#"rval.tag = ({}) {}.{};"#, tag_type, tag_enum_name, variant_name)?;
+                indented!(w, [_], r#"return rval;"#)?;
+                indented!(w, r#"}}"#)?;
+                continue;
+            }
+
+            let variant_type = self.converter().composite_to_typename(&the_type.variant_composite(variant));
+
+            w.newline()?;
+            indented!(w, r#"public {} As{}()"#, variant_type, variant_name)?;
+            indented!(w, r#"{{"#)?;
+            indented!(w, [_], r#"if (Tag != {}.{})"#, tag_enum_name, variant_name)?;
+            indented!(w, [_], r#"{{"#)?;
+            indented!(w, [_ _], r#"throw new InvalidOperationException($"Variant is {{Tag}}, not {}.");"#, variant_name)?;
+            indented!(w, [_], r#"}}"#)?;
+            indented!(w, [_], r#"return {}{};"#, payload_access, variant_name)?;
+            indented!(w, r#"}}"#)?;
+
+            w.newline()?;
+            indented!(w, r#"public static {} From{}({} value)"#, name, variant_name, variant_type)?;
+            indented!(w, r#"{{"#)?;
+            indented!(w, [_], r#"var rval = new {}();"#, name)?;
+            indented!(w, [_], r#"rval.{}{} = value;"#, payload_access, variant_name)?;
+            indented!(w, [_], r#"rval.tag = ({}) {}.{};"#, tag_type, tag_enum_name, variant_name)?;
+            indented!(w, [_], r#"return rval;"#)?;
+            indented!(w, r#"}}"#)?;
+        }
+
+        w.unindent();
+        indented!(w, r#"}}"#)
+    }
+
     fn namespace_for_id(&self, id: &str) -> String {
         self.config()
             .namespace_mappings
@@ -504,6 +796,8 @@ pub trait CSharpWriter {
             CType::Enum(x) => self.should_emit_by_meta(x.meta()),
             CType::Opaque(x) => self.should_emit_by_meta(x.meta()),
             CType::Composite(x) => self.should_emit_by_meta(x.meta()),
+            CType::Union(x) => self.should_emit_by_meta(x.meta()),
+            CType::TaggedUnion(x) => self.should_emit_by_meta(x.meta()),
             CType::FnPointer(_) => true,
             CType::ReadPointer(_) => false,
             CType::ReadWritePointer(_) => false,
@@ -513,10 +807,15 @@ pub trait CSharpWriter {
                 TypePattern::FFIErrorEnum(x) => self.should_emit_by_meta(x.the_enum().meta()),
                 TypePattern::Slice(x) => self.should_emit_by_meta(x.meta()),
                 TypePattern::SliceMut(x) => self.should_emit_by_meta(x.meta()),
+                TypePattern::Vec(x) => self.should_emit_by_meta(x.meta()),
+                TypePattern::Str(x) => self.should_emit_by_meta(x.meta()),
+                TypePattern::String(x) => self.should_emit_by_meta(x.meta()),
                 TypePattern::Option(x) => self.should_emit_by_meta(x.meta()),
+                TypePattern::Result(x) => self.should_emit_by_meta(x.the_type().meta()),
                 TypePattern::Bool => self.config().write_types == WriteTypes::NamespaceAndInteroptopusGlobal,
                 TypePattern::CChar => false,
                 TypePattern::NamedCallback(_) => true,
+                TypePattern::CallbackWithData(x) => self.should_emit_by_meta(x.the_type().meta()),
             },
         }
     }
@@ -526,9 +825,13 @@ pub trait CSharpWriter {
             match pattern {
                 LibraryPattern::Service(cls) => {
                     if self.should_emit_by_meta(cls.the_type().meta()) {
-                        self.write_pattern_service(w, cls)?
+                        self.write_pattern_service(w, cls).map_err(|e| e.with_item(format!("service {}", cls.the_type().rust_name())))?
                     }
                 }
+                // Written along with their type.
+                LibraryPattern::Vec(_) => {}
+                LibraryPattern::String(_) => {}
+                LibraryPattern::LastError(_) => {}
             }
         }
 
@@ -577,6 +880,96 @@ pub trait CSharpWriter {
         Ok(())
     }
 
+    fn write_pattern_result(&self, w: &mut IndentWriter, result: &FFIResultType) -> Result<(), Error> {
+        self.debug(w, "write_pattern_result")?;
+
+        let context_type_name = result.the_type().rust_name();
+        let type_string = self.converter().to_typespecifier_in_rval(result.value_type());
+        let error = result.error();
+
+        indented!(w, r#"{} partial struct {}"#, self.config().visibility_types.to_access_modifier(), context_type_name)?;
+        indented!(w, r#"{{"#)?;
+
+        // Ok
+        indented!(w, [_], r#"public {} Ok()"#, type_string)?;
+        indented!(w, [_], r#"{{"#)?;
+        indented!(w, [_ _], r#"if (err != {}.{})"#, error.the_enum().rust_name(), error.success_variant().name())?;
+        indented!(w, [_ _], r#"{{"#)?;
+        indented!(w, [_ _ _], r#"throw {};"#, self.helper().new_exception(error, "err"))?;
+        indented!(w, [_ _], r#"}}"#)?;
+        w.newline()?;
+        indented!(w, [_ _], r#"return t;"#)?;
+        indented!(w, [_], r#"}}"#)?;
+
+        indented!(w, r#"}}"#)?;
+        w.newline()?;
+        Ok(())
+    }
+
+    fn write_pattern_callback_with_data(&self, w: &mut IndentWriter, callback: &CallbackWithData) -> Result<(), Error> {
+        self.debug(w, "write_pattern_callback_with_data")?;
+
+        let visibility = self.config().visibility_types.to_access_modifier();
+        let context_type_name = callback.the_type().rust_name();
+        let delegate_name = format!("{}Delegate", context_type_name);
+        let rval = self.converter().to_typespecifier_in_rval(callback.fnpointer().signature().rval());
+        let raw_name = self.converter().fnpointer_to_typename(callback.raw_fnpointer());
+        let destructor_name = self.converter().fnpointer_to_typename(callback.destructor());
+
+        let params = callback.fnpointer().signature().params();
+        let typed_params = params
+            .iter()
+            .map(|x| format!("{} {}", self.converter().to_typespecifier_in_param(x.the_type()), x.name()))
+            .collect::<Vec<_>>();
+        let args = params.iter().map(|x| x.name().to_string()).collect::<Vec<_>>().join(", ");
+        let mut raw_params = typed_params.clone();
+        raw_params.push("IntPtr data".to_string());
+
+        indented!(w, r#"{} delegate {} {}({});"#, visibility, rval, delegate_name, typed_params.join(", "))?;
+        w.newline()?;
+
+        indented!(w, r#"{} partial struct {}"#, visibility, context_type_name)?;
+        indented!(w, r#"{{"#)?;
+
+        // Static so the delegates outlive any callback handed to Rust.
+        indented!(w, [_], r#"static readonly {} _call = Call;"#, raw_name)?;
+        indented!(w, [_], r#"static readonly {} _release = Release;"#, destructor_name)?;
+        w.newline()?;
+
+        // Constructor boxing the delegate
+        indented!(w, [_], r#"/// Boxes `f` into a GCHandle, which is freed once Rust drops this callback."#)?;
+        indented!(w, [_], r#"///"#)?;
+        indented!(w, [_], r#"/// Pass each instance to Rust only once, overloads taking a {} box it for every call."#, delegate_name)?;
+        indented!(w, [_], r#"public {}({} f)"#, context_type_name, delegate_name)?;
+        indented!(w, [_], r#"{{"#)?;
+        indented!(w, [_ _], r#"callback = _call;"#)?;
+        indented!(w, [_ _], r#"data = GCHandle.ToIntPtr(GCHandle.Alloc(f));"#)?;
+        indented!(w, [_ _], r#"destructor = _release;"#)?;
+        indented!(w, [_], r#"}}"#)?;
+        w.newline()?;
+
+        // Trampoline unboxing the delegate
+        indented!(w, [_], r#"static {} Call({})"#, rval, raw_params.join(", "))?;
+        indented!(w, [_], r#"{{"#)?;
+        let invoke = format!("(({}) GCHandle.FromIntPtr(data).Target)({})", delegate_name, args);
+        if callback.fnpointer().signature().rval().is_void() {
+            indented!(w, [_ _], r#"{};"#, invoke)?;
+        } else {
+            indented!(w, [_ _], r#"return {};"#, invoke)?;
+        }
+        indented!(w, [_], r#"}}"#)?;
+        w.newline()?;
+
+        indented!(w, [_], r#"static void Release(IntPtr data)"#)?;
+        indented!(w, [_], r#"{{"#)?;
+        indented!(w, [_ _], r#"GCHandle.FromIntPtr(data).Free();"#)?;
+        indented!(w, [_], r#"}}"#)?;
+
+        indented!(w, r#"}}"#)?;
+        w.newline()?;
+        Ok(())
+    }
+
     fn write_pattern_slice(&self, w: &mut IndentWriter, slice: &CompositeType) -> Result<(), Error> {
         self.debug(w, "write_pattern_slice")?;
 
@@ -700,6 +1093,132 @@ pub trait CSharpWriter {
         Ok(())
     }
 
+    fn write_pattern_vec(&self, w: &mut IndentWriter, vec: &CompositeType) -> Result<(), Error> {
+        self.debug(w, "write_pattern_vec")?;
+
+        let context_type_name = vec.rust_name();
+        let data_type = vec_element_type(vec);
+        let type_string = self.converter().to_typespecifier_in_rval(data_type);
+        let is_blittable = self.converter().is_blittable(data_type);
+
+        // Copies of this struct share its elements, so overloads free them after copying instead of a `Dispose`.
+        indented!(w, r#"{} partial struct {} : IEnumerable<{}>"#, self.config().visibility_types.to_access_modifier(), context_type_name, type_string)?;
+        indented!(w, r#"{{"#)?;
+
+        // Getter
+        indented!(w, [_], r#"public {} this[int i]"#, type_string)?;
+        indented!(w, [_], r#"{{"#)?;
+        indented!(w, [_ _], r#"get"#)?;
+        indented!(w, [_ _], r#"{{"#)?;
+        indented!(w, [_ _ _], r#"if (i >= Count) throw new IndexOutOfRangeException();"#)?;
+
+        if self.config().use_unsafe.any_unsafe() && is_blittable {
+            indented!(w, [_ _ _], r#"unsafe"#)?;
+            indented!(w, [_ _ _], r#"{{"#)?;
+            indented!(w, [_ _ _ _], r#"var d = ({}*) data.ToPointer();"#, type_string)?;
+            indented!(w, [_ _ _ _], r#"return d[i];"#)?;
+            indented!(w, [_ _ _], r#"}}"#)?;
+        } else {
+            indented!(w, [_ _ _], r#"var size = Marshal.SizeOf(typeof({}));"#, type_string)?;
+            indented!(w, [_ _ _], r#"var ptr = new IntPtr(data.ToInt64() + i * size);"#)?;
+            indented!(w, [_ _ _], r#"return Marshal.PtrToStructure<{}>(ptr);"#, type_string)?;
+        }
+
+        indented!(w, [_ _], r#"}}"#)?;
+        indented!(w, [_], r#"}}"#)?;
+
+        // ToArray
+        indented!(w, [_], r#"public {}[] ToArray()"#, type_string)?;
+        indented!(w, [_], r#"{{"#)?;
+        indented!(w, [_ _], r#"var rval = new {}[len];"#, type_string)?;
+        indented!(w, [_ _], r#"for (var i = 0; i < (int) len; i++) {{"#)?;
+        indented!(w, [_ _ _], r#"rval[i] = this[i];"#)?;
+        indented!(w, [_ _], r#"}}"#)?;
+        indented!(w, [_ _], r#"return rval;"#)?;
+        indented!(w, [_], r#"}}"#)?;
+
+        // Count
+        indented!(w, [_], r#"public int Count => (int) len;"#)?;
+
+        // GetEnumerator
+        indented!(w, [_], r#"public IEnumerator<{}> GetEnumerator()"#, type_string)?;
+        indented!(w, [_], r#"{{"#)?;
+        indented!(w, [_ _], r#"for (var i = 0; i < (int)len; ++i)"#)?;
+        indented!(w, [_ _], r#"{{"#)?;
+        indented!(w, [_ _ _], r#"yield return this[i];"#)?;
+        indented!(w, [_ _], r#"}}"#)?;
+        indented!(w, [_], r#"}}"#)?;
+
+        // The other GetEnumerator
+        indented!(w, [_], r#"IEnumerator IEnumerable.GetEnumerator()"#)?;
+        indented!(w, [_], r#"{{"#)?;
+        indented!(w, [_ _], r#"return this.GetEnumerator();"#)?;
+        indented!(w, [_], r#"}}"#)?;
+
+        indented!(w, r#"}}"#)?;
+        w.newline()?;
+
+        Ok(())
+    }
+
+    fn write_pattern_str(&self, w: &mut IndentWriter, the_type: &CompositeType) -> Result<(), Error> {
+        self.debug(w, "write_pattern_str")?;
+
+        let context_type_name = the_type.rust_name();
+
+        indented!(w, r#"{} partial struct {}"#, self.config().visibility_types.to_access_modifier(), context_type_name)?;
+        indented!(w, r#"{{"#)?;
+
+        // Ctor
+        indented!(w, [_], r#"public {}(GCHandle handle, ulong count)"#, context_type_name)?;
+        indented!(w, [_], r#"{{"#)?;
+        indented!(w, [_ _], r#"this.data = handle.AddrOfPinnedObject();"#)?;
+        indented!(w, [_ _], r#"this.len = count;"#)?;
+        indented!(w, [_], r#"}}"#)?;
+
+        // Ctor
+        indented!(w, [_], r#"public {}(IntPtr handle, ulong count)"#, context_type_name)?;
+        indented!(w, [_], r#"{{"#)?;
+        indented!(w, [_ _], r#"this.data = handle;"#)?;
+        indented!(w, [_ _], r#"this.len = count;"#)?;
+        indented!(w, [_], r#"}}"#)?;
+
+        self.write_pattern_string_to_string(w)?;
+
+        indented!(w, r#"}}"#)?;
+        w.newline()?;
+
+        Ok(())
+    }
+
+    fn write_pattern_string(&self, w: &mut IndentWriter, the_type: &CompositeType) -> Result<(), Error> {
+        self.debug(w, "write_pattern_string")?;
+
+        let context_type_name = the_type.rust_name();
+
+        // Like vecs, these are freed by overloads once converted to a `string`.
+        indented!(w, r#"{} partial struct {}"#, self.config().visibility_types.to_access_modifier(), context_type_name)?;
+        indented!(w, r#"{{"#)?;
+
+        self.write_pattern_string_to_string(w)?;
+
+        indented!(w, r#"}}"#)?;
+        w.newline()?;
+
+        Ok(())
+    }
+
+    /// Decodes the UTF-8 `data` and `len` of a string struct, which may contain NUL.
+    fn write_pattern_string_to_string(&self, w: &mut IndentWriter) -> Result<(), Error> {
+        indented!(w, [_], r#"public override string ToString()"#)?;
+        indented!(w, [_], r#"{{"#)?;
+        indented!(w, [_ _], r#"if (len == 0) return "";"#)?;
+        indented!(w, [_ _], r#"var bytes = new byte[len];"#)?;
+        indented!(w, [_ _], r#"Marshal.Copy(data, bytes, 0, (int) len);"#)?;
+        indented!(w, [_ _], r#"return System.Text.Encoding.UTF8.GetString(bytes);"#)?;
+        indented!(w, [_], r#"}}"#)
+    }
+
     fn write_pattern_slice_mut(&self, w: &mut IndentWriter, slice: &CompositeType) -> Result<(), Error> {
         self.debug(w, "write_pattern_slice_mut")?;
         let context_type_name = slice.rust_name();
@@ -886,11 +1405,7 @@ pub trait CSharpWriter {
 
             // Write checked method. These are "normal" methods that accept
             // common C# types.
-            let rval = match function.signature().rval() {
-                CType::Pattern(TypePattern::FFIErrorEnum(_)) => "void".to_string(),
-                CType::Pattern(TypePattern::AsciiPointer) => "string".to_string(),
-                _ => self.converter().to_typespecifier_in_rval(function.signature().rval()),
-            };
+            let rval = self.helper().converted_rval(function.signature().rval());
             self.write_documentation(w, function.meta().documentation())?;
             self.write_pattern_service_method(w, class, function, &rval, &fn_name, false, false, WriteFor::Code)?;
 
@@ -988,13 +1503,22 @@ pub trait CSharpWriter {
                 indented!(w, [_], r#"var rval = {};"#, fn_call)?;
                 indented!(w, [_], r#"if (rval != {}.{})"#, e.the_enum().rust_name(), e.success_variant().name())?;
                 indented!(w, [_], r#"{{"#)?;
-                indented!(w, [_ _], r#"throw new InteropException<{}>(rval);"#, e.the_enum().rust_name())?;
+                indented!(w, [_ _], r#"throw {};"#, self.helper().new_exception(e, "rval"))?;
                 indented!(w, [_], r#"}}"#)?;
             }
             CType::Pattern(TypePattern::AsciiPointer) => {
                 indented!(w, [_], r#"var s = {};"#, fn_call)?;
                 indented!(w, [_], r#"return Marshal.PtrToStringAnsi(s);"#)?;
             }
+            CType::Pattern(TypePattern::Str(_)) => {
+                indented!(w, [_], r#"var rval = {};"#, fn_call)?;
+                indented!(w, [_], r#"return rval.ToString();"#)?;
+            }
+            CType::Pattern(TypePattern::Result(_)) => {
+                indented!(w, [_], r#"var rval = {};"#, fn_call)?;
+                indented!(w, [_], r#"return rval.Ok();"#)?;
+            }
+            rval if self.helper().owned_rval(rval).is_some() => write_owned_rval_copy(w, &self.helper(), rval, &fn_call)?,
             CType::Primitive(PrimitiveType::Void) => {
                 indented!(w, [_], r#"{};"#, fn_call)?;
             }
@@ -1022,6 +1546,15 @@ pub trait CSharpWriter {
             indented!(w, [_], r#"{{"#)?;
             indented!(w, [_ _], r#"Error = error;"#)?;
             indented!(w, [_], r#"}}"#)?;
+            w.newline()?;
+            indented!(
+                w,
+                [_],
+                r#"public InteropException(T error, string message): base(string.IsNullOrEmpty(message) ? $"Something went wrong: {{error}}" : $"Something went wrong: {{error}} ({{message}})")"#
+            )?;
+            indented!(w, [_], r#"{{"#)?;
+            indented!(w, [_ _], r#"Error = error;"#)?;
+            indented!(w, [_], r#"}}"#)?;
             indented!(w, r#"}}"#)?;
             w.newline()?;
         }
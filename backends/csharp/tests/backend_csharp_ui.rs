@@ -1,5 +1,6 @@
-use interoptopus::testing::assert_file_matches_generated;
+use interoptopus::testing::snapshot::assert_generated_matches_snapshot;
 use interoptopus::util::NamespaceMappings;
+use interoptopus::writer::IndentWriter;
 use interoptopus::{Error, Interop};
 use interoptopus_backend_csharp::overloads::{DotNet, Unity};
 use interoptopus_backend_csharp::{run_dotnet_command_if_installed, CSharpVisibility, Config, DocConfig, DocGenerator, Generator, Unsafe, WriteTypes};
@@ -26,8 +27,8 @@ pub fn write_simple_project_file(path: impl AsRef<Path>) -> Result<(), Error> {
     Ok(())
 }
 
-/// Generates runnable bindings for the reference project.
-fn generate_bindings_multi(folder: impl AsRef<Path>, use_unsafe: Unsafe, config: Option<Config>) -> Result<(), Error> {
+/// Returns the generator of each namespace of the reference project, together with its `Interop.*.cs` file name.
+fn generators(use_unsafe: Unsafe, config: Option<Config>) -> Vec<(String, Generator)> {
     let library = interoptopus_reference_project::ffi_inventory();
 
     let config = config.unwrap_or(Config {
@@ -38,8 +39,10 @@ fn generate_bindings_multi(folder: impl AsRef<Path>, use_unsafe: Unsafe, config:
         ..Config::default()
     });
 
+    let mut rval = Vec::new();
+
     for namespace_id in library.namespaces() {
-        let file_name = format!("{}/Interop.{}.cs", folder.as_ref().to_str().ok_or(Error::FileNotFound)?, namespace_id).replace("..", ".");
+        let file_name = format!("Interop.{}.cs", namespace_id).replace("..", ".");
 
         let write_types = if namespace_id.is_empty() {
             WriteTypes::Namespace
@@ -61,12 +64,28 @@ fn generate_bindings_multi(folder: impl AsRef<Path>, use_unsafe: Unsafe, config:
             generator.add_overload_writer(Unity::new());
         }
 
-        generator.write_file(file_name)?;
+        rval.push((file_name, generator));
+    }
+
+    rval
+}
+
+/// Generates runnable bindings for the reference project.
+fn generate_bindings_multi(folder: impl AsRef<Path>, use_unsafe: Unsafe, config: Option<Config>) -> Result<(), Error> {
+    for (file_name, generator) in generators(use_unsafe, config) {
+        generator.write_file(folder.as_ref().join(file_name))?;
     }
 
     Ok(())
 }
 
+/// Asserts the bindings of each namespace match their `Interop.*.cs.expected` in `folder`.
+fn assert_bindings_match_snapshots(folder: impl AsRef<Path>, use_unsafe: Unsafe) {
+    for (file_name, generator) in generators(use_unsafe, None) {
+        assert_generated_matches_snapshot(&generator, folder.as_ref().join(format!("{}.expected", file_name)));
+    }
+}
+
 fn generate_documentation(output: &str) -> Result<(), Error> {
     let inventory = interoptopus_reference_project::ffi_inventory();
     let mut generator = Generator::new(
@@ -85,18 +104,12 @@ fn generate_documentation(output: &str) -> Result<(), Error> {
 #[test]
 #[cfg_attr(miri, ignore)]
 fn bindings_match_reference() -> Result<(), Error> {
-    generate_bindings_multi("tests/output_safe", Unsafe::None, None)?;
-    generate_bindings_multi("tests/output_unsafe", Unsafe::UnsafePlatformMemCpy, None)?;
-    generate_bindings_multi("tests/output_unity/Assets", Unsafe::UnsafePlatformMemCpy, None)?;
-
-    assert_file_matches_generated("tests/output_safe/Interop.cs");
-    assert_file_matches_generated("tests/output_safe/Interop.common.cs");
+    assert_bindings_match_snapshots("tests/output_safe", Unsafe::None);
+    assert_bindings_match_snapshots("tests/output_unsafe", Unsafe::UnsafePlatformMemCpy);
+    assert_bindings_match_snapshots("tests/output_unity/Assets", Unsafe::UnsafePlatformMemCpy);
 
-    assert_file_matches_generated("tests/output_unsafe/Interop.cs");
-    assert_file_matches_generated("tests/output_unsafe/Interop.common.cs");
-
-    assert_file_matches_generated("tests/output_unity/Assets/Interop.cs");
-    assert_file_matches_generated("tests/output_unity/Assets/Interop.common.cs");
+    // Nothing can run the Unity project here, but it should still be up to date.
+    generate_bindings_multi("tests/output_unity/Assets", Unsafe::UnsafePlatformMemCpy, None)?;
 
     generate_documentation("tests/output/reference_project.md")?;
 
@@ -214,3 +227,55 @@ fn config_visibility_force_visibility_public() -> Result<(), Error> {
 
     Ok(())
 }
+
+#[test]
+fn reference_project_is_valid() {
+    let config = Config {
+        namespace_mappings: NamespaceMappings::new("My.Company").add("common", "My.Company.Common"),
+        use_unsafe: Unsafe::UnsafePlatformMemCpy,
+        ..Config::default()
+    };
+    let generator = Generator::new(config, interoptopus_reference_project::ffi_inventory());
+    let validation = generator.validate();
+
+    assert!(!validation.has_errors(), "{}", validation);
+}
+
+#[test]
+fn unsupported_primitives_are_errors() {
+    let config = Config {
+        namespace_mappings: NamespaceMappings::new("My.Company").add("common", "My.Company.Common"),
+        ..Config::default()
+    };
+    let generator = Generator::new(config, interoptopus_reference_project::ffi_inventory_with_128bit());
+    let error = generator.write_to(&mut IndentWriter::new(&mut Vec::new())).unwrap_err();
+
+    let cause = error.root_cause();
+
+    assert!(matches!(cause, Error::Invalid(_)));
+    assert!(cause.to_string().contains("error: fn primitive_u128: parameter `x` uses `u128`"), "{}", cause);
+}
+
+#[test]
+fn unsafe_slices_of_managed_options_are_invalid() {
+    use interoptopus::patterns::option::FFIOption;
+    use interoptopus::patterns::slice::FFISlice;
+    use interoptopus::patterns::string::AsciiPointer;
+    use interoptopus::{extra_type, InventoryBuilder};
+
+    let inventory = InventoryBuilder::new().register(extra_type!(FFISlice<FFIOption<AsciiPointer>>)).inventory();
+
+    let config = Config {
+        use_unsafe: Unsafe::None,
+        ..Config::default()
+    };
+    let validation = Generator::new(config, inventory.clone()).validate();
+    assert!(!validation.diagnostics().iter().any(|x| x.message().contains("use_unsafe")));
+
+    let config = Config {
+        use_unsafe: Unsafe::UnsafeKeyword,
+        ..Config::default()
+    };
+    let validation = Generator::new(config, inventory).validate();
+    assert!(validation.diagnostics().iter().any(|x| x.message().contains("use_unsafe")));
+}
@@ -11,6 +11,21 @@ using My.Company.Common;
 
 namespace My.Company.Common
 {
+    public static partial class Interop
+    {
+        public const string NativeLib = "interoptopus_reference_project";
+
+        static Interop()
+        {
+        }
+
+        public const uint COMMON_U32 = (uint) 42;
+
+
+        [DllImport(NativeLib, CallingConvention = CallingConvention.Cdecl, EntryPoint = "namespaced_function_renamed")]
+        public static extern Vec namespaced_function_renamed(Vec x);
+
+    }
 
     [Serializable]
     [StructLayout(LayoutKind.Sequential)]
@@ -20,6 +35,12 @@ namespace My.Company.Common
         public double z;
     }
 
+    [UnmanagedFunctionPointer(CallingConvention.Cdecl)]
+    public delegate void InteropDelegate_fn_pconst(IntPtr x0);
+
+    [UnmanagedFunctionPointer(CallingConvention.Cdecl)]
+    public delegate uint InteropDelegate_fn_u32_pconst__rval_u32(uint x0, IntPtr x1);
+
     [UnmanagedFunctionPointer(CallingConvention.Cdecl)]
     public delegate byte InteropDelegate_fn_u8_rval_u8(byte x0);
 
@@ -536,6 +557,11 @@ namespace My.Company.Common
         {
             Error = error;
         }
+
+        public InteropException(T error, string message): base(string.IsNullOrEmpty(message) ? $"Something went wrong: {error}" : $"Something went wrong: {error} ({message})")
+        {
+            Error = error;
+        }
     }
 
 }
@@ -18,9 +18,12 @@ namespace My.Company
         static Interop()
         {
             var api_version = Interop.pattern_api_guard();
-            if (api_version != 4238638219687175308ul)
+            var api_major = api_version >> 48;
+            var api_minor = (api_version >> 32) & 0xFFFF;
+            if (api_major != 1 || (api_minor == 0 && api_version != 0x000100006F474943ul))
             {
-                throw new TypeLoadException($"API reports hash {api_version} which differs from hash in bindings (4238638219687175308). You probably forgot to update / copy either the bindings or the library.");
+                var reason = api_major < 1 ? "the library is older" : api_major == 1 && api_minor == 0 ? "both report the same version but differ" : "the bindings are older";
+                throw new TypeLoadException($"Library has API version {api_major}.{api_minor} (hash {api_version & 0xFFFFFFFF}), bindings need 1.0 (hash 1866942787); {reason}. You probably forgot to update / copy either the bindings or the library.");
             }
         }
 
@@ -30,6 +33,19 @@ namespace My.Company
 
         public const int COMPUTED_I32 = (int) -2147483647;
 
+        public const nuint USIZE = (nuint) 1048576;
+
+        /// Exported as its Unicode scalar value.
+        public const uint CHAR = (uint) 955;
+
+        /// Exported as a string literal.
+        public const string VERSION = "1.2.3";
+
+        public static readonly byte[] ARRAY_U8 = new byte[] { (byte) 1, (byte) 2, (byte) 3, (byte) 4 };
+
+        /// Struct constants must be written as struct literals.
+        public static readonly Vec3f32 VEC3_ONE = new Vec3f32 { x = (float) 1, y = (float) 1, z = (float) 1 };
+
 
         [DllImport(NativeLib, CallingConvention = CallingConvention.Cdecl, EntryPoint = "primitive_void")]
         public static extern void primitive_void();
@@ -64,6 +80,16 @@ namespace My.Company
         [DllImport(NativeLib, CallingConvention = CallingConvention.Cdecl, EntryPoint = "primitive_i64")]
         public static extern long primitive_i64(long x);
 
+        [DllImport(NativeLib, CallingConvention = CallingConvention.Cdecl, EntryPoint = "primitive_usize")]
+        public static extern nuint primitive_usize(nuint x);
+
+        [DllImport(NativeLib, CallingConvention = CallingConvention.Cdecl, EntryPoint = "primitive_isize")]
+        public static extern nint primitive_isize(nint x);
+
+        /// Callers must pass a valid Unicode scalar value, which is why `rustc` considers `char` not FFI-safe.
+        [DllImport(NativeLib, CallingConvention = CallingConvention.Cdecl, EntryPoint = "primitive_char")]
+        public static extern uint primitive_char(uint x);
+
         [DllImport(NativeLib, CallingConvention = CallingConvention.Cdecl, EntryPoint = "many_args_5")]
         public static extern long many_args_5(long x0, long x1, long x2, long x3, long x4);
 
@@ -105,7 +131,7 @@ namespace My.Company
             var rval = complex_args_1(a, ref b);;
             if (rval != FFIError.Ok)
             {
-                throw new InteropException<FFIError>(rval);
+                throw new InteropException<FFIError>(rval, Interop.pattern_last_error_message().ToString());
             }
         }
 
@@ -197,7 +223,7 @@ namespace My.Company
             var rval = panics();;
             if (rval != FFIError.Ok)
             {
-                throw new InteropException<FFIError>(rval);
+                throw new InteropException<FFIError>(rval, Interop.pattern_last_error_message().ToString());
             }
         }
 
@@ -216,6 +242,18 @@ namespace My.Company
         [DllImport(NativeLib, CallingConvention = CallingConvention.Cdecl, EntryPoint = "repr_transparent")]
         public static extern Tupled repr_transparent(Tupled x, ref Tupled r);
 
+        [DllImport(NativeLib, CallingConvention = CallingConvention.Cdecl, EntryPoint = "tagged_union_1")]
+        public static extern TaggedShape tagged_union_1(TaggedShape x);
+
+        [DllImport(NativeLib, CallingConvention = CallingConvention.Cdecl, EntryPoint = "tagged_union_2")]
+        public static extern uint tagged_union_2(ref TaggedInline x);
+
+        [DllImport(NativeLib, CallingConvention = CallingConvention.Cdecl, EntryPoint = "union_1")]
+        public static extern uint union_1(UnionBits x);
+
+        [DllImport(NativeLib, CallingConvention = CallingConvention.Cdecl, EntryPoint = "enum_repr_1")]
+        public static extern EnumReprI8 enum_repr_1(EnumReprI8 x);
+
         [DllImport(NativeLib, CallingConvention = CallingConvention.Cdecl, EntryPoint = "pattern_ascii_pointer_1")]
         public static extern uint pattern_ascii_pointer_1(string x);
 
@@ -359,12 +397,116 @@ namespace My.Company
         [DllImport(NativeLib, CallingConvention = CallingConvention.Cdecl, EntryPoint = "pattern_ffi_slice_delegate_huge")]
         public static extern Vec3f32 pattern_ffi_slice_delegate_huge(CallbackHugeVecSlice callback);
 
+        [DllImport(NativeLib, CallingConvention = CallingConvention.Cdecl, EntryPoint = "pattern_ffi_str_1")]
+        public static extern uint pattern_ffi_str_1(Utf8Str x);
+
+        public static uint pattern_ffi_str_1(string x)
+        {
+            var x_utf8 = System.Text.Encoding.UTF8.GetBytes(x);
+            var x_utf8_pinned = GCHandle.Alloc(x_utf8, GCHandleType.Pinned);
+            var x_utf8_slice = new Utf8Str(x_utf8_pinned, (ulong) x_utf8.Length);
+            try
+            {
+                return pattern_ffi_str_1(x_utf8_slice);;
+            }
+            finally
+            {
+                x_utf8_pinned.Free();
+            }
+        }
+
+        [DllImport(NativeLib, CallingConvention = CallingConvention.Cdecl, EntryPoint = "pattern_ffi_string_1")]
+        public static extern Utf8String pattern_ffi_string_1(Utf8Str x);
+
+        public static string pattern_ffi_string_1(string x)
+        {
+            var x_utf8 = System.Text.Encoding.UTF8.GetBytes(x);
+            var x_utf8_pinned = GCHandle.Alloc(x_utf8, GCHandleType.Pinned);
+            var x_utf8_slice = new Utf8Str(x_utf8_pinned, (ulong) x_utf8.Length);
+            try
+            {
+                var rval = pattern_ffi_string_1(x_utf8_slice);;
+                try
+                {
+                    return rval.ToString();
+                }
+                finally
+                {
+                    Interop.pattern_ffi_string_destroy(rval);
+                }
+            }
+            finally
+            {
+                x_utf8_pinned.Free();
+            }
+        }
+
+        [DllImport(NativeLib, CallingConvention = CallingConvention.Cdecl, EntryPoint = "pattern_ffi_string_2")]
+        public static extern Utf8String pattern_ffi_string_2();
+
+        public static string pattern_ffi_string_2_checked()
+        {
+            var rval = pattern_ffi_string_2();;
+            try
+            {
+                return rval.ToString();
+            }
+            finally
+            {
+                Interop.pattern_ffi_string_destroy(rval);
+            }
+        }
+
+        [DllImport(NativeLib, CallingConvention = CallingConvention.Cdecl, EntryPoint = "pattern_ffi_vec_1")]
+        public static extern Vecu32 pattern_ffi_vec_1(uint len);
+
+        public static uint[] pattern_ffi_vec_1_checked(uint len)
+        {
+            var rval = pattern_ffi_vec_1(len);;
+            try
+            {
+                return rval.ToArray();
+            }
+            finally
+            {
+                Interop.pattern_ffi_vec_u32_destroy(rval);
+            }
+        }
+
+        [DllImport(NativeLib, CallingConvention = CallingConvention.Cdecl, EntryPoint = "pattern_ffi_vec_2")]
+        public static extern VecVec3f32 pattern_ffi_vec_2(float x, uint len);
+
+        public static Vec3f32[] pattern_ffi_vec_2_checked(float x, uint len)
+        {
+            var rval = pattern_ffi_vec_2(x, len);;
+            try
+            {
+                return rval.ToArray();
+            }
+            finally
+            {
+                Interop.pattern_ffi_vec_vec3_destroy(rval);
+            }
+        }
+
+        [DllImport(NativeLib, CallingConvention = CallingConvention.Cdecl, EntryPoint = "pattern_ffi_vec_3")]
+        public static extern uint pattern_ffi_vec_3(Vecu32 vec);
+
         [DllImport(NativeLib, CallingConvention = CallingConvention.Cdecl, EntryPoint = "pattern_ffi_option_1")]
         public static extern OptionInner pattern_ffi_option_1(OptionInner ffi_slice);
 
         [DllImport(NativeLib, CallingConvention = CallingConvention.Cdecl, EntryPoint = "pattern_ffi_option_2")]
         public static extern Inner pattern_ffi_option_2(OptionInner ffi_slice);
 
+        [DllImport(NativeLib, CallingConvention = CallingConvention.Cdecl, EntryPoint = "pattern_ffi_result_1")]
+        public static extern Resultu32FFIError pattern_ffi_result_1(uint x);
+
+        public static uint pattern_ffi_result_1_checked(uint x)
+        {
+            var rval = pattern_ffi_result_1(x);;
+            return rval.Ok();
+        }
+
         [DllImport(NativeLib, CallingConvention = CallingConvention.Cdecl, EntryPoint = "pattern_ffi_bool")]
         public static extern Bool pattern_ffi_bool(Bool ffi_bool);
 
@@ -386,6 +528,14 @@ namespace My.Company
         [DllImport(NativeLib, CallingConvention = CallingConvention.Cdecl, EntryPoint = "pattern_callback_2")]
         public static extern MyCallbackVoid pattern_callback_2(MyCallbackVoid callback);
 
+        [DllImport(NativeLib, CallingConvention = CallingConvention.Cdecl, EntryPoint = "pattern_callback_with_data_1")]
+        public static extern uint pattern_callback_with_data_1(MyCallbackData callback, uint x);
+
+        public static uint pattern_callback_with_data_1(MyCallbackDataDelegate callback, uint x)
+        {
+            return pattern_callback_with_data_1(new MyCallbackData(callback), x);;
+        }
+
         /// Destroys the given instance.
         ///
         /// # Safety
@@ -406,7 +556,7 @@ namespace My.Company
             var rval = simple_service_destroy(ref context);;
             if (rval != FFIError.Ok)
             {
-                throw new InteropException<FFIError>(rval);
+                throw new InteropException<FFIError>(rval, Interop.pattern_last_error_message().ToString());
             }
         }
 
@@ -420,7 +570,7 @@ namespace My.Company
             var rval = simple_service_new_with(ref context, some_value);;
             if (rval != FFIError.Ok)
             {
-                throw new InteropException<FFIError>(rval);
+                throw new InteropException<FFIError>(rval, Interop.pattern_last_error_message().ToString());
             }
         }
 
@@ -432,7 +582,7 @@ namespace My.Company
             var rval = simple_service_new_without(ref context);;
             if (rval != FFIError.Ok)
             {
-                throw new InteropException<FFIError>(rval);
+                throw new InteropException<FFIError>(rval, Interop.pattern_last_error_message().ToString());
             }
         }
 
@@ -444,7 +594,7 @@ namespace My.Company
             var rval = simple_service_new_with_string(ref context, ascii);;
             if (rval != FFIError.Ok)
             {
-                throw new InteropException<FFIError>(rval);
+                throw new InteropException<FFIError>(rval, Interop.pattern_last_error_message().ToString());
             }
         }
 
@@ -456,7 +606,7 @@ namespace My.Company
             var rval = simple_service_new_failing(ref context, some_value);;
             if (rval != FFIError.Ok)
             {
-                throw new InteropException<FFIError>(rval);
+                throw new InteropException<FFIError>(rval, Interop.pattern_last_error_message().ToString());
             }
         }
 
@@ -472,10 +622,21 @@ namespace My.Company
             var rval = simple_service_method_result(context, anon1);;
             if (rval != FFIError.Ok)
             {
-                throw new InteropException<FFIError>(rval);
+                throw new InteropException<FFIError>(rval, Interop.pattern_last_error_message().ToString());
             }
         }
 
+        /// Methods returning a Result<T, _> become an `FFIResult<T, FFIError>`.
+        [DllImport(NativeLib, CallingConvention = CallingConvention.Cdecl, EntryPoint = "simple_service_method_result_value")]
+        public static extern Resultu32FFIError simple_service_method_result_value(IntPtr context, uint x);
+
+        /// Methods returning a Result<T, _> become an `FFIResult<T, FFIError>`.
+        public static uint simple_service_method_result_value_checked(IntPtr context, uint x)
+        {
+            var rval = simple_service_method_result_value(context, x);;
+            return rval.Ok();
+        }
+
         [DllImport(NativeLib, CallingConvention = CallingConvention.Cdecl, EntryPoint = "simple_service_method_value")]
         public static extern uint simple_service_method_value(IntPtr context, uint x);
 
@@ -573,7 +734,7 @@ namespace My.Company
                 var rval = simple_service_method_mut_self_ffi_error(context, slice_slice);;
                 if (rval != FFIError.Ok)
                 {
-                    throw new InteropException<FFIError>(rval);
+                    throw new InteropException<FFIError>(rval, Interop.pattern_last_error_message().ToString());
                 }
             }
             finally
@@ -594,7 +755,7 @@ namespace My.Company
                 var rval = simple_service_method_mut_self_no_error(context, slice_slice);;
                 if (rval != FFIError.Ok)
                 {
-                    throw new InteropException<FFIError>(rval);
+                    throw new InteropException<FFIError>(rval, Interop.pattern_last_error_message().ToString());
                 }
             }
             finally
@@ -617,6 +778,34 @@ namespace My.Company
         [DllImport(NativeLib, CallingConvention = CallingConvention.Cdecl, EntryPoint = "simple_service_return_string")]
         public static extern IntPtr simple_service_return_string(IntPtr context);
 
+        /// Returns an owned copy of this service's string, followed by `suffix`.
+        [DllImport(NativeLib, CallingConvention = CallingConvention.Cdecl, EntryPoint = "simple_service_return_string_utf8")]
+        public static extern Utf8String simple_service_return_string_utf8(IntPtr context, Utf8Str suffix);
+
+        /// Returns an owned copy of this service's string, followed by `suffix`.
+        public static string simple_service_return_string_utf8(IntPtr context, string suffix)
+        {
+            var suffix_utf8 = System.Text.Encoding.UTF8.GetBytes(suffix);
+            var suffix_utf8_pinned = GCHandle.Alloc(suffix_utf8, GCHandleType.Pinned);
+            var suffix_utf8_slice = new Utf8Str(suffix_utf8_pinned, (ulong) suffix_utf8.Length);
+            try
+            {
+                var rval = simple_service_return_string_utf8(context, suffix_utf8_slice);;
+                try
+                {
+                    return rval.ToString();
+                }
+                finally
+                {
+                    Interop.pattern_ffi_string_destroy(rval);
+                }
+            }
+            finally
+            {
+                suffix_utf8_pinned.Free();
+            }
+        }
+
         [DllImport(NativeLib, CallingConvention = CallingConvention.Cdecl, EntryPoint = "simple_service_method_void_ffi_error")]
         public static extern FFIError simple_service_method_void_ffi_error(IntPtr context);
 
@@ -625,7 +814,7 @@ namespace My.Company
             var rval = simple_service_method_void_ffi_error(context);;
             if (rval != FFIError.Ok)
             {
-                throw new InteropException<FFIError>(rval);
+                throw new InteropException<FFIError>(rval, Interop.pattern_last_error_message().ToString());
             }
         }
 
@@ -637,7 +826,7 @@ namespace My.Company
             var rval = simple_service_method_callback(context, callback);;
             if (rval != FFIError.Ok)
             {
-                throw new InteropException<FFIError>(rval);
+                throw new InteropException<FFIError>(rval, Interop.pattern_last_error_message().ToString());
             }
         }
 
@@ -661,7 +850,7 @@ namespace My.Company
             var rval = simple_service_lt_destroy(ref context);;
             if (rval != FFIError.Ok)
             {
-                throw new InteropException<FFIError>(rval);
+                throw new InteropException<FFIError>(rval, Interop.pattern_last_error_message().ToString());
             }
         }
 
@@ -673,7 +862,7 @@ namespace My.Company
             var rval = simple_service_lt_new_with(ref context, ref some_value);;
             if (rval != FFIError.Ok)
             {
-                throw new InteropException<FFIError>(rval);
+                throw new InteropException<FFIError>(rval, Interop.pattern_last_error_message().ToString());
             }
         }
 
@@ -737,10 +926,37 @@ namespace My.Company
             var rval = simple_service_lt_method_void_ffi_error(context);;
             if (rval != FFIError.Ok)
             {
-                throw new InteropException<FFIError>(rval);
+                throw new InteropException<FFIError>(rval, Interop.pattern_last_error_message().ToString());
             }
         }
 
+        /// Frees a vec returned by this library.
+        [DllImport(NativeLib, CallingConvention = CallingConvention.Cdecl, EntryPoint = "pattern_ffi_vec_u32_destroy")]
+        public static extern void pattern_ffi_vec_u32_destroy(Vecu32 vec);
+
+        /// Frees a vec returned by this library.
+        [DllImport(NativeLib, CallingConvention = CallingConvention.Cdecl, EntryPoint = "pattern_ffi_vec_vec3_destroy")]
+        public static extern void pattern_ffi_vec_vec3_destroy(VecVec3f32 vec);
+
+        /// Frees a string returned by this library.
+        [DllImport(NativeLib, CallingConvention = CallingConvention.Cdecl, EntryPoint = "pattern_ffi_string_destroy")]
+        public static extern void pattern_ffi_string_destroy(Utf8String s);
+
+        /// Returns the message of the last error or panic on this thread.
+        ///
+        /// The string is valid until the next error on this thread, and empty if there was none.
+        [DllImport(NativeLib, CallingConvention = CallingConvention.Cdecl, EntryPoint = "pattern_last_error_message")]
+        public static extern Utf8Str pattern_last_error_message();
+
+        /// Returns the message of the last error or panic on this thread.
+        ///
+        /// The string is valid until the next error on this thread, and empty if there was none.
+        public static string pattern_last_error_message_checked()
+        {
+            var rval = pattern_last_error_message();;
+            return rval.ToString();
+        }
+
     }
 
     /// Documented enum.
@@ -759,6 +975,14 @@ namespace My.Company
         X = 0,
     }
 
+    /// Enum stored as a single signed byte.
+    public enum EnumReprI8 : sbyte
+    {
+        Negative = -1,
+        Zero = 0,
+        Positive = 5,
+    }
+
     [Serializable]
     [StructLayout(LayoutKind.Sequential)]
     public partial struct Array
@@ -914,6 +1138,207 @@ namespace My.Company
         IntPtr r;
     }
 
+    /// Union where all fields share the same four bytes.
+    [Serializable]
+    [StructLayout(LayoutKind.Explicit)]
+    public partial struct UnionBits
+    {
+        [FieldOffset(0)]
+        public float f;
+        [FieldOffset(0)]
+        public uint u;
+        /// The raw bytes, in native byte order.
+        [FieldOffset(0)]
+        public byte bytes0;
+        [FieldOffset(1)]
+        public byte bytes1;
+        [FieldOffset(2)]
+        public byte bytes2;
+        [FieldOffset(3)]
+        public byte bytes3;
+    }
+
+    public enum TaggedInlineTag : uint
+    {
+        A = 0,
+        B = 1,
+        C = 10,
+    }
+
+    [Serializable]
+    [StructLayout(LayoutKind.Sequential)]
+    public partial struct TaggedInlineA
+    {
+        public uint tag;
+        public byte x0;
+    }
+
+    [Serializable]
+    [StructLayout(LayoutKind.Sequential)]
+    public partial struct TaggedInlineB
+    {
+        public uint tag;
+        public Vec3f32 x;
+    }
+
+    [Serializable]
+    [StructLayout(LayoutKind.Explicit)]
+    public partial struct TaggedInline
+    {
+        [FieldOffset(0)]
+        uint tag;
+        [FieldOffset(0)]
+        TaggedInlineA A;
+        [FieldOffset(0)]
+        TaggedInlineB B;
+
+        public TaggedInlineTag Tag => (TaggedInlineTag) tag;
+
+        public bool IsA => Tag == TaggedInlineTag.A;
+
+        public TaggedInlineA AsA()
+        {
+            if (Tag != TaggedInlineTag.A)
+            {
+                throw new InvalidOperationException($"Variant is {Tag}, not A.");
+            }
+            return A;
+        }
+
+        public static TaggedInline FromA(TaggedInlineA value)
+        {
+            var rval = new TaggedInline();
+            rval.A = value;
+            rval.tag = (uint) TaggedInlineTag.A;
+            return rval;
+        }
+
+        public bool IsB => Tag == TaggedInlineTag.B;
+
+        public TaggedInlineB AsB()
+        {
+            if (Tag != TaggedInlineTag.B)
+            {
+                throw new InvalidOperationException($"Variant is {Tag}, not B.");
+            }
+            return B;
+        }
+
+        public static TaggedInline FromB(TaggedInlineB value)
+        {
+            var rval = new TaggedInline();
+            rval.B = value;
+            rval.tag = (uint) TaggedInlineTag.B;
+            return rval;
+        }
+
+        public bool IsC => Tag == TaggedInlineTag.C;
+
+        public static TaggedInline FromC()
+        {
+            var rval = new TaggedInline();
+            rval.tag = (uint) TaggedInlineTag.C;
+            return rval;
+        }
+    }
+
+    public enum TaggedShapeTag : byte
+    {
+        /// Has no payload.
+        Empty = 0,
+        Circle = 1,
+        Rect = 2,
+    }
+
+    [Serializable]
+    [StructLayout(LayoutKind.Sequential)]
+    public partial struct TaggedShapeCircle
+    {
+        public float x0;
+    }
+
+    [Serializable]
+    [StructLayout(LayoutKind.Sequential)]
+    public partial struct TaggedShapeRect
+    {
+        public float width;
+        public float height;
+    }
+
+    [Serializable]
+    [StructLayout(LayoutKind.Explicit)]
+    public partial struct TaggedShapePayload
+    {
+        [FieldOffset(0)]
+        public TaggedShapeCircle Circle;
+        [FieldOffset(0)]
+        public TaggedShapeRect Rect;
+    }
+
+    /// Enum with payloads, stored as a tag followed by a union.
+    [Serializable]
+    [StructLayout(LayoutKind.Sequential)]
+    public partial struct TaggedShape
+    {
+        byte tag;
+        TaggedShapePayload payload;
+
+        public TaggedShapeTag Tag => (TaggedShapeTag) tag;
+
+        public bool IsEmpty => Tag == TaggedShapeTag.Empty;
+
+        public static TaggedShape FromEmpty()
+        {
+            var rval = new TaggedShape();
+            rval.tag = (byte) TaggedShapeTag.Empty;
+            return rval;
+        }
+
+        public bool IsCircle => Tag == TaggedShapeTag.Circle;
+
+        public TaggedShapeCircle AsCircle()
+        {
+            if (Tag != TaggedShapeTag.Circle)
+            {
+                throw new InvalidOperationException($"Variant is {Tag}, not Circle.");
+            }
+            return payload.Circle;
+        }
+
+        public static TaggedShape FromCircle(TaggedShapeCircle value)
+        {
+            var rval = new TaggedShape();
+            rval.payload.Circle = value;
+            rval.tag = (byte) TaggedShapeTag.Circle;
+            return rval;
+        }
+
+        public bool IsRect => Tag == TaggedShapeTag.Rect;
+
+        public TaggedShapeRect AsRect()
+        {
+            if (Tag != TaggedShapeTag.Rect)
+            {
+                throw new InvalidOperationException($"Variant is {Tag}, not Rect.");
+            }
+            return payload.Rect;
+        }
+
+        public static TaggedShape FromRect(TaggedShapeRect value)
+        {
+            var rval = new TaggedShape();
+            rval.payload.Rect = value;
+            rval.tag = (byte) TaggedShapeTag.Rect;
+            return rval;
+        }
+    }
+
+    [UnmanagedFunctionPointer(CallingConvention.Cdecl)]
+    public delegate void InteropDelegate_fn_pconst(IntPtr x0);
+
+    [UnmanagedFunctionPointer(CallingConvention.Cdecl)]
+    public delegate uint InteropDelegate_fn_u32_pconst__rval_u32(uint x0, IntPtr x1);
+
     [UnmanagedFunctionPointer(CallingConvention.Cdecl)]
     public delegate byte InteropDelegate_fn_u8_rval_u8(byte x0);
 
@@ -1043,6 +1468,160 @@ namespace My.Company
     }
 
 
+    ///An array of data owned by this library, must be freed by the caller.
+    [Serializable]
+    [StructLayout(LayoutKind.Sequential)]
+    public partial struct VecVec3f32
+    {
+        ///Pointer to start of owned data.
+        IntPtr data;
+        ///Number of elements.
+        ulong len;
+        ///Number of elements allocated.
+        ulong capacity;
+    }
+
+    public partial struct VecVec3f32 : IEnumerable<Vec3f32>
+    {
+        public Vec3f32 this[int i]
+        {
+            get
+            {
+                if (i >= Count) throw new IndexOutOfRangeException();
+                var size = Marshal.SizeOf(typeof(Vec3f32));
+                var ptr = new IntPtr(data.ToInt64() + i * size);
+                return Marshal.PtrToStructure<Vec3f32>(ptr);
+            }
+        }
+        public Vec3f32[] ToArray()
+        {
+            var rval = new Vec3f32[len];
+            for (var i = 0; i < (int) len; i++) {
+                rval[i] = this[i];
+            }
+            return rval;
+        }
+        public int Count => (int) len;
+        public IEnumerator<Vec3f32> GetEnumerator()
+        {
+            for (var i = 0; i < (int)len; ++i)
+            {
+                yield return this[i];
+            }
+        }
+        IEnumerator IEnumerable.GetEnumerator()
+        {
+            return this.GetEnumerator();
+        }
+    }
+
+
+    ///An array of data owned by this library, must be freed by the caller.
+    [Serializable]
+    [StructLayout(LayoutKind.Sequential)]
+    public partial struct Vecu32
+    {
+        ///Pointer to start of owned data.
+        IntPtr data;
+        ///Number of elements.
+        ulong len;
+        ///Number of elements allocated.
+        ulong capacity;
+    }
+
+    public partial struct Vecu32 : IEnumerable<uint>
+    {
+        public uint this[int i]
+        {
+            get
+            {
+                if (i >= Count) throw new IndexOutOfRangeException();
+                var size = Marshal.SizeOf(typeof(uint));
+                var ptr = new IntPtr(data.ToInt64() + i * size);
+                return Marshal.PtrToStructure<uint>(ptr);
+            }
+        }
+        public uint[] ToArray()
+        {
+            var rval = new uint[len];
+            for (var i = 0; i < (int) len; i++) {
+                rval[i] = this[i];
+            }
+            return rval;
+        }
+        public int Count => (int) len;
+        public IEnumerator<uint> GetEnumerator()
+        {
+            for (var i = 0; i < (int)len; ++i)
+            {
+                yield return this[i];
+            }
+        }
+        IEnumerator IEnumerable.GetEnumerator()
+        {
+            return this.GetEnumerator();
+        }
+    }
+
+
+    ///A UTF-8 string borrowed for the duration of a call, not necessarily NUL terminated.
+    [Serializable]
+    [StructLayout(LayoutKind.Sequential)]
+    public partial struct Utf8Str
+    {
+        ///Pointer to the first byte, may be null if empty.
+        IntPtr data;
+        ///Number of bytes.
+        ulong len;
+    }
+
+    public partial struct Utf8Str
+    {
+        public Utf8Str(GCHandle handle, ulong count)
+        {
+            this.data = handle.AddrOfPinnedObject();
+            this.len = count;
+        }
+        public Utf8Str(IntPtr handle, ulong count)
+        {
+            this.data = handle;
+            this.len = count;
+        }
+        public override string ToString()
+        {
+            if (len == 0) return "";
+            var bytes = new byte[len];
+            Marshal.Copy(data, bytes, 0, (int) len);
+            return System.Text.Encoding.UTF8.GetString(bytes);
+        }
+    }
+
+
+    ///A UTF-8 string owned by this library, not NUL terminated, must be freed by the caller.
+    [Serializable]
+    [StructLayout(LayoutKind.Sequential)]
+    public partial struct Utf8String
+    {
+        ///Pointer to the first byte of owned data.
+        IntPtr data;
+        ///Number of bytes.
+        ulong len;
+        ///Number of bytes allocated.
+        ulong capacity;
+    }
+
+    public partial struct Utf8String
+    {
+        public override string ToString()
+        {
+            if (len == 0) return "";
+            var bytes = new byte[len];
+            Marshal.Copy(data, bytes, 0, (int) len);
+            return System.Text.Encoding.UTF8.GetString(bytes);
+        }
+    }
+
+
     ///Option type containing boolean flag and maybe valid data.
     [Serializable]
     [StructLayout(LayoutKind.Sequential)]
@@ -1075,6 +1654,31 @@ namespace My.Company
     }
 
 
+    ///Result type containing either a value or an error code.
+    [Serializable]
+    [StructLayout(LayoutKind.Sequential)]
+    public partial struct Resultu32FFIError
+    {
+        ///Element that is only valid if `err` signals success.
+        public uint t;
+        ///Error code, success variant if `t` is valid.
+        public FFIError err;
+    }
+
+    public partial struct Resultu32FFIError
+    {
+        public uint Ok()
+        {
+            if (err != FFIError.Ok)
+            {
+                throw new InteropException<FFIError>(err, Interop.pattern_last_error_message().ToString());
+            }
+
+            return t;
+        }
+    }
+
+
     [UnmanagedFunctionPointer(CallingConvention.Cdecl)]
     public delegate byte CallbackFFISlice(Sliceu8 slice);
 
@@ -1093,6 +1697,48 @@ namespace My.Company
     [UnmanagedFunctionPointer(CallingConvention.Cdecl)]
     public delegate void MyCallbackVoid(IntPtr ptr);
 
+    ///Callback carrying user data, which is released once the callback is dropped.
+    [Serializable]
+    [StructLayout(LayoutKind.Sequential)]
+    public partial struct MyCallbackData
+    {
+        ///Function to invoke, receiving `data` as its last argument.
+        public InteropDelegate_fn_u32_pconst__rval_u32 callback;
+        ///User data passed to `callback` and `destructor`, may be null.
+        public IntPtr data;
+        ///Called with `data` once the callback is dropped, may be null.
+        public InteropDelegate_fn_pconst destructor;
+    }
+
+    public delegate uint MyCallbackDataDelegate(uint value);
+
+    public partial struct MyCallbackData
+    {
+        static readonly InteropDelegate_fn_u32_pconst__rval_u32 _call = Call;
+        static readonly InteropDelegate_fn_pconst _release = Release;
+
+        /// Boxes `f` into a GCHandle, which is freed once Rust drops this callback.
+        ///
+        /// Pass each instance to Rust only once, overloads taking a MyCallbackDataDelegate box it for every call.
+        public MyCallbackData(MyCallbackDataDelegate f)
+        {
+            callback = _call;
+            data = GCHandle.ToIntPtr(GCHandle.Alloc(f));
+            destructor = _release;
+        }
+
+        static uint Call(uint value, IntPtr data)
+        {
+            return ((MyCallbackDataDelegate) GCHandle.FromIntPtr(data).Target)(value);
+        }
+
+        static void Release(IntPtr data)
+        {
+            GCHandle.FromIntPtr(data).Free();
+        }
+    }
+
+
 
     /// Some struct we want to expose as a class.
     public partial class SimpleService : IDisposable
@@ -1108,7 +1754,7 @@ namespace My.Company
             var rval = Interop.simple_service_new_with(ref self._context, some_value);
             if (rval != FFIError.Ok)
             {
-                throw new InteropException<FFIError>(rval);
+                throw new InteropException<FFIError>(rval, Interop.pattern_last_error_message().ToString());
             }
             return self;
         }
@@ -1119,7 +1765,7 @@ namespace My.Company
             var rval = Interop.simple_service_new_without(ref self._context);
             if (rval != FFIError.Ok)
             {
-                throw new InteropException<FFIError>(rval);
+                throw new InteropException<FFIError>(rval, Interop.pattern_last_error_message().ToString());
             }
             return self;
         }
@@ -1130,7 +1776,7 @@ namespace My.Company
             var rval = Interop.simple_service_new_with_string(ref self._context, ascii);
             if (rval != FFIError.Ok)
             {
-                throw new InteropException<FFIError>(rval);
+                throw new InteropException<FFIError>(rval, Interop.pattern_last_error_message().ToString());
             }
             return self;
         }
@@ -1141,7 +1787,7 @@ namespace My.Company
             var rval = Interop.simple_service_new_failing(ref self._context, some_value);
             if (rval != FFIError.Ok)
             {
-                throw new InteropException<FFIError>(rval);
+                throw new InteropException<FFIError>(rval, Interop.pattern_last_error_message().ToString());
             }
             return self;
         }
@@ -1151,7 +1797,7 @@ namespace My.Company
             var rval = Interop.simple_service_destroy(ref _context);
             if (rval != FFIError.Ok)
             {
-                throw new InteropException<FFIError>(rval);
+                throw new InteropException<FFIError>(rval, Interop.pattern_last_error_message().ToString());
             }
         }
 
@@ -1162,10 +1808,17 @@ namespace My.Company
             var rval = Interop.simple_service_method_result(_context, anon1);
             if (rval != FFIError.Ok)
             {
-                throw new InteropException<FFIError>(rval);
+                throw new InteropException<FFIError>(rval, Interop.pattern_last_error_message().ToString());
             }
         }
 
+        /// Methods returning a Result<T, _> become an `FFIResult<T, FFIError>`.
+        public uint MethodResultValue(uint x)
+        {
+            var rval = Interop.simple_service_method_result_value(_context, x);
+            return rval.Ok();
+        }
+
         public uint MethodValue(uint x)
         {
             return Interop.simple_service_method_value(_context, x);
@@ -1231,7 +1884,7 @@ namespace My.Company
             var rval = Interop.simple_service_method_mut_self_ffi_error(_context, slice);
             if (rval != FFIError.Ok)
             {
-                throw new InteropException<FFIError>(rval);
+                throw new InteropException<FFIError>(rval, Interop.pattern_last_error_message().ToString());
             }
         }
 
@@ -1245,7 +1898,7 @@ namespace My.Company
             var rval = Interop.simple_service_method_mut_self_no_error(_context, slice);
             if (rval != FFIError.Ok)
             {
-                throw new InteropException<FFIError>(rval);
+                throw new InteropException<FFIError>(rval, Interop.pattern_last_error_message().ToString());
             }
         }
 
@@ -1275,12 +1928,32 @@ namespace My.Company
             return Marshal.PtrToStringAnsi(s);
         }
 
+        /// Returns an owned copy of this service's string, followed by `suffix`.
+        public string ReturnStringUtf8(Utf8Str suffix)
+        {
+            var rval = Interop.simple_service_return_string_utf8(_context, suffix);
+            try
+            {
+                return rval.ToString();
+            }
+            finally
+            {
+                Interop.pattern_ffi_string_destroy(rval);
+            }
+        }
+
+        /// Returns an owned copy of this service's string, followed by `suffix`.
+        public string ReturnStringUtf8(string suffix)
+        {
+            return Interop.simple_service_return_string_utf8(_context, suffix);
+        }
+
         public void MethodVoidFfiError()
         {
             var rval = Interop.simple_service_method_void_ffi_error(_context);
             if (rval != FFIError.Ok)
             {
-                throw new InteropException<FFIError>(rval);
+                throw new InteropException<FFIError>(rval, Interop.pattern_last_error_message().ToString());
             }
         }
 
@@ -1289,7 +1962,7 @@ namespace My.Company
             var rval = Interop.simple_service_method_callback(_context, callback);
             if (rval != FFIError.Ok)
             {
-                throw new InteropException<FFIError>(rval);
+                throw new InteropException<FFIError>(rval, Interop.pattern_last_error_message().ToString());
             }
         }
 
@@ -1309,7 +1982,7 @@ namespace My.Company
             var rval = Interop.simple_service_lt_new_with(ref self._context, ref some_value);
             if (rval != FFIError.Ok)
             {
-                throw new InteropException<FFIError>(rval);
+                throw new InteropException<FFIError>(rval, Interop.pattern_last_error_message().ToString());
             }
             return self;
         }
@@ -1319,7 +1992,7 @@ namespace My.Company
             var rval = Interop.simple_service_lt_destroy(ref _context);
             if (rval != FFIError.Ok)
             {
-                throw new InteropException<FFIError>(rval);
+                throw new InteropException<FFIError>(rval, Interop.pattern_last_error_message().ToString());
             }
         }
 
@@ -1359,7 +2032,7 @@ namespace My.Company
             var rval = Interop.simple_service_lt_method_void_ffi_error(_context);
             if (rval != FFIError.Ok)
             {
-                throw new InteropException<FFIError>(rval);
+                throw new InteropException<FFIError>(rval, Interop.pattern_last_error_message().ToString());
             }
         }
 
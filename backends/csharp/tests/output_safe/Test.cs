@@ -19,6 +19,12 @@ namespace interop_test
             Assert.Equal(5, Interop.array_1(array));
         }
 
+        [Fact]
+        public void primitive_char()
+        {
+            Assert.Equal((uint) 'A', Interop.primitive_char('a'));
+        }
+
         [Fact]
         public void pattern_ffi_slice_delegate()
         {
@@ -53,6 +59,67 @@ namespace interop_test
         }
 
 
+        [Fact]
+        public void pattern_ffi_vec()
+        {
+            Assert.Equal(new uint[] { 0, 1, 2, 3, 4 }, Interop.pattern_ffi_vec_1_checked(5));
+
+            var array = Interop.pattern_ffi_vec_2_checked(1.5f, 3);
+            var other = Interop.pattern_ffi_vec_2_checked(9.0f, 3);
+
+            Assert.Equal(3, array.Length);
+            Assert.Equal(1.5f, array[2].z);
+            Assert.Equal(9.0f, other[2].z);
+
+            var vec = Interop.pattern_ffi_vec_1(5);
+            Assert.Equal(5, vec.Count);
+            Assert.Equal(4u, vec[4]);
+            Assert.Equal(10u, Interop.pattern_ffi_vec_3(vec));
+        }
+
+        [Fact]
+        public void pattern_ffi_string()
+        {
+            Assert.Equal(5u, Interop.pattern_ffi_str_1("grüße"));
+            Assert.Equal("GRÜSSE\0", Interop.pattern_ffi_string_1("grüße\0"));
+            Assert.Equal("hello\0wörld", Interop.pattern_ffi_string_2_checked());
+            Assert.Equal("", Interop.pattern_ffi_string_1(""));
+        }
+
+        [Fact]
+        public void pattern_ffi_result()
+        {
+            Assert.Equal(20u, Interop.pattern_ffi_result_1_checked(10));
+            Assert.Throws<InteropException<FFIError>>(() => Interop.pattern_ffi_result_1_checked(uint.MaxValue));
+
+            var simpleService = SimpleService.NewWith(123);
+            Assert.Equal(133u, simpleService.MethodResultValue(10));
+            Assert.Throws<InteropException<FFIError>>(() => simpleService.MethodResultValue(uint.MaxValue));
+        }
+
+        [Fact]
+        public void pattern_last_error()
+        {
+            var e = Assert.Throws<InteropException<FFIError>>(() => Interop.panics_checked());
+            Assert.Contains("Oh no", e.Message);
+            Assert.Contains("Oh no", Interop.pattern_last_error_message_checked());
+        }
+
+        [Fact]
+        public void pattern_callback_with_data()
+        {
+            var offset = 10u;
+            Assert.Equal(13u, Interop.pattern_callback_with_data_1(x => x + offset, 3));
+        }
+
+        [Fact]
+        public void pattern_callback_with_data_reused()
+        {
+            MyCallbackDataDelegate twice = x => x * 2;
+            Assert.Equal(6u, Interop.pattern_callback_with_data_1(twice, 3));
+            Assert.Equal(8u, Interop.pattern_callback_with_data_1(twice, 4));
+        }
+
         [Fact]
         public void pattern_ffi_option_nullable()
         {
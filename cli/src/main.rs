@@ -0,0 +1,222 @@
+//! Generates bindings from a serialized inventory, without having to write a Rust test per backend.
+//!
+//! ```text
+//! interoptopus <bindings.toml> [--inventory <inventory.json> | --library <library.so>]
+//! ```
+//!
+//! The inventory is what [`Inventory::to_json`](interoptopus::Inventory::to_json) produced for your library, or is
+//! read from a compiled library that [embedded](interoptopus::embed) it (via `library = "..."` or `--library`). The
+//! bindings file is TOML (or JSON if it ends in `.json`) and lists which backends to run, where to write their output,
+//! and which values of the backend's `Config` to change:
+//!
+//! ```toml
+//! inventory = "target/inventory.json"
+//!
+//! [[c]]
+//! output = "bindings/c/my_library.h"
+//!
+//! [c.config]
+//! ifndef = "my_library"
+//! indentation = "KAndR"
+//!
+//! [[csharp]]
+//! output = "bindings/csharp/Interop.cs"
+//! overloads = ["DotNet"]
+//!
+//! [csharp.config]
+//! dll_name = "my_library"
+//! namespace_mappings = { "" = "My.Company", common = "My.Company.Common" }
+//!
+//! [[python]]
+//! output = "bindings/python/my_library.py"
+//! ```
+//!
+//! Before writing a file the backend [validates](interoptopus::validate) the inventory, errors abort the run.
+//!
+//! Config fields not given keep their defaults, enum values are given by their Rust variant name. Relative paths are
+//! resolved against the directory of the bindings file, except for `--inventory` and `--library` which override the
+//! file's `inventory` or `library` and are resolved against the working directory.
+
+use interoptopus::embed::inventory_from_library;
+use interoptopus::{Interop, Inventory};
+use interoptopus_backend_csharp::overloads::{DotNet, Unity};
+use serde::Deserialize;
+use std::error::Error;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const USAGE: &str = "usage: interoptopus <bindings.toml> [--inventory <inventory.json> | --library <library.so>]";
+
+/// Contents of the bindings file.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Bindings {
+    inventory: Option<PathBuf>,
+    library: Option<PathBuf>,
+    #[serde(default)]
+    c: Vec<Output<interoptopus_backend_c::Config>>,
+    #[serde(default)]
+    csharp: Vec<CSharpOutput>,
+    #[serde(default)]
+    python: Vec<Output<interoptopus_backend_cpython::Config>>,
+}
+
+/// A single file to generate with a backend's config.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields, bound(deserialize = "C: Deserialize<'de> + Default"))]
+struct Output<C> {
+    output: PathBuf,
+    #[serde(default)]
+    config: C,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CSharpOutput {
+    output: PathBuf,
+    #[serde(default)]
+    config: interoptopus_backend_csharp::Config,
+    #[serde(default)]
+    overloads: Vec<Overload>,
+}
+
+#[derive(Deserialize)]
+enum Overload {
+    DotNet,
+    Unity,
+}
+
+/// Where to read the inventory from.
+enum Source {
+    Inventory(PathBuf),
+    Library(PathBuf),
+}
+
+/// Command line arguments.
+struct Args {
+    bindings: PathBuf,
+    source: Option<Source>,
+}
+
+impl Args {
+    /// Returns `None` if the usage was asked for.
+    fn parse(mut args: impl Iterator<Item = String>) -> Result<Option<Self>, String> {
+        let mut bindings = None;
+        let mut source = None;
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "-h" | "--help" => return Ok(None),
+                "--inventory" if source.is_none() => source = Some(Source::Inventory(args.next().ok_or("--inventory requires a path")?.into())),
+                "--library" if source.is_none() => source = Some(Source::Library(args.next().ok_or("--library requires a path")?.into())),
+                "--inventory" | "--library" => return Err(format!("only one of --inventory and --library can be given\n{}", USAGE)),
+                _ if arg.starts_with('-') => return Err(format!("unknown option {}\n{}", arg, USAGE)),
+                _ if bindings.is_none() => bindings = Some(arg.into()),
+                _ => return Err(format!("unexpected argument {}\n{}", arg, USAGE)),
+            }
+        }
+
+        let bindings = bindings.ok_or(USAGE)?;
+
+        Ok(Some(Self { bindings, source }))
+    }
+}
+
+fn main() {
+    let result = match Args::parse(std::env::args().skip(1)) {
+        Ok(Some(args)) => run(args),
+        Ok(None) => {
+            println!("{}", USAGE);
+            return;
+        }
+        Err(e) => Err(e.into()),
+    };
+
+    if let Err(e) = result {
+        let mut message = e.to_string();
+        let mut source = e.source();
+
+        while let Some(x) = source {
+            message = format!("{}: {}", message, x);
+            source = x.source();
+        }
+
+        eprintln!("error: {}", message);
+        std::process::exit(1);
+    }
+}
+
+fn run(args: Args) -> Result<(), Box<dyn Error>> {
+    let text = fs::read_to_string(&args.bindings).map_err(|e| format!("cannot read {}: {}", args.bindings.display(), e))?;
+
+    let bindings: Bindings = if args.bindings.extension() == Some("json".as_ref()) {
+        serde_json::from_str(&text).map_err(|e| format!("invalid bindings file {}: {}", args.bindings.display(), e))?
+    } else {
+        toml::from_str(&text).map_err(|e| format!("invalid bindings file {}: {}", args.bindings.display(), e))?
+    };
+
+    let base = args.bindings.parent().unwrap_or_else(|| Path::new(""));
+    let source = match (args.source, bindings.inventory, bindings.library) {
+        (Some(source), _, _) => source,
+        (None, Some(path), None) => Source::Inventory(base.join(path)),
+        (None, None, Some(path)) => Source::Library(base.join(path)),
+        (None, Some(_), Some(_)) => return Err("only one of `inventory` and `library` can be set in the bindings file".into()),
+        (None, None, None) => return Err("no inventory given, set `inventory` or `library` in the bindings file, or pass --inventory or --library".into()),
+    };
+
+    let inventory = read_inventory(&source)?;
+
+    for x in bindings.c {
+        let generator = interoptopus_backend_c::Generator::new(x.config, inventory.clone());
+        write(&generator, &base.join(x.output))?;
+    }
+
+    for x in bindings.csharp {
+        let mut generator = interoptopus_backend_csharp::Generator::new(x.config, inventory.clone());
+
+        for overload in x.overloads {
+            match overload {
+                Overload::DotNet => generator.add_overload_writer(DotNet::new()),
+                Overload::Unity => generator.add_overload_writer(Unity::new()),
+            };
+        }
+
+        write(&generator, &base.join(x.output))?;
+    }
+
+    for x in bindings.python {
+        let generator = interoptopus_backend_cpython::Generator::new(x.config, inventory.clone());
+        write(&generator, &base.join(x.output))?;
+    }
+
+    Ok(())
+}
+
+fn read_inventory(source: &Source) -> Result<Inventory, Box<dyn Error>> {
+    match source {
+        Source::Inventory(path) => {
+            let json = fs::read_to_string(path).map_err(|e| format!("cannot read {}: {}", path.display(), e))?;
+
+            Inventory::from_json(&json).map_err(|e| format!("{}: {}", path.display(), e).into())
+        }
+        Source::Library(path) => inventory_from_library(path).map_err(|e| format!("cannot read inventory from {}: {}", path.display(), e).into()),
+    }
+}
+
+fn write(generator: &impl Interop, path: &Path) -> Result<(), Box<dyn Error>> {
+    let validation = generator.validate();
+    eprint!("{}", validation);
+
+    if validation.has_errors() {
+        return Err(format!("cannot generate {}, see errors above", path.display()).into());
+    }
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    generator.write_file(path)?;
+
+    println!("Wrote {}", path.display());
+    Ok(())
+}
@@ -0,0 +1,125 @@
+use interoptopus::diff::diff_inventories;
+use interoptopus::embed::inventory_from_library;
+use interoptopus::util::NamespaceMappings;
+use interoptopus::{Error, Interop};
+use interoptopus_backend_c::CIndentationStyle;
+use interoptopus_backend_csharp::overloads::DotNet;
+use std::env::consts::{DLL_PREFIX, DLL_SUFFIX};
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Output};
+use tempdir::TempDir;
+
+fn run_cli(args: &[&Path]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_interoptopus")).args(args).output().unwrap()
+}
+
+fn generated(generator: &impl Interop) -> Result<String, Error> {
+    let mut buffer = Vec::new();
+    let mut writer = interoptopus::writer::IndentWriter::new(&mut buffer);
+    generator.write_to(&mut writer)?;
+    Ok(String::from_utf8(buffer)?)
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn generates_same_bindings_as_backends() -> Result<(), Error> {
+    let dir = TempDir::new("interoptopus_cli")?;
+    let inventory = interoptopus_reference_project::ffi_inventory();
+
+    fs::write(dir.path().join("inventory.json"), inventory.to_json()?)?;
+    fs::write(
+        dir.path().join("bindings.toml"),
+        r#"
+inventory = "inventory.json"
+
+[[c]]
+output = "c/reference_project.h"
+
+[c.config]
+ifndef = "reference_project"
+indentation = "KAndR"
+
+[[csharp]]
+output = "csharp/Interop.cs"
+overloads = ["DotNet"]
+
+[csharp.config]
+dll_name = "interoptopus_reference_project"
+namespace_mappings = { "" = "My.Company", common = "My.Company.Common" }
+
+[[python]]
+output = "reference_project.py"
+"#,
+    )?;
+
+    let output = run_cli(&[&dir.path().join("bindings.toml")]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+
+    let c_config = interoptopus_backend_c::Config {
+        ifndef: "reference_project".to_string(),
+        indentation: CIndentationStyle::KAndR,
+        ..Default::default()
+    };
+    let c = interoptopus_backend_c::Generator::new(c_config, inventory.clone());
+    assert_eq!(fs::read_to_string(dir.path().join("c/reference_project.h"))?, generated(&c)?);
+
+    let csharp_config = interoptopus_backend_csharp::Config {
+        dll_name: "interoptopus_reference_project".to_string(),
+        namespace_mappings: NamespaceMappings::new("My.Company").add("common", "My.Company.Common"),
+        ..Default::default()
+    };
+    let mut csharp = interoptopus_backend_csharp::Generator::new(csharp_config, inventory.clone());
+    csharp.add_overload_writer(DotNet::new());
+    assert_eq!(fs::read_to_string(dir.path().join("csharp/Interop.cs"))?, generated(&csharp)?);
+
+    let python = interoptopus_backend_cpython::Generator::new(Default::default(), inventory);
+    assert_eq!(fs::read_to_string(dir.path().join("reference_project.py"))?, generated(&python)?);
+
+    Ok(())
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn rejects_unknown_config_fields() -> Result<(), Error> {
+    let dir = TempDir::new("interoptopus_cli")?;
+
+    fs::write(dir.path().join("inventory.json"), interoptopus_reference_project::ffi_inventory().to_json()?)?;
+    fs::write(dir.path().join("bindings.json"), r#"{ "inventory": "inventory.json", "java": [] }"#)?;
+
+    let output = run_cli(&[&dir.path().join("bindings.json")]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("unknown field `java`"));
+
+    Ok(())
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn generates_bindings_from_library() -> Result<(), Error> {
+    let dir = TempDir::new("interoptopus_cli")?;
+    let deps = Path::new(env!("CARGO_BIN_EXE_interoptopus")).parent().unwrap().join("deps");
+    let library = deps.join(format!("{}interoptopus_reference_project{}", DLL_PREFIX, DLL_SUFFIX));
+    let inventory = interoptopus_reference_project::ffi_inventory();
+
+    assert!(diff_inventories(&inventory, &inventory_from_library(&library)?).is_empty());
+
+    fs::write(dir.path().join("bindings.toml"), "[[python]]\noutput = \"reference_project.py\"\n")?;
+
+    let output = run_cli(&[&dir.path().join("bindings.toml"), Path::new("--library"), &library]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+
+    let python = interoptopus_backend_cpython::Generator::new(Default::default(), inventory);
+    assert_eq!(fs::read_to_string(dir.path().join("reference_project.py"))?, generated(&python)?);
+
+    Ok(())
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn prints_usage_on_help() {
+    let output = run_cli(&[Path::new("--help")]);
+
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).starts_with("usage: interoptopus"));
+}
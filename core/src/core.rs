@@ -1,6 +1,13 @@
 use crate::lang::c::{CType, Constant, Function};
 use crate::patterns::LibraryPattern;
+#[cfg(feature = "registry")]
+use crate::registry::Registration;
 use crate::util::{ctypes_from_functions_types, extract_namespaces_from_types};
+use crate::validate::Validation;
+#[cfg(feature = "serde")]
+use crate::Error;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
 
 /// Tells the [`InventoryBuilder`] what to register.
@@ -14,6 +21,21 @@ pub enum Symbol {
     Pattern(LibraryPattern),
 }
 
+impl Symbol {
+    /// The namespace of this item, if it has one.
+    pub fn namespace(&self) -> Option<&str> {
+        match self {
+            Symbol::Function(x) => Some(x.meta().namespace()),
+            Symbol::Constant(x) => Some(x.meta().namespace()),
+            Symbol::Type(x) => x.namespace(),
+            Symbol::Pattern(LibraryPattern::Service(x)) => Some(x.the_type().meta().namespace()),
+            Symbol::Pattern(LibraryPattern::Vec(x)) => Some(x.the_type().meta().namespace()),
+            Symbol::Pattern(LibraryPattern::String(x)) => Some(x.destructor().meta().namespace()),
+            Symbol::Pattern(LibraryPattern::LastError(x)) => Some(x.function().meta().namespace()),
+        }
+    }
+}
+
 /// Produces a [`Inventory`] inside your inventory function, **start here**.
 ///
 /// # Example
@@ -56,6 +78,7 @@ pub struct InventoryBuilder {
     ctypes: Vec<CType>,
     constants: Vec<Constant>,
     patterns: Vec<LibraryPattern>,
+    api_version: (u16, u16),
 }
 
 impl InventoryBuilder {
@@ -66,9 +89,18 @@ impl InventoryBuilder {
             ctypes: Vec::new(),
             constants: Vec::new(),
             patterns: Vec::new(),
+            api_version: (0, 0),
         }
     }
 
+    /// Declares the `major.minor` version of this API, used by the [`api_guard`](crate::patterns::api_guard) pattern.
+    ///
+    /// Bump the minor version when only adding items, and the major version on breaking changes.
+    pub fn api_version(mut self, major: u16, minor: u16) -> Self {
+        self.api_version = (major, minor);
+        self
+    }
+
     /// Registers a symbol.
     ///
     /// Call this with the result of a [`function`](crate::function), [`constant`](crate::constant), [`extra_type`](crate::extra_type) or [`pattern`](crate::pattern) macro,
@@ -85,6 +117,9 @@ impl InventoryBuilder {
                         self.functions.extend(x.constructors().iter().cloned());
                         self.functions.extend(x.methods().iter().cloned());
                     }
+                    LibraryPattern::Vec(x) => self.functions.push(x.destructor().clone()),
+                    LibraryPattern::String(x) => self.functions.push(x.destructor().clone()),
+                    LibraryPattern::LastError(x) => self.functions.push(x.function().clone()),
                 }
                 self.patterns.push(x)
             }
@@ -93,20 +128,72 @@ impl InventoryBuilder {
         self
     }
 
+    /// Registers all items defined in module `path` or its submodules, see [`registry`](crate::registry).
+    ///
+    /// The `path` is a module path like `my_crate` or `my_crate::ffi`, usually built via `module_path!()`.
+    #[cfg(feature = "registry")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "registry")))]
+    pub fn register_module(self, path: &str) -> Self {
+        self.register_where(|registration, _| registration.is_in_module(path))
+    }
+
+    /// Registers all items accepted by `filter`, see [`registry`](crate::registry).
+    ///
+    /// Items already registered are skipped, so this can be combined with [`register`](Self::register) and
+    /// other filters, e.g., to only include items of a namespace:
+    ///
+    /// ```
+    /// # use interoptopus::InventoryBuilder;
+    /// # #[cfg(feature = "registry")]
+    /// let inventory = InventoryBuilder::new()
+    ///     .register_where(|registration, symbol| registration.is_in_module("my_crate") && symbol.namespace() == Some("common"))
+    ///     .inventory();
+    /// ```
+    #[cfg(feature = "registry")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "registry")))]
+    pub fn register_where(mut self, filter: impl Fn(&Registration, &Symbol) -> bool) -> Self {
+        let (patterns, others): (Vec<_>, Vec<_>) = crate::registry::registrations()
+            .into_iter()
+            .map(|x| (x, x.symbol()))
+            .filter(|(registration, symbol)| filter(registration, symbol))
+            .map(|(_, symbol)| symbol)
+            .partition(|x| matches!(x, Symbol::Pattern(_)));
+
+        // Services register their own functions, which also registered themselves; so do patterns first.
+        for symbol in patterns.into_iter().chain(others) {
+            let known = match &symbol {
+                Symbol::Function(x) => self.functions.iter().any(|f| f.name() == x.name()),
+                Symbol::Constant(x) => self.constants.iter().any(|c| c.name() == x.name()),
+                Symbol::Type(x) => self.ctypes.contains(x),
+                Symbol::Pattern(x) => self.patterns.contains(x),
+            };
+
+            if !known {
+                self = self.register(symbol);
+            }
+        }
+
+        self
+    }
+
     /// Produce the [`Inventory`].
     pub fn inventory(self) -> Inventory {
-        Inventory::new(self.functions, self.constants, self.patterns, self.ctypes)
+        let mut inventory = Inventory::new(self.functions, self.constants, self.patterns, self.ctypes);
+        inventory.api_version = self.api_version;
+        inventory
     }
 }
 
 /// Represents all FFI-relevant items, produced via [`InventoryBuilder`], ingested by backends.
 #[derive(Clone, Debug, PartialOrd, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct Inventory {
     functions: Vec<Function>,
     ctypes: Vec<CType>,
     constants: Vec<Constant>,
     patterns: Vec<LibraryPattern>,
     namespaces: Vec<String>,
+    api_version: (u16, u16),
 }
 
 /// References to items contained within an [`Inventory`].
@@ -123,10 +210,17 @@ pub enum InventoryItem<'a> {
 }
 
 impl Inventory {
+    /// Version of the format written by [`Inventory::to_json`].
+    #[cfg(feature = "serde")]
+    pub const JSON_FORMAT_VERSION: u32 = 1;
+
     /// Produce a new inventory for the given functions, constants and patterns.
     ///
     /// Type information will be automatically derived from the used fields and parameters.
-    fn new(functions: Vec<Function>, constants: Vec<Constant>, patterns: Vec<LibraryPattern>, extra_types: Vec<CType>) -> Self {
+    fn new(functions: Vec<Function>, constants: Vec<Constant>, patterns: Vec<LibraryPattern>, mut extra_types: Vec<CType>) -> Self {
+        // Struct and array constants need their types defined, even if no function uses them.
+        extra_types.extend(constants.iter().map(|x| x.the_type()).filter(|x| matches!(x, CType::Array(_) | CType::Composite(_))));
+
         let mut ctypes = ctypes_from_functions_types(&functions, &extra_types);
         let mut namespaces = HashSet::new();
 
@@ -150,6 +244,7 @@ impl Inventory {
             constants,
             patterns,
             namespaces,
+            api_version: (0, 0),
         }
     }
 
@@ -169,6 +264,11 @@ impl Inventory {
         &self.constants
     }
 
+    /// Return the declared `major.minor` API version, `(0, 0)` if none was declared.
+    pub fn api_version(&self) -> (u16, u16) {
+        self.api_version
+    }
+
     /// Return all known namespaces.
     pub fn namespaces(&self) -> &[String] {
         &self.namespaces
@@ -180,6 +280,77 @@ impl Inventory {
         &self.patterns
     }
 
+    /// Checks for problems that would break bindings in any language, see [`validate`](crate::validate).
+    pub fn validate(&self) -> Validation {
+        crate::validate::validate(self)
+    }
+
+    /// Serializes this inventory into the JSON format described below, e.g., to check an API baseline into git.
+    ///
+    /// # Format
+    ///
+    /// The JSON document is an object holding the format version and the inventory:
+    ///
+    /// ```json
+    /// {
+    ///   "format_version": 1,
+    ///   "inventory": {
+    ///     "functions": [ { "name": "f", "meta": { "documentation": { "lines": [] }, "namespace": "" }, "signature": { ... } } ],
+    ///     "ctypes": [ { "primitive": "u32" }, { "read_pointer": { "composite": { ... } } } ],
+    ///     "constants": [ { "name": "C", "value": { "primitive": { "u8": 255 } }, "meta": { ... } } ],
+    ///     "patterns": [ { "service": { ... } } ],
+    ///     "namespaces": [ "" ],
+    ///     "api_version": [ 1, 0 ]
+    ///   }
+    /// }
+    /// ```
+    ///
+    /// Within `inventory`, every item of the [`lang::c`](crate::lang::c) model and the [`patterns`](crate::patterns) maps as follows:
+    ///
+    /// - structs become objects with one key per field, e.g., a [`Field`](crate::lang::c::Field) is `{ "name": "x", "the_type": { ... }, "visibility": "public", "documentation": { "lines": [] } }`,
+    /// - enums with data become objects with a single key, the variant name in `snake_case`, e.g., `{ "fn_pointer": { ... } }`,
+    /// - enums without data become the variant name as a string, e.g., `"separate"` for [`TagPlacement`](crate::lang::c::TagPlacement),
+    /// - primitives use their lowercase Rust name, e.g., `"u8"`, `"usize"`, and `"void"` for `()`,
+    /// - 128-bit integer values become decimal strings, e.g., `{ "u128": "340282366920938463463374607431768211455" }`,
+    /// - non-finite float values become `"NaN"`, `"inf"` or `"-inf"`, finite ones stay numbers,
+    /// - type patterns use `snake_case` names, e.g., `"ascii_pointer"`, `"api_version"` or `{ "ffi_error_enum": { ... } }`.
+    ///
+    /// The `format_version` is bumped on every incompatible change to this format, [`Inventory::from_json`] will
+    /// reject documents with any other version.
+    #[cfg(feature = "serde")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+    pub fn to_json(&self) -> Result<String, Error> {
+        #[derive(serde::Serialize)]
+        struct Document<'a> {
+            format_version: u32,
+            inventory: &'a Inventory,
+        }
+
+        let document = Document {
+            format_version: Self::JSON_FORMAT_VERSION,
+            inventory: self,
+        };
+
+        Ok(serde_json::to_string_pretty(&document)?)
+    }
+
+    /// Reads an inventory produced by [`Inventory::to_json`].
+    #[cfg(feature = "serde")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+    pub fn from_json(json: &str) -> Result<Self, Error> {
+        use serde::de::Error as _;
+
+        let mut json: serde_json::Value = serde_json::from_str(json)?;
+        let version = json.get("format_version").and_then(|x| x.as_u64());
+
+        if version != Some(u64::from(Self::JSON_FORMAT_VERSION)) {
+            let message = format!("expected inventory format version {}, found {:?}", Self::JSON_FORMAT_VERSION, version);
+            return Err(Error::Serde(serde_json::Error::custom(message)));
+        }
+
+        Ok(serde_json::from_value(json["inventory"].take())?)
+    }
+
     /// Return a new [`Inventory`] filtering items by a predicate.
     ///
     /// Useful for removing duplicate symbols when generating bindings split across multiple files.
@@ -213,6 +384,7 @@ impl Inventory {
             constants,
             patterns,
             namespaces,
+            api_version: self.api_version,
         }
     }
 }
@@ -230,6 +402,9 @@ pub fn non_service_functions(inventory: &Inventory) -> Vec<&Function> {
                 service_methods.extend_from_slice(service.constructors());
                 service_methods.push(service.destructor().clone());
             }
+            LibraryPattern::Vec(_) => {}
+            LibraryPattern::String(_) => {}
+            LibraryPattern::LastError(_) => {}
         }
     }
 
@@ -242,6 +417,8 @@ pub fn non_service_functions(inventory: &Inventory) -> Vec<&Function> {
 /// symbols (e.g., _core_ and _extension_ functions) and you want to create different
 /// bindings based on some compile target or configuration
 ///
+/// The merged inventory has the highest [`api_version`](Inventory::api_version) of all inventories.
+///
 /// # Example
 ///
 /// ```
@@ -264,13 +441,93 @@ pub fn merge_inventories(inventories: &[Inventory]) -> Inventory {
     let mut constants = Vec::new();
     let mut patterns = Vec::new();
     let mut types = Vec::new();
+    let mut api_version = (0, 0);
 
     for inventory in inventories {
+        api_version = api_version.max(inventory.api_version());
         functions.extend_from_slice(inventory.functions());
         constants.extend_from_slice(inventory.constants());
         patterns.extend_from_slice(inventory.patterns());
         types.extend_from_slice(inventory.ctypes());
     }
 
-    Inventory::new(functions, constants, patterns, types)
+    let mut inventory = Inventory::new(functions, constants, patterns, types);
+    inventory.api_version = api_version;
+    inventory
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod test {
+    use crate::lang::c::{CType, CompositeType, Constant, ConstantValue, Field, Function, FunctionSignature, Meta, Parameter, PrimitiveType, PrimitiveValue};
+    use crate::patterns::TypePattern;
+    use crate::{Error, Inventory, InventoryBuilder, Symbol};
+
+    fn inventory() -> Inventory {
+        let vec = CompositeType::new("Vec".to_string(), vec![Field::new("x".to_string(), CType::Primitive(PrimitiveType::F32))]);
+        let params = vec![
+            Parameter::new("v".to_string(), CType::ReadPointer(Box::new(CType::Composite(vec)))),
+            Parameter::new("s".to_string(), CType::Pattern(TypePattern::AsciiPointer)),
+        ];
+        let function = Function::new("f".to_string(), FunctionSignature::new(params, CType::Primitive(PrimitiveType::USize)), Meta::new());
+        let constant = Constant::new("C".to_string(), ConstantValue::Primitive(PrimitiveValue::U8(255)), Meta::new());
+
+        InventoryBuilder::new()
+            .api_version(1, 2)
+            .register(Symbol::Function(function))
+            .register(Symbol::Constant(constant))
+            .inventory()
+    }
+
+    #[test]
+    fn json_roundtrip() -> Result<(), Error> {
+        let inventory = inventory();
+        let json = inventory.to_json()?;
+
+        assert_eq!(Inventory::from_json(&json)?, inventory);
+        Ok(())
+    }
+
+    fn constants(values: Vec<PrimitiveValue>) -> Inventory {
+        values
+            .into_iter()
+            .enumerate()
+            .map(|(i, x)| Constant::new(format!("C{}", i), ConstantValue::Primitive(x), Meta::new()))
+            .fold(InventoryBuilder::new(), |b, x| b.register(Symbol::Constant(x)))
+            .inventory()
+    }
+
+    #[test]
+    fn json_roundtrip_128bit() -> Result<(), Error> {
+        let inventory = constants(vec![PrimitiveValue::U128(u128::MAX), PrimitiveValue::I128(i128::MIN)]);
+        let json = inventory.to_json()?;
+
+        assert!(json.contains("\"340282366920938463463374607431768211455\""));
+        assert_eq!(Inventory::from_json(&json)?, inventory);
+        Ok(())
+    }
+
+    #[test]
+    fn json_roundtrip_non_finite_floats() -> Result<(), Error> {
+        let inventory = constants(vec![
+            PrimitiveValue::F32(f32::INFINITY),
+            PrimitiveValue::F64(f64::NEG_INFINITY),
+            PrimitiveValue::F64(f64::NAN),
+            PrimitiveValue::F64(1.5),
+        ]);
+        let roundtrip = Inventory::from_json(&inventory.to_json()?)?;
+        let values = roundtrip.constants().iter().map(|x| x.value().clone()).collect::<Vec<_>>();
+
+        assert_eq!(values[0], ConstantValue::Primitive(PrimitiveValue::F32(f32::INFINITY)));
+        assert_eq!(values[1], ConstantValue::Primitive(PrimitiveValue::F64(f64::NEG_INFINITY)));
+        assert!(matches!(values[2], ConstantValue::Primitive(PrimitiveValue::F64(x)) if x.is_nan()));
+        assert_eq!(values[3], ConstantValue::Primitive(PrimitiveValue::F64(1.5)));
+        Ok(())
+    }
+
+    #[test]
+    fn json_rejects_other_versions() {
+        let json = inventory().to_json().unwrap().replace("\"format_version\": 1", "\"format_version\": 2");
+
+        assert!(Inventory::from_json(&json).is_err());
+    }
 }
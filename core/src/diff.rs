@@ -0,0 +1,491 @@
+//! Compares two inventories, e.g., to detect accidental ABI breaks in CI.
+//!
+//! Given the [`Inventory`] your current bindings were generated from and the one of your library,
+//! [`diff_inventories`] reports added, removed and changed items, classifying each change by the
+//! [`Severity`] it has for consumers of existing bindings.
+//!
+//! # Example
+//!
+//! ```
+//! use interoptopus::diff::{diff_inventories, Severity};
+//! # use interoptopus::Inventory;
+//! # let baseline = Inventory::default();
+//! # let current = Inventory::default();
+//!
+//! let diff = diff_inventories(&baseline, &current);
+//!
+//! // Prints lines like `[abi-breaking] struct Vec3: field `z` removed`.
+//! println!("{}", diff);
+//!
+//! assert!(diff.severity() < Severity::AbiBreaking);
+//! ```
+//!
+//! # Classification
+//!
+//! | Change | Severity |
+//! | --- | --- |
+//! | Function, type, constant, service or enum variant added | [`Compatible`](Severity::Compatible) |
+//! | Parameter renamed, field visibility changed | [`SourceBreaking`](Severity::SourceBreaking) |
+//! | Type, constant or service removed | [`SourceBreaking`](Severity::SourceBreaking) |
+//! | Function removed, parameter or return type changed, parameter added or removed | [`AbiBreaking`](Severity::AbiBreaking) |
+//! | Struct field added, removed, reordered or changed | [`AbiBreaking`](Severity::AbiBreaking) |
+//! | Union or tagged union changed in a way that changes its layout | [`AbiBreaking`](Severity::AbiBreaking) |
+//! | Enum variant removed or its value changed, enum repr changed | [`AbiBreaking`](Severity::AbiBreaking) |
+//! | Constant value or type changed | [`AbiBreaking`](Severity::AbiBreaking) |
+//!
+//! Constant changes are considered ABI-breaking since bindings contain a copy of the value which would
+//! then disagree with the library. Layouts are compared for the [`LP64`](crate::lang::c::DataModel::LP64)
+//! data model, changes inside services are reported via their functions.
+use crate::lang::c::{CType, DataModel, Field, FunctionSignature};
+use crate::patterns::api_guard::{type_name, value_name};
+use crate::patterns::LibraryPattern;
+use crate::util::{kind_name, named_types};
+use crate::Inventory;
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+
+/// How much a change affects consumers of existing bindings, ordered from least to most severe.
+#[derive(Copy, Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub enum Severity {
+    /// Existing bindings continue to work.
+    Compatible,
+    /// Existing bindings continue to work, but regenerated ones might break code using them.
+    SourceBreaking,
+    /// Existing bindings will misbehave and must be regenerated.
+    AbiBreaking,
+}
+
+impl Display for Severity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Severity::Compatible => write!(f, "compatible"),
+            Severity::SourceBreaking => write!(f, "source-breaking"),
+            Severity::AbiBreaking => write!(f, "abi-breaking"),
+        }
+    }
+}
+
+/// Whether an item was added, removed or changed.
+#[derive(Copy, Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Changed,
+}
+
+/// A single difference between two inventories.
+#[derive(Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub struct Change {
+    item: String,
+    kind: ChangeKind,
+    severity: Severity,
+    description: String,
+}
+
+impl Change {
+    fn new(item: &str, kind: ChangeKind, severity: Severity, description: String) -> Self {
+        Self {
+            item: item.to_string(),
+            kind,
+            severity,
+            description,
+        }
+    }
+
+    /// The affected item, e.g., `fn my_function` or `struct Vec3`.
+    pub fn item(&self) -> &str {
+        &self.item
+    }
+
+    pub fn kind(&self) -> ChangeKind {
+        self.kind
+    }
+
+    pub fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    /// Human readable explanation of what changed.
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+}
+
+impl Display for Change {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[{}] {}: {}", self.severity, self.item, self.description)
+    }
+}
+
+/// All differences between two inventories, produced by [`diff_inventories`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct InventoryDiff {
+    changes: Vec<Change>,
+}
+
+impl InventoryDiff {
+    /// All changes found, in no particular order.
+    pub fn changes(&self) -> &[Change] {
+        &self.changes
+    }
+
+    /// The most severe change, [`Severity::Compatible`] if nothing changed.
+    pub fn severity(&self) -> Severity {
+        self.changes.iter().map(|x| x.severity).max().unwrap_or(Severity::Compatible)
+    }
+
+    /// Returns `true` if both inventories were equivalent.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+impl Display for InventoryDiff {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for change in &self.changes {
+            writeln!(f, "{}", change)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Reports how `new` differs from `old`, see the [module documentation](self) for how changes are classified.
+pub fn diff_inventories(old: &Inventory, new: &Inventory) -> InventoryDiff {
+    let mut changes = Vec::new();
+
+    diff_functions(old, new, &mut changes);
+    diff_types(old, new, &mut changes);
+    diff_constants(old, new, &mut changes);
+    diff_patterns(old, new, &mut changes);
+
+    InventoryDiff { changes }
+}
+
+fn diff_functions(old: &Inventory, new: &Inventory, changes: &mut Vec<Change>) {
+    for o in old.functions() {
+        let item = format!("fn {}", o.name());
+
+        match new.functions().iter().find(|x| x.name() == o.name()) {
+            Some(n) => diff_signatures(&item, o.signature(), n.signature(), changes),
+            None => changes.push(Change::new(&item, ChangeKind::Removed, Severity::AbiBreaking, "function removed".to_string())),
+        }
+    }
+
+    for n in new.functions().iter().filter(|x| !old.functions().iter().any(|o| o.name() == x.name())) {
+        let item = format!("fn {}", n.name());
+        changes.push(Change::new(&item, ChangeKind::Added, Severity::Compatible, "function added".to_string()));
+    }
+}
+
+fn diff_signatures(item: &str, old: &FunctionSignature, new: &FunctionSignature, changes: &mut Vec<Change>) {
+    let len = old.params().len().max(new.params().len());
+
+    for i in 0..len {
+        match (old.params().get(i), new.params().get(i)) {
+            (Some(o), Some(n)) if type_name(o.the_type()) != type_name(n.the_type()) => {
+                let description = format!(
+                    "parameter `{}` changed type from `{}` to `{}`",
+                    n.name(),
+                    type_name(o.the_type()),
+                    type_name(n.the_type())
+                );
+                changes.push(Change::new(item, ChangeKind::Changed, Severity::AbiBreaking, description));
+            }
+            (Some(o), Some(n)) if o.name() != n.name() => {
+                let description = format!("parameter `{}` renamed to `{}`", o.name(), n.name());
+                changes.push(Change::new(item, ChangeKind::Changed, Severity::SourceBreaking, description));
+            }
+            (Some(o), None) => {
+                let description = format!("parameter `{}` removed", o.name());
+                changes.push(Change::new(item, ChangeKind::Changed, Severity::AbiBreaking, description));
+            }
+            (None, Some(n)) => {
+                let description = format!("parameter `{}` added", n.name());
+                changes.push(Change::new(item, ChangeKind::Changed, Severity::AbiBreaking, description));
+            }
+            _ => {}
+        }
+    }
+
+    if type_name(old.rval()) != type_name(new.rval()) {
+        let description = format!("return type changed from `{}` to `{}`", type_name(old.rval()), type_name(new.rval()));
+        changes.push(Change::new(item, ChangeKind::Changed, Severity::AbiBreaking, description));
+    }
+}
+
+/// Compares named types by name, if several types share a name only the last one is compared.
+fn diff_types(old: &Inventory, new: &Inventory, changes: &mut Vec<Change>) {
+    let old_types = named_types(old).into_iter().collect::<BTreeMap<_, _>>();
+    let new_types = named_types(new).into_iter().collect::<BTreeMap<_, _>>();
+
+    for (name, o) in &old_types {
+        let item = format!("{} {}", kind_name(o), name);
+
+        match new_types.get(name) {
+            Some(n) => diff_type(&item, o, n, changes),
+            None => changes.push(Change::new(&item, ChangeKind::Removed, Severity::SourceBreaking, "type removed".to_string())),
+        }
+    }
+
+    for (name, n) in new_types.iter().filter(|(name, _)| !old_types.contains_key(*name)) {
+        let item = format!("{} {}", kind_name(n), name);
+        changes.push(Change::new(&item, ChangeKind::Added, Severity::Compatible, "type added".to_string()));
+    }
+}
+
+fn diff_type(item: &str, old: &CType, new: &CType, changes: &mut Vec<Change>) {
+    let model = DataModel::LP64;
+
+    match (old, new) {
+        (CType::Composite(o), CType::Composite(n)) => diff_fields(item, o.fields(), n.fields(), Severity::AbiBreaking, changes),
+        (CType::Union(o), CType::Union(n)) => {
+            // Union fields all start at offset 0, so new fields only matter if they change the layout.
+            let severity = if o.layout(model) == n.layout(model) {
+                Severity::Compatible
+            } else {
+                Severity::AbiBreaking
+            };
+            diff_fields(item, o.fields(), n.fields(), severity, changes);
+        }
+        (CType::Enum(o), CType::Enum(n)) => {
+            if o.repr() != n.repr() {
+                let description = format!("repr changed from `{}` to `{}`", o.repr().rust_name(), n.repr().rust_name());
+                changes.push(Change::new(item, ChangeKind::Changed, Severity::AbiBreaking, description));
+            }
+
+            for ov in o.variants() {
+                match n.variants().iter().find(|x| x.name() == ov.name()) {
+                    Some(nv) if nv.value() != ov.value() => {
+                        let description = format!("variant `{}` changed value from {} to {}", ov.name(), ov.value(), nv.value());
+                        changes.push(Change::new(item, ChangeKind::Changed, Severity::AbiBreaking, description));
+                    }
+                    Some(_) => {}
+                    None => changes.push(Change::new(
+                        item,
+                        ChangeKind::Changed,
+                        Severity::AbiBreaking,
+                        format!("variant `{}` removed", ov.name()),
+                    )),
+                }
+            }
+
+            for nv in n.variants().iter().filter(|x| !o.variants().iter().any(|ov| ov.name() == x.name())) {
+                changes.push(Change::new(item, ChangeKind::Changed, Severity::Compatible, format!("variant `{}` added", nv.name())));
+            }
+        }
+        (CType::TaggedUnion(o), CType::TaggedUnion(n)) => {
+            if o.tag() != n.tag() || o.placement() != n.placement() {
+                changes.push(Change::new(
+                    item,
+                    ChangeKind::Changed,
+                    Severity::AbiBreaking,
+                    "tag type or placement changed".to_string(),
+                ));
+            }
+
+            let severity = if o.layout(model) == n.layout(model) {
+                Severity::Compatible
+            } else {
+                Severity::AbiBreaking
+            };
+
+            for ov in o.variants() {
+                match n.variants().iter().find(|x| x.name() == ov.name()) {
+                    Some(nv) if nv.value() != ov.value() => {
+                        let description = format!("variant `{}` changed value from {} to {}", ov.name(), ov.value(), nv.value());
+                        changes.push(Change::new(item, ChangeKind::Changed, Severity::AbiBreaking, description));
+                    }
+                    Some(nv) => diff_fields(&format!("{}::{}", item, ov.name()), ov.fields(), nv.fields(), Severity::AbiBreaking, changes),
+                    None => changes.push(Change::new(
+                        item,
+                        ChangeKind::Changed,
+                        Severity::AbiBreaking,
+                        format!("variant `{}` removed", ov.name()),
+                    )),
+                }
+            }
+
+            for nv in n.variants().iter().filter(|x| !o.variants().iter().any(|ov| ov.name() == x.name())) {
+                changes.push(Change::new(item, ChangeKind::Changed, severity, format!("variant `{}` added", nv.name())));
+            }
+        }
+        (CType::Opaque(_), CType::Opaque(_)) => {}
+        _ => {
+            let description = format!("changed from {} to {}", kind_name(old), kind_name(new));
+            changes.push(Change::new(item, ChangeKind::Changed, Severity::AbiBreaking, description));
+        }
+    }
+}
+
+/// Compares fields by name; `added` is the severity of a new field.
+fn diff_fields(item: &str, old: &[Field], new: &[Field], added: Severity, changes: &mut Vec<Change>) {
+    for o in old {
+        match new.iter().find(|x| x.name() == o.name()) {
+            Some(n) if type_name(o.the_type()) != type_name(n.the_type()) => {
+                let description = format!(
+                    "field `{}` changed type from `{}` to `{}`",
+                    o.name(),
+                    type_name(o.the_type()),
+                    type_name(n.the_type())
+                );
+                changes.push(Change::new(item, ChangeKind::Changed, Severity::AbiBreaking, description));
+            }
+            Some(n) if o.visibility() != n.visibility() => {
+                let description = format!("field `{}` changed visibility", o.name());
+                changes.push(Change::new(item, ChangeKind::Changed, Severity::SourceBreaking, description));
+            }
+            Some(_) => {}
+            None => changes.push(Change::new(item, ChangeKind::Changed, Severity::AbiBreaking, format!("field `{}` removed", o.name()))),
+        }
+    }
+
+    for n in new.iter().filter(|x| !old.iter().any(|o| o.name() == x.name())) {
+        changes.push(Change::new(item, ChangeKind::Changed, added, format!("field `{}` added", n.name())));
+    }
+
+    let old_order = old.iter().map(|x| x.name()).filter(|x| new.iter().any(|n| n.name() == *x));
+    let new_order = new.iter().map(|x| x.name()).filter(|x| old.iter().any(|o| o.name() == *x));
+
+    if added == Severity::AbiBreaking && !old_order.eq(new_order) {
+        changes.push(Change::new(item, ChangeKind::Changed, Severity::AbiBreaking, "fields reordered".to_string()));
+    }
+}
+
+fn diff_constants(old: &Inventory, new: &Inventory, changes: &mut Vec<Change>) {
+    for o in old.constants() {
+        let item = format!("const {}", o.name());
+
+        match new.constants().iter().find(|x| x.name() == o.name()) {
+            Some(n) if type_name(&o.the_type()) != type_name(&n.the_type()) => {
+                let description = format!("type changed from `{}` to `{}`", type_name(&o.the_type()), type_name(&n.the_type()));
+                changes.push(Change::new(&item, ChangeKind::Changed, Severity::AbiBreaking, description));
+            }
+            Some(n) if value_name(o.value()) != value_name(n.value()) => {
+                changes.push(Change::new(&item, ChangeKind::Changed, Severity::AbiBreaking, "value changed".to_string()));
+            }
+            Some(_) => {}
+            None => changes.push(Change::new(&item, ChangeKind::Removed, Severity::SourceBreaking, "constant removed".to_string())),
+        }
+    }
+
+    for n in new.constants().iter().filter(|x| !old.constants().iter().any(|o| o.name() == x.name())) {
+        let item = format!("const {}", n.name());
+        changes.push(Change::new(&item, ChangeKind::Added, Severity::Compatible, "constant added".to_string()));
+    }
+}
+
+fn service_names(inventory: &Inventory) -> Vec<&str> {
+    inventory
+        .patterns()
+        .iter()
+        .filter_map(|x| match x {
+            LibraryPattern::Service(x) => Some(x.the_type().rust_name()),
+            LibraryPattern::Vec(_) => None,
+            LibraryPattern::String(_) => None,
+            LibraryPattern::LastError(_) => None,
+        })
+        .collect()
+}
+
+fn diff_patterns(old: &Inventory, new: &Inventory, changes: &mut Vec<Change>) {
+    let old_services = service_names(old);
+    let new_services = service_names(new);
+
+    for o in old_services.iter().filter(|x| !new_services.contains(x)) {
+        changes.push(Change::new(
+            &format!("service {}", o),
+            ChangeKind::Removed,
+            Severity::SourceBreaking,
+            "service removed".to_string(),
+        ));
+    }
+
+    for n in new_services.iter().filter(|x| !old_services.contains(x)) {
+        changes.push(Change::new(
+            &format!("service {}", n),
+            ChangeKind::Added,
+            Severity::Compatible,
+            "service added".to_string(),
+        ));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{diff_inventories, ChangeKind, Severity};
+    use crate::lang::c::Documentation;
+    use crate::lang::c::{CType, EnumType, Meta, PrimitiveType, Variant};
+    use crate::util::fixtures::{composite, function, inventory};
+    use crate::Symbol;
+
+    fn enumeration(variants: &[(&str, i64)]) -> CType {
+        let variants = variants
+            .iter()
+            .map(|(name, value)| Variant::new(name.to_string(), *value, Documentation::new()))
+            .collect();
+        CType::Enum(EnumType::new("Status".to_string(), variants, Meta::new()))
+    }
+
+    #[test]
+    fn identical_inventories_have_no_changes() {
+        let old = inventory(vec![function(
+            "f",
+            &[("x", composite("Vec", &[("a", PrimitiveType::U32), ("b", PrimitiveType::U32)]))],
+        )]);
+        let new = inventory(vec![function(
+            "f",
+            &[("x", composite("Vec", &[("a", PrimitiveType::U32), ("b", PrimitiveType::U32)]))],
+        )]);
+
+        assert!(diff_inventories(&old, &new).is_empty());
+    }
+
+    #[test]
+    fn added_function_is_compatible() {
+        let old = inventory(vec![function("f", &[])]);
+        let new = inventory(vec![function("f", &[]), function("g", &[])]);
+        let diff = diff_inventories(&old, &new);
+
+        assert_eq!(diff.changes().len(), 1);
+        assert_eq!(diff.changes()[0].kind(), ChangeKind::Added);
+        assert_eq!(diff.severity(), Severity::Compatible);
+    }
+
+    #[test]
+    fn parameter_changes_are_classified() {
+        let old = inventory(vec![function("f", &[("x", CType::Primitive(PrimitiveType::U8))])]);
+        let renamed = inventory(vec![function("f", &[("y", CType::Primitive(PrimitiveType::U8))])]);
+        let added = inventory(vec![function(
+            "f",
+            &[("x", CType::Primitive(PrimitiveType::U8)), ("y", CType::Primitive(PrimitiveType::U8))],
+        )]);
+
+        assert_eq!(diff_inventories(&old, &renamed).severity(), Severity::SourceBreaking);
+        assert_eq!(diff_inventories(&old, &added).severity(), Severity::AbiBreaking);
+    }
+
+    #[test]
+    fn reordered_fields_break_abi() {
+        let old = inventory(vec![Symbol::Type(composite("Vec", &[("a", PrimitiveType::U32), ("b", PrimitiveType::U32)]))]);
+        let new = inventory(vec![Symbol::Type(composite("Vec", &[("b", PrimitiveType::U32), ("a", PrimitiveType::U32)]))]);
+        let diff = diff_inventories(&old, &new);
+
+        assert_eq!(diff.severity(), Severity::AbiBreaking);
+        assert_eq!(diff.changes()[0].description(), "fields reordered");
+    }
+
+    #[test]
+    fn enum_variants_are_classified() {
+        let old = inventory(vec![Symbol::Type(enumeration(&[("A", 0), ("B", 1)]))]);
+        let added = inventory(vec![Symbol::Type(enumeration(&[("A", 0), ("B", 1), ("C", 2)]))]);
+        let changed = inventory(vec![Symbol::Type(enumeration(&[("A", 0), ("B", 2)]))]);
+
+        assert_eq!(diff_inventories(&old, &added).severity(), Severity::Compatible);
+        assert_eq!(diff_inventories(&old, &changed).severity(), Severity::AbiBreaking);
+        assert_eq!(
+            diff_inventories(&old, &changed).to_string(),
+            "[abi-breaking] enum Status: variant `B` changed value from 1 to 2\n"
+        );
+    }
+}
@@ -0,0 +1,161 @@
+//! Embeds the inventory in your library, so bindings can be generated from the compiled `.dll` / `.so` alone.
+//!
+//! Usually bindings are produced by a Rust test linking against your library crate. That doesn't help if all you
+//! have is a released binary, or if you want to check that a shipped library and shipped bindings really match.
+//! With [`embed_inventory`](crate::embed_inventory) your library exports one additional symbol,
+//! [`INVENTORY_SYMBOL`], returning its serialized inventory, which [`inventory_from_library`] can read back.
+//!
+//! # Example
+//!
+//! In your library:
+//!
+//! ```
+//! use interoptopus::{embed_inventory, ffi_function, function, Inventory, InventoryBuilder};
+//!
+//! #[ffi_function]
+//! #[no_mangle]
+//! pub extern "C" fn my_function() {}
+//!
+//! pub fn my_inventory() -> Inventory {
+//!     InventoryBuilder::new().register(function!(my_function)).inventory()
+//! }
+//!
+//! // Exports `interoptopus_inventory_json` from the compiled library.
+//! embed_inventory!(my_inventory);
+//! ```
+//!
+//! Anywhere else, with the `loader` feature enabled:
+//!
+//! ```no_run
+//! # #[cfg(feature = "loader")]
+//! # fn f() -> Result<(), interoptopus::Error> {
+//! use interoptopus::diff::diff_inventories;
+//! use interoptopus::embed::inventory_from_library;
+//!
+//! let shipped = inventory_from_library("libmy_library.so")?;
+//! let expected = interoptopus::Inventory::from_json(&std::fs::read_to_string("inventory.json")?)?;
+//!
+//! assert!(diff_inventories(&expected, &shipped).is_empty());
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! The `interoptopus` CLI can also generate bindings straight from a library via `--library`.
+//!
+//! # ABI
+//!
+//! The exported symbol has the C signature `uint64_t interoptopus_inventory_json(uint8_t* buffer, uint64_t capacity)`.
+//! It returns the length of the UTF-8 encoded [`Inventory::to_json`] document (without trailing `NUL`), and if
+//! `capacity` is at least that length also copies the document into `buffer`. Callers therefore query the length
+//! with a null `buffer` first. A return value of `0` means the inventory could not be serialized.
+//!
+//! The symbol is not part of the inventory itself, so it neither shows up in bindings nor changes the API hash.
+
+#[cfg(feature = "loader")]
+use crate::Error;
+use crate::Inventory;
+
+/// Name of the symbol exported by [`embed_inventory`](crate::embed_inventory).
+pub const INVENTORY_SYMBOL: &str = "interoptopus_inventory_json";
+
+/// Exports the inventory returned by the given function from the compiled library.
+///
+/// This defines a `#[no_mangle]` function named [`INVENTORY_SYMBOL`](crate::embed::INVENTORY_SYMBOL), see the
+/// [`embed`](crate::embed) module for details. It can be invoked at most once per library.
+#[macro_export]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+macro_rules! embed_inventory {
+    ($inventory:path) => {
+        /// Copies the serialized inventory of this library into `buffer`, returns its length.
+        ///
+        /// # Safety
+        ///
+        /// `buffer` must be null or valid for `capacity` bytes.
+        #[no_mangle]
+        pub unsafe extern "C" fn interoptopus_inventory_json(buffer: *mut u8, capacity: u64) -> u64 {
+            $crate::embed::write_inventory(&$inventory(), buffer, capacity)
+        }
+    };
+}
+
+/// Implementation of the symbol defined by [`embed_inventory`](crate::embed_inventory).
+///
+/// # Safety
+///
+/// `buffer` must be null or valid for `capacity` bytes.
+#[doc(hidden)]
+pub unsafe fn write_inventory(inventory: &Inventory, buffer: *mut u8, capacity: u64) -> u64 {
+    let json = match inventory.to_json() {
+        Ok(json) => json,
+        Err(_) => return 0,
+    };
+
+    let len = json.len() as u64;
+
+    if !buffer.is_null() && capacity >= len {
+        std::ptr::copy_nonoverlapping(json.as_ptr(), buffer, json.len());
+    }
+
+    len
+}
+
+/// Loads the library at `path` and reads the inventory it embedded via [`embed_inventory`](crate::embed_inventory).
+///
+/// # Safety Considerations
+///
+/// Loading a library runs its initialization routines, only call this for libraries you trust.
+#[cfg(feature = "loader")]
+#[cfg_attr(docsrs, doc(cfg(feature = "loader")))]
+pub fn inventory_from_library<P: AsRef<std::ffi::OsStr>>(path: P) -> Result<Inventory, Error> {
+    type InventoryFn = unsafe extern "C" fn(*mut u8, u64) -> u64;
+
+    // SAFETY: We trust the caller to only hand us libraries which are safe to load, and the symbol, if present, to
+    // have been defined by `embed_inventory`.
+    unsafe {
+        let library = libloading::Library::new(path)?;
+        let inventory_json: libloading::Symbol<InventoryFn> = library.get(INVENTORY_SYMBOL.as_bytes())?;
+
+        let len = inventory_json(std::ptr::null_mut(), 0);
+
+        if len == 0 {
+            let message = "the library failed to serialize its inventory";
+            return Err(Error::EmbeddedInventory(message.to_string()));
+        }
+
+        let mut buffer = vec![0u8; len as usize];
+
+        let copied = inventory_json(buffer.as_mut_ptr(), len);
+
+        if copied != len {
+            let message = format!("the inventory changed length between calls, from {} to {} bytes", len, copied);
+            return Err(Error::EmbeddedInventory(message));
+        }
+
+        Inventory::from_json(std::str::from_utf8(&buffer)?)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::embed::write_inventory;
+    use crate::{Error, InventoryBuilder};
+
+    #[test]
+    fn write_inventory_reports_length_and_copies() -> Result<(), Error> {
+        let inventory = InventoryBuilder::new().api_version(1, 2).inventory();
+        let json = inventory.to_json()?;
+
+        let len = unsafe { write_inventory(&inventory, std::ptr::null_mut(), 0) };
+        assert_eq!(len, json.len() as u64);
+
+        let mut small = vec![0u8; json.len() - 1];
+        unsafe { write_inventory(&inventory, small.as_mut_ptr(), small.len() as u64) };
+        assert!(small.iter().all(|x| *x == 0));
+
+        let mut buffer = vec![0u8; json.len()];
+        unsafe { write_inventory(&inventory, buffer.as_mut_ptr(), buffer.len() as u64) };
+        assert_eq!(buffer, json.as_bytes());
+
+        Ok(())
+    }
+}
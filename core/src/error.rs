@@ -1,6 +1,13 @@
+use crate::validate::Validation;
 use std::fmt::{Display, Formatter};
+use std::path::PathBuf;
 
 /// Can be observed if something goes wrong.
+///
+/// Errors raised while generating bindings are wrapped in [`Error::Context`], naming the file, backend and item
+/// being written at the time. Like all variants wrapping another error, their [`Display`] only describes themselves,
+/// while the underlying cause is returned by [`source()`](std::error::Error::source). Reporters walking that chain
+/// print, e.g., `writing bindings/my_library.h: C backend: fn my_function: formatting failed: ...`.
 #[derive(Debug)]
 pub enum Error {
     /// A null pointer was observed where it wasn't expected.
@@ -24,14 +31,84 @@ pub enum Error {
     /// Not valid UTF-8
     FromUtf8(std::string::FromUtf8Error),
 
-    /// A command to test was not found.
-    CommandNotFound,
+    /// A command to test was not found, contains the command.
+    CommandNotFound(String),
 
     /// A test failed to execute.
-    TestFailed,
+    TestFailed {
+        /// The command that failed, e.g., `python app.py`.
+        command: String,
+        /// What the command reported, usually its captured `stderr`.
+        output: String,
+    },
 
     /// A specified file was not found.
     FileNotFound,
+
+    /// A library could not be parsed.
+    InvalidLibrary,
+
+    /// The inventory can't be turned into working bindings, contains the problems found by validation.
+    Invalid(Validation),
+
+    /// (De)serializing an inventory failed.
+    #[cfg(feature = "serde")]
+    Serde(serde_json::Error),
+
+    /// Loading a library or one of its symbols failed.
+    #[cfg(feature = "loader")]
+    Library(libloading::Error),
+
+    /// A library exports an embedded inventory, but reading it failed, contains the reason.
+    #[cfg(feature = "loader")]
+    EmbeddedInventory(String),
+
+    /// Another error happened while working on the given file, backend or item.
+    Context { context: ErrorContext, source: Box<Error> },
+}
+
+/// What was being worked on when an [`Error`] happened.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ErrorContext {
+    /// A file being written.
+    File(PathBuf),
+    /// A backend producing bindings, e.g., `C#`.
+    Backend(String),
+    /// An item being written, e.g., `fn my_function` or `struct Vec3`.
+    Item(String),
+}
+
+impl Error {
+    /// Wraps this error, recording what was being worked on.
+    pub fn with_context(self, context: ErrorContext) -> Self {
+        Self::Context { context, source: Box::new(self) }
+    }
+
+    /// Wraps this error, recording the item (e.g., `fn my_function`) being written.
+    pub fn with_item(self, item: impl Into<String>) -> Self {
+        self.with_context(ErrorContext::Item(item.into()))
+    }
+
+    /// Returns all contexts of this error, outermost first.
+    pub fn contexts(&self) -> Vec<&ErrorContext> {
+        let mut rval = Vec::new();
+        let mut error = self;
+
+        while let Self::Context { context, source } = error {
+            rval.push(context);
+            error = source;
+        }
+
+        rval
+    }
+
+    /// The error that caused this one, without any [`ErrorContext`].
+    pub fn root_cause(&self) -> &Self {
+        match self {
+            Self::Context { source, .. } => source.root_cause(),
+            x => x,
+        }
+    }
 }
 
 impl From<std::fmt::Error> for Error {
@@ -58,12 +135,116 @@ impl From<std::string::FromUtf8Error> for Error {
     }
 }
 
+#[cfg(feature = "serde")]
+impl From<serde_json::Error> for Error {
+    fn from(e: serde_json::Error) -> Self {
+        Self::Serde(e)
+    }
+}
+
+#[cfg(feature = "loader")]
+impl From<libloading::Error> for Error {
+    fn from(e: libloading::Error) -> Self {
+        Self::Library(e)
+    }
+}
+
+impl Display for ErrorContext {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ErrorContext::File(x) => write!(f, "writing {}", x.display()),
+            ErrorContext::Backend(x) => write!(f, "{} backend", x),
+            ErrorContext::Item(x) => write!(f, "{}", x),
+        }
+    }
+}
+
 impl Display for Error {
-    // TODO: This should be nicer.
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "Interoptopus failed!")
+        match self {
+            Error::Null => write!(f, "unexpected null pointer"),
+            Error::Unsupported => write!(f, "unsupported operation"),
+            Error::Ascii => write!(f, "string is not valid ASCII"),
+            Error::Format(_) => write!(f, "formatting failed"),
+            Error::IO(_) => write!(f, "I/O error"),
+            Error::UTF8(_) => write!(f, "invalid UTF-8"),
+            Error::FromUtf8(_) => write!(f, "invalid UTF-8"),
+            Error::CommandNotFound(x) => write!(f, "command `{}` not found", x),
+            Error::TestFailed { command, output } if output.trim().is_empty() => write!(f, "`{}` failed", command),
+            Error::TestFailed { command, output } => write!(f, "`{}` failed:\n{}", command, output.trim_end()),
+            Error::FileNotFound => write!(f, "file not found"),
+            Error::InvalidLibrary => write!(f, "not a valid library"),
+            Error::Invalid(x) => write!(f, "inventory is invalid:\n{}", x.to_string().trim_end()),
+            #[cfg(feature = "serde")]
+            Error::Serde(_) => write!(f, "invalid inventory"),
+            #[cfg(feature = "loader")]
+            Error::Library(_) => write!(f, "cannot load library"),
+            #[cfg(feature = "loader")]
+            Error::EmbeddedInventory(x) => write!(f, "cannot read embedded inventory: {}", x),
+            Error::Context { context, .. } => write!(f, "{}", context),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Format(x) => Some(x),
+            Error::IO(x) => Some(x),
+            Error::UTF8(x) => Some(x),
+            Error::FromUtf8(x) => Some(x),
+            #[cfg(feature = "serde")]
+            Error::Serde(x) => Some(x),
+            #[cfg(feature = "loader")]
+            Error::Library(x) => Some(x),
+            Error::Context { source, .. } => Some(source.as_ref()),
+            _ => None,
+        }
     }
 }
 
-// TODO
-impl std::error::Error for Error {}
+#[cfg(test)]
+mod test {
+    use crate::error::ErrorContext;
+    use crate::Error;
+    use std::error::Error as _;
+
+    #[test]
+    fn context_is_displayed_outermost_first() {
+        let error = Error::Format(std::fmt::Error)
+            .with_item("fn my_function")
+            .with_context(ErrorContext::Backend("C".to_string()))
+            .with_context(ErrorContext::File("my_library.h".into()));
+
+        let mut chain = vec![error.to_string()];
+        let mut source = error.source();
+
+        while let Some(x) = source {
+            chain.push(x.to_string());
+            source = x.source();
+        }
+
+        assert_eq!(chain[..4].join(": "), "writing my_library.h: C backend: fn my_function: formatting failed");
+        assert_eq!(chain.len(), 5);
+        assert_eq!(error.contexts().len(), 3);
+        assert!(matches!(error.root_cause(), Error::Format(_)));
+    }
+
+    #[test]
+    fn failed_tests_show_their_output() {
+        let error = Error::TestFailed {
+            command: "python app.py".to_string(),
+            output: "Traceback\n".to_string(),
+        };
+
+        assert_eq!(error.to_string(), "`python app.py` failed:\nTraceback");
+    }
+
+    #[test]
+    #[cfg(feature = "loader")]
+    fn embedded_inventory_errors_show_their_reason() {
+        let error = Error::EmbeddedInventory("the library failed to serialize its inventory".to_string());
+
+        assert_eq!(error.to_string(), "cannot read embedded inventory: the library failed to serialize its inventory");
+    }
+}
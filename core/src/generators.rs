@@ -1,5 +1,6 @@
+use crate::validate::Validation;
 use crate::writer::IndentWriter;
-use crate::Error;
+use crate::{Error, ErrorContext};
 use std::fs::File;
 use std::path::Path;
 
@@ -8,13 +9,27 @@ use std::path::Path;
 /// This trait will be implemented by each backend and is the main way to interface with a generator.
 pub trait Interop {
     /// Generates FFI binding code and writes them to the [`IndentWriter`].
+    ///
+    /// Backends run [`validate`](Self::validate) first and return [`Error::Invalid`] instead of writing bindings
+    /// that wouldn't work, see [`Validation::into_result`].
     fn write_to(&self, w: &mut IndentWriter) -> Result<(), Error>;
 
     /// Convenience method to write FFI bindings to the specified file with default indentation.
+    ///
+    /// Errors name the file in their [`ErrorContext`].
     fn write_file<P: AsRef<Path>>(&self, file_name: P) -> Result<(), Error> {
-        let mut file = File::create(file_name)?;
+        let context = || ErrorContext::File(file_name.as_ref().to_path_buf());
+        let mut file = File::create(file_name.as_ref()).map_err(|e| Error::from(e).with_context(context()))?;
         let mut writer = IndentWriter::new(&mut file);
 
-        self.write_to(&mut writer)
+        self.write_to(&mut writer).map_err(|e| e.with_context(context()))
+    }
+
+    /// Checks whether working bindings can be generated, see [`validate`](crate::validate).
+    ///
+    /// Backends should return [`Inventory::validate`](crate::Inventory::validate) of their inventory, extended by
+    /// rules specific to their language and configuration. The default reports no problems.
+    fn validate(&self) -> Validation {
+        Validation::default()
     }
 }
@@ -11,8 +11,10 @@
 
 use crate::patterns::TypePattern;
 use crate::util::{ctypes_from_type_recursive, IdPrettifier};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
-use std::hash::{Hash, Hasher};
+use std::hash::Hash;
 
 // /// If a name like `abc::XXX` is given, strips the `abc::` part.
 // fn strip_rust_path_prefix(name_with_path: &str) -> String {
@@ -22,6 +24,8 @@ use std::hash::{Hash, Hasher};
 
 /// A primitive value expressible on C-level.
 #[derive(Clone, Debug, PartialOrd, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
 pub enum PrimitiveValue {
     Bool(bool),
     U8(u8),
@@ -32,38 +36,61 @@ pub enum PrimitiveValue {
     I16(i16),
     I32(i32),
     I64(i64),
-    F32(f32),
-    F64(f64),
+    U128(#[cfg_attr(feature = "serde", serde(with = "lossless::int128"))] u128),
+    I128(#[cfg_attr(feature = "serde", serde(with = "lossless::int128"))] i128),
+    USize(usize),
+    ISize(isize),
+    Char(char),
+    F32(#[cfg_attr(feature = "serde", serde(with = "lossless::float"))] f32),
+    F64(#[cfg_attr(feature = "serde", serde(with = "lossless::float"))] f64),
 }
 
 /// The value of a constant.
 #[derive(Clone, Debug, PartialOrd, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum ConstantValue {
     Primitive(PrimitiveValue),
+    /// A string literal, from a Rust `&str`.
+    String(String),
+    /// The values of all elements of a fixed-size array.
+    Array(ArrayType, Vec<ConstantValue>),
+    /// The values of all fields of a struct, in the order of [`CompositeType::fields`].
+    Composite(CompositeType, Vec<ConstantValue>),
 }
 
 impl ConstantValue {
-    pub(crate) fn fucking_hash_it_already<H: Hasher>(&self, h: &mut H) {
+    /// Returns the type of this value.
+    pub fn the_type(&self) -> CType {
         match self {
-            ConstantValue::Primitive(x) => match x {
-                PrimitiveValue::Bool(x) => x.hash(h),
-                PrimitiveValue::U8(x) => x.hash(h),
-                PrimitiveValue::U16(x) => x.hash(h),
-                PrimitiveValue::U32(x) => x.hash(h),
-                PrimitiveValue::U64(x) => x.hash(h),
-                PrimitiveValue::I8(x) => x.hash(h),
-                PrimitiveValue::I16(x) => x.hash(h),
-                PrimitiveValue::I32(x) => x.hash(h),
-                PrimitiveValue::I64(x) => x.hash(h),
-                PrimitiveValue::F32(x) => x.to_le_bytes().hash(h),
-                PrimitiveValue::F64(x) => x.to_le_bytes().hash(h),
-            },
+            ConstantValue::String(_) => CType::Pattern(TypePattern::AsciiPointer),
+            ConstantValue::Array(x, _) => CType::Array(x.clone()),
+            ConstantValue::Composite(x, _) => CType::Composite(x.clone()),
+            ConstantValue::Primitive(x) => CType::Primitive(match x {
+                PrimitiveValue::Bool(_) => PrimitiveType::Bool,
+                PrimitiveValue::U8(_) => PrimitiveType::U8,
+                PrimitiveValue::U16(_) => PrimitiveType::U16,
+                PrimitiveValue::U32(_) => PrimitiveType::U32,
+                PrimitiveValue::U64(_) => PrimitiveType::U64,
+                PrimitiveValue::I8(_) => PrimitiveType::I8,
+                PrimitiveValue::I16(_) => PrimitiveType::I16,
+                PrimitiveValue::I32(_) => PrimitiveType::I32,
+                PrimitiveValue::I64(_) => PrimitiveType::I64,
+                PrimitiveValue::U128(_) => PrimitiveType::U128,
+                PrimitiveValue::I128(_) => PrimitiveType::I128,
+                PrimitiveValue::USize(_) => PrimitiveType::USize,
+                PrimitiveValue::ISize(_) => PrimitiveType::ISize,
+                PrimitiveValue::Char(_) => PrimitiveType::Char,
+                PrimitiveValue::F32(_) => PrimitiveType::F32,
+                PrimitiveValue::F64(_) => PrimitiveType::F64,
+            }),
         }
     }
 }
 
 /// A Rust `const` definition with a name and value, might become a `#define`.
 #[derive(Clone, Debug, PartialOrd, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct Constant {
     name: String,
     value: ConstantValue,
@@ -89,32 +116,22 @@ impl Constant {
 
     /// Returns the type of this constant.
     pub fn the_type(&self) -> CType {
-        match &self.value {
-            ConstantValue::Primitive(x) => CType::Primitive(match x {
-                PrimitiveValue::Bool(_) => PrimitiveType::Bool,
-                PrimitiveValue::U8(_) => PrimitiveType::U8,
-                PrimitiveValue::U16(_) => PrimitiveType::U16,
-                PrimitiveValue::U32(_) => PrimitiveType::U32,
-                PrimitiveValue::U64(_) => PrimitiveType::U64,
-                PrimitiveValue::I8(_) => PrimitiveType::I8,
-                PrimitiveValue::I16(_) => PrimitiveType::I16,
-                PrimitiveValue::I32(_) => PrimitiveType::I32,
-                PrimitiveValue::I64(_) => PrimitiveType::I64,
-                PrimitiveValue::F32(_) => PrimitiveType::F32,
-                PrimitiveValue::F64(_) => PrimitiveType::F64,
-            }),
-        }
+        self.value.the_type()
     }
 }
 
 /// A type that can exist at the FFI boundary.
 #[derive(Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum CType {
     Primitive(PrimitiveType),
     Array(ArrayType),
     Enum(EnumType),
     Opaque(OpaqueType),
     Composite(CompositeType),
+    Union(UnionType),
+    TaggedUnion(TaggedUnionType),
     FnPointer(FnPointerType),
     ReadPointer(Box<CType>),
     ReadWritePointer(Box<CType>),
@@ -130,29 +147,35 @@ impl Default for CType {
 }
 
 impl CType {
-    pub fn size_of(&self) -> usize {
+    /// Computes the C layout of this type for the given data model.
+    ///
+    /// Types without a known layout (`void`, opaques) have size 0 and alignment 1; patterns
+    /// use the layout of their [`fallback_type`](TypePattern::fallback_type).
+    pub fn layout(&self, model: DataModel) -> Layout {
         match self {
-            CType::Primitive(p) => match p {
-                PrimitiveType::Void => 0,
-                PrimitiveType::Bool => 1,
-                PrimitiveType::U8 => 1,
-                PrimitiveType::U16 => 2,
-                PrimitiveType::U32 => 4,
-                PrimitiveType::U64 => 8,
-                PrimitiveType::I8 => 1,
-                PrimitiveType::I16 => 2,
-                PrimitiveType::I32 => 4,
-                PrimitiveType::I64 => 8,
-                PrimitiveType::F32 => 4,
-                PrimitiveType::F64 => 8,
-            },
-            // TODO
-            _ => 999,
+            CType::Primitive(x) => x.layout(model),
+            CType::Array(x) => x.layout(model),
+            // Enums use their `#[repr()]` integer, which is an `int` (`i32`) without an explicit one.
+            CType::Enum(x) => x.repr().layout(model),
+            CType::Opaque(_) => Layout::new(0, 1),
+            CType::Composite(x) => x.layout(model),
+            CType::Union(x) => x.layout(model),
+            CType::TaggedUnion(x) => x.layout(model),
+            CType::FnPointer(_) => Layout::new(model.pointer_size(), model.pointer_size()),
+            CType::ReadPointer(_) => Layout::new(model.pointer_size(), model.pointer_size()),
+            CType::ReadWritePointer(_) => Layout::new(model.pointer_size(), model.pointer_size()),
+            CType::Pattern(x) => x.fallback_type().layout(model),
         }
     }
 
+    /// Size of this type in bytes on the host's data model.
+    pub fn size_of(&self) -> usize {
+        self.layout(DataModel::host()).size()
+    }
+
+    /// Alignment of this type in bytes on the host's data model.
     pub fn align_of(&self) -> usize {
-        unimplemented!()
+        self.layout(DataModel::host()).align()
     }
 
     pub const fn void() -> Self {
@@ -173,6 +196,8 @@ impl CType {
             CType::Enum(x) => x.rust_name().to_string(),
             CType::Opaque(x) => x.rust_name().to_string(),
             CType::Composite(x) => x.rust_name().to_string(),
+            CType::Union(x) => x.rust_name().to_string(),
+            CType::TaggedUnion(x) => x.rust_name().to_string(),
             CType::FnPointer(x) => x.internal_name(),
             CType::ReadPointer(x) => format!("*const {}", x.name_within_lib()),
             CType::ReadWritePointer(x) => format!("*mut {}", x.name_within_lib()),
@@ -201,6 +226,8 @@ impl CType {
             CType::Enum(_) => None,
             CType::Opaque(_) => None,
             CType::Composite(_) => None,
+            CType::Union(_) => None,
+            CType::TaggedUnion(_) => None,
             CType::FnPointer(_) => None,
             CType::ReadPointer(x) => Some(x.as_ref()),
             CType::ReadWritePointer(x) => Some(x.as_ref()),
@@ -237,6 +264,8 @@ impl CType {
             CType::Enum(t) => Some(t.meta.namespace()),
             CType::Opaque(t) => Some(t.meta.namespace()),
             CType::Composite(t) => Some(t.meta.namespace()),
+            CType::Union(t) => Some(t.meta.namespace()),
+            CType::TaggedUnion(t) => Some(t.meta.namespace()),
             _ => None,
         }
     }
@@ -244,6 +273,8 @@ impl CType {
 
 /// A primitive type that natively exists in C and is FFI safe.
 #[derive(Copy, Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
 pub enum PrimitiveType {
     Void,
     Bool,
@@ -255,6 +286,12 @@ pub enum PrimitiveType {
     I16,
     I32,
     I64,
+    U128,
+    I128,
+    USize,
+    ISize,
+    /// A Rust `char`, which is a 32-bit Unicode scalar value.
+    Char,
     F32,
     F64,
 }
@@ -272,14 +309,48 @@ impl PrimitiveType {
             PrimitiveType::I16 => "i16",
             PrimitiveType::I32 => "i32",
             PrimitiveType::I64 => "i64",
+            PrimitiveType::U128 => "u128",
+            PrimitiveType::I128 => "i128",
+            PrimitiveType::USize => "usize",
+            PrimitiveType::ISize => "isize",
+            PrimitiveType::Char => "char",
             PrimitiveType::F32 => "f32",
             PrimitiveType::F64 => "f64",
         }
     }
+
+    /// Layout of this primitive; pointer-sized integers and the alignment of 8 byte scalars depend on the data model.
+    pub fn layout(&self, model: DataModel) -> Layout {
+        let size = match self {
+            PrimitiveType::Void => return Layout::new(0, 1),
+            PrimitiveType::Bool => 1,
+            PrimitiveType::U8 => 1,
+            PrimitiveType::U16 => 2,
+            PrimitiveType::U32 => 4,
+            PrimitiveType::U64 => 8,
+            PrimitiveType::I8 => 1,
+            PrimitiveType::I16 => 2,
+            PrimitiveType::I32 => 4,
+            PrimitiveType::I64 => 8,
+            PrimitiveType::U128 => 16,
+            PrimitiveType::I128 => 16,
+            PrimitiveType::USize => model.pointer_size(),
+            PrimitiveType::ISize => model.pointer_size(),
+            PrimitiveType::Char => 4,
+            PrimitiveType::F32 => 4,
+            PrimitiveType::F64 => 8,
+        };
+
+        match size {
+            8 => Layout::new(size, model.scalar8_align()),
+            _ => Layout::new(size, size),
+        }
+    }
 }
 
 /// A (C-style) `type[N]` containing a fixed number of elements of the same type.
 #[derive(Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct ArrayType {
     array_type: Box<CType>,
     len: usize,
@@ -308,19 +379,35 @@ impl ArrayType {
     pub fn is_empty(&self) -> bool {
         self.len == 0
     }
+
+    /// Computes the layout of `len` consecutive elements.
+    pub fn layout(&self, model: DataModel) -> Layout {
+        let element = self.array_type.layout(model);
+        Layout::new(element.size() * self.len, element.align())
+    }
 }
 
 /// A (C-style) `enum` containing numbered variants.
+///
+/// The underlying integer type is given by [`EnumType::repr`], e.g., [`PrimitiveType::U8`] for a `#[repr(u8)]` enum.
 #[derive(Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct EnumType {
     name: String,
     variants: Vec<Variant>,
+    repr: PrimitiveType,
     meta: Meta,
 }
 
 impl EnumType {
+    /// Creates a new enum backed by a C `int`, which is what `#[repr(C)]` produces.
     pub fn new(name: String, variants: Vec<Variant>, meta: Meta) -> Self {
-        Self { name, variants, meta }
+        Self::with_repr(name, variants, PrimitiveType::I32, meta)
+    }
+
+    /// Creates a new enum with the given underlying integer type.
+    pub fn with_repr(name: String, variants: Vec<Variant>, repr: PrimitiveType, meta: Meta) -> Self {
+        Self { name, variants, repr, meta }
     }
 
     pub fn rust_name(&self) -> &str {
@@ -335,6 +422,11 @@ impl EnumType {
         self.variants.iter().find(|x| x.name == name).cloned()
     }
 
+    /// The integer type holding the discriminant.
+    pub fn repr(&self) -> PrimitiveType {
+        self.repr
+    }
+
     pub fn meta(&self) -> &Meta {
         &self.meta
     }
@@ -342,14 +434,15 @@ impl EnumType {
 
 /// Variant and value of a [`EnumType`].
 #[derive(Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct Variant {
     name: String,
-    value: usize,
+    value: i64,
     documentation: Documentation,
 }
 
 impl Variant {
-    pub fn new(name: String, value: usize, documentation: Documentation) -> Self {
+    pub fn new(name: String, value: i64, documentation: Documentation) -> Self {
         Self { name, value, documentation }
     }
 
@@ -357,7 +450,7 @@ impl Variant {
         &self.name
     }
 
-    pub fn value(&self) -> usize {
+    pub fn value(&self) -> i64 {
         self.value
     }
 
@@ -381,6 +474,7 @@ impl Variant {
 /// } MyComposite;
 /// ```
 #[derive(Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct CompositeType {
     name: String,
     fields: Vec<Field>,
@@ -415,10 +509,260 @@ impl CompositeType {
     pub fn meta(&self) -> &Meta {
         &self.meta
     }
+
+    /// Computes the `#[repr(C)]` layout of this composite.
+    pub fn layout(&self, model: DataModel) -> Layout {
+        let mut size = 0;
+        let mut align = 1;
+
+        for field in &self.fields {
+            let field_layout = field.the_type().layout(model);
+            size = align_up(size, field_layout.align()) + field_layout.size();
+            align = align.max(field_layout.align());
+        }
+
+        Layout::new(align_up(size, align), align)
+    }
+
+    /// Computes the byte offset of each field, in declaration order.
+    pub fn field_offsets(&self, model: DataModel) -> Vec<usize> {
+        let mut offset = 0;
+        let mut offsets = Vec::with_capacity(self.fields.len());
+
+        for field in &self.fields {
+            let field_layout = field.the_type().layout(model);
+            offset = align_up(offset, field_layout.align());
+            offsets.push(offset);
+            offset += field_layout.size();
+        }
+
+        offsets
+    }
+}
+
+/// Used for Rust and C `union` with named fields, must be `#[repr(C)]`.
+///
+/// All fields share the same storage at offset 0, equivalent on C-level to:
+///
+/// ```ignore
+/// typedef union MyUnion
+/// {
+///     float    as_float;
+///     uint32_t as_bits;
+/// } MyUnion;
+/// ```
+#[derive(Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct UnionType {
+    name: String,
+    fields: Vec<Field>,
+    meta: Meta,
+}
+
+impl UnionType {
+    /// Creates a new union with the given name and fields and no documentation.
+    pub fn new(name: String, fields: Vec<Field>) -> Self {
+        Self::with_meta(name, fields, Meta::new())
+    }
+
+    /// Creates a new union with the given name and type-level documentation.
+    pub fn with_meta(name: String, fields: Vec<Field>, meta: Meta) -> Self {
+        Self { name, fields, meta }
+    }
+
+    pub fn rust_name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn fields(&self) -> &[Field] {
+        &self.fields
+    }
+
+    pub fn meta(&self) -> &Meta {
+        &self.meta
+    }
+
+    /// Computes the layout of this union, which is as large as its largest field.
+    pub fn layout(&self, model: DataModel) -> Layout {
+        let mut size = 0;
+        let mut align = 1;
+
+        for field in &self.fields {
+            let field_layout = field.the_type().layout(model);
+            size = size.max(field_layout.size());
+            align = align.max(field_layout.align());
+        }
+
+        Layout::new(align_up(size, align), align)
+    }
+}
+
+/// Where the tag of a [`TaggedUnionType`] is stored, mirroring Rust's layout rules for data-carrying enums.
+#[derive(Copy, Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum TagPlacement {
+    /// Produced by `#[repr(C, u8)]`, a struct of the tag followed by a union of all variant payloads.
+    Separate,
+    /// Produced by `#[repr(u8)]`, a union of all variant payloads, each of which starts with the tag.
+    Inline,
+}
+
+/// A Rust `enum` with data-carrying variants, e.g., `#[repr(C, u8)] enum Shape { Circle(f32), Empty }`.
+///
+/// On C-level this becomes a tag enum and one struct per variant, equivalent to:
+///
+/// ```ignore
+/// typedef enum ShapeTag { SHAPETAG_CIRCLE = 0, SHAPETAG_EMPTY = 1 } ShapeTag;
+/// typedef struct ShapeCircle { float x0; } ShapeCircle;
+/// typedef union ShapePayload { ShapeCircle Circle; } ShapePayload;
+/// typedef struct Shape { uint8_t tag; ShapePayload payload; } Shape;
+/// ```
+#[derive(Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct TaggedUnionType {
+    name: String,
+    tag: PrimitiveType,
+    placement: TagPlacement,
+    variants: Vec<TaggedVariant>,
+    meta: Meta,
+}
+
+impl TaggedUnionType {
+    pub fn new(name: String, tag: PrimitiveType, placement: TagPlacement, variants: Vec<TaggedVariant>, meta: Meta) -> Self {
+        Self {
+            name,
+            tag,
+            placement,
+            variants,
+            meta,
+        }
+    }
+
+    pub fn rust_name(&self) -> &str {
+        &self.name
+    }
+
+    /// The primitive the tag is stored as, e.g., `u8` for `#[repr(C, u8)]`.
+    pub fn tag(&self) -> PrimitiveType {
+        self.tag
+    }
+
+    pub fn placement(&self) -> TagPlacement {
+        self.placement
+    }
+
+    pub fn variants(&self) -> &[TaggedVariant] {
+        &self.variants
+    }
+
+    pub fn meta(&self) -> &Meta {
+        &self.meta
+    }
+
+    /// The enum of all variant discriminants, named `{Name}Tag`.
+    pub fn tag_enum(&self) -> EnumType {
+        let variants = self.variants.iter().map(|x| Variant::new(x.name.clone(), x.value, x.documentation.clone())).collect();
+
+        EnumType::with_repr(
+            format!("{}Tag", self.name),
+            variants,
+            self.tag,
+            Meta::with_namespace_documentation(self.meta.namespace.clone(), Documentation::new()),
+        )
+    }
+
+    /// Variants carrying data, in declaration order; fieldless variants are represented by their tag alone.
+    pub fn payload_variants(&self) -> impl Iterator<Item = &TaggedVariant> {
+        self.variants.iter().filter(|x| !x.fields.is_empty())
+    }
+
+    /// The C struct holding the payload of the given variant, named `{Name}{Variant}`.
+    ///
+    /// For [`TagPlacement::Inline`] the struct starts with a `tag` field, [`validation`](crate::validate)
+    /// rejects variants having a field of that name themselves.
+    pub fn variant_composite(&self, variant: &TaggedVariant) -> CompositeType {
+        let mut fields = Vec::with_capacity(variant.fields.len() + 1);
+
+        if self.placement == TagPlacement::Inline {
+            fields.push(Field::new("tag".to_string(), CType::Primitive(self.tag)));
+        }
+
+        fields.extend(variant.fields.iter().cloned());
+
+        let meta = Meta::with_namespace_documentation(self.meta.namespace.clone(), variant.documentation.clone());
+        CompositeType::with_meta(format!("{}{}", self.name, variant.name), fields, meta)
+    }
+
+    /// Byte offset at which the variant structs of [`variant_composite`](Self::variant_composite) start.
+    pub fn payload_offset(&self, model: DataModel) -> usize {
+        match self.placement {
+            TagPlacement::Separate => align_up(self.tag.layout(model).size(), self.payload_layout(model).align()),
+            TagPlacement::Inline => 0,
+        }
+    }
+
+    /// Computes the layout of the whole type, tag included.
+    pub fn layout(&self, model: DataModel) -> Layout {
+        let tag = self.tag.layout(model);
+        let payload = self.payload_layout(model);
+        let align = tag.align().max(payload.align());
+        let size = self.payload_offset(model) + payload.size();
+
+        Layout::new(align_up(size.max(tag.size()), align), align)
+    }
+
+    /// Layout of the union of all variant structs.
+    fn payload_layout(&self, model: DataModel) -> Layout {
+        self.payload_variants()
+            .map(|x| self.variant_composite(x).layout(model))
+            .fold(Layout::new(0, 1), |a, b| Layout::new(a.size().max(b.size()), a.align().max(b.align())))
+    }
+}
+
+/// Variant of a [`TaggedUnionType`], with a discriminant and possibly some fields.
+///
+/// Tuple variants have their fields named `x0`, `x1`, ..., the same as tuple structs.
+#[derive(Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct TaggedVariant {
+    name: String,
+    value: i64,
+    fields: Vec<Field>,
+    documentation: Documentation,
+}
+
+impl TaggedVariant {
+    pub fn new(name: String, value: i64, fields: Vec<Field>, documentation: Documentation) -> Self {
+        Self {
+            name,
+            value,
+            fields,
+            documentation,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn value(&self) -> i64 {
+        self.value
+    }
+
+    pub fn fields(&self) -> &[Field] {
+        &self.fields
+    }
+
+    pub fn documentation(&self) -> &Documentation {
+        &self.documentation
+    }
 }
 
 /// Doesn't exist in C, but other languages can benefit from accidentally using 'private' fields.
 #[derive(Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum Visibility {
     Public,
     Private,
@@ -426,6 +770,7 @@ pub enum Visibility {
 
 /// Fields of a [`CompositeType`].
 #[derive(Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct Field {
     name: String,
     visibility: Visibility,
@@ -466,6 +811,7 @@ impl Field {
 
 /// A named `struct` that becomes a fieldless `typedef struct S S;` in C.
 #[derive(Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct OpaqueType {
     name: String,
     meta: Meta,
@@ -487,6 +833,7 @@ impl OpaqueType {
 
 /// Additional information for user-defined types.
 #[derive(Clone, Debug, Default, Ord, PartialOrd, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct Meta {
     documentation: Documentation,
     namespace: String,
@@ -521,6 +868,7 @@ impl Meta {
 
 /// A named, exported `#[no_mangle] extern "C" fn f()` function.
 #[derive(Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct Function {
     name: String,
     meta: Meta,
@@ -551,6 +899,7 @@ impl Function {
 
 /// Represents multiple `in` and a single `out` parameters.
 #[derive(Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct FunctionSignature {
     params: Vec<Parameter>,
     rval: CType,
@@ -572,6 +921,7 @@ impl FunctionSignature {
 
 /// Parameters of a [`FunctionSignature`].
 #[derive(Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct Parameter {
     name: String,
     the_type: CType,
@@ -593,6 +943,7 @@ impl Parameter {
 
 /// Represents `extern "C" fn()` types in Rust and `(*f)().` in C.
 #[derive(Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct FnPointerType {
     signature: Box<FunctionSignature>,
 }
@@ -615,8 +966,92 @@ impl FnPointerType {
     }
 }
 
+/// The C data model of a target, determining the width of pointers and the alignment of 8 byte scalars.
+///
+/// Apart from 8 byte scalars on [`ILP32`](DataModel::ILP32), all primitives are naturally aligned.
+#[derive(Copy, Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub enum DataModel {
+    /// 64 bit `long` and pointers, e.g., Linux and macOS on x86_64 and aarch64.
+    LP64,
+    /// 32 bit `long`, 64 bit pointers, e.g., Windows on x86_64.
+    LLP64,
+    /// 32 bit pointers with the System V i386 ABI, aligning `u64`, `i64` and `f64` to 4 bytes in structs.
+    ILP32,
+    /// 32 bit pointers with naturally aligned 8 byte scalars, e.g., ARM, wasm32 and 32 bit Windows.
+    ILP32Aligned,
+}
+
+impl DataModel {
+    /// The data model of the platform we are compiled for.
+    pub const fn host() -> Self {
+        if cfg!(all(target_pointer_width = "32", target_arch = "x86", not(windows))) {
+            DataModel::ILP32
+        } else if cfg!(target_pointer_width = "32") {
+            DataModel::ILP32Aligned
+        } else if cfg!(windows) {
+            DataModel::LLP64
+        } else {
+            DataModel::LP64
+        }
+    }
+
+    pub const fn pointer_size(&self) -> usize {
+        match self {
+            DataModel::LP64 => 8,
+            DataModel::LLP64 => 8,
+            DataModel::ILP32 => 4,
+            DataModel::ILP32Aligned => 4,
+        }
+    }
+
+    /// Alignment of `u64`, `i64` and `f64` when used as struct fields.
+    pub const fn scalar8_align(&self) -> usize {
+        match self {
+            DataModel::LP64 => 8,
+            DataModel::LLP64 => 8,
+            DataModel::ILP32 => 4,
+            DataModel::ILP32Aligned => 8,
+        }
+    }
+}
+
+impl Default for DataModel {
+    fn default() -> Self {
+        Self::host()
+    }
+}
+
+/// Size and alignment of a type, as computed by [`CType::layout`].
+#[derive(Copy, Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub struct Layout {
+    size: usize,
+    align: usize,
+}
+
+impl Layout {
+    pub fn new(size: usize, align: usize) -> Self {
+        Self { size, align }
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    pub fn align(&self) -> usize {
+        self.align
+    }
+}
+
+fn align_up(offset: usize, align: usize) -> usize {
+    match offset % align {
+        0 => offset,
+        rest => offset + align - rest,
+    }
+}
+
 /// Markdown generated from the `///` you put on Rust code.
 #[derive(Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct Documentation {
     lines: Vec<String>,
 }
@@ -644,3 +1079,211 @@ impl Documentation {
         &self.lines
     }
 }
+
+/// Serializes [`PrimitiveValue`]s JSON numbers can't hold without loss as strings.
+#[cfg(feature = "serde")]
+mod lossless {
+    /// 128-bit integers, which exceed the range of JSON numbers, as decimal strings.
+    pub mod int128 {
+        use serde::de::Error;
+        use serde::{Deserialize, Deserializer, Serializer};
+        use std::fmt::Display;
+        use std::str::FromStr;
+
+        pub fn serialize<T: Display, S: Serializer>(x: &T, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.collect_str(x)
+        }
+
+        pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+        where
+            T: FromStr,
+            T::Err: Display,
+            D: Deserializer<'de>,
+        {
+            String::deserialize(deserializer)?.parse().map_err(D::Error::custom)
+        }
+    }
+
+    /// Finite floats as numbers, others as `"NaN"`, `"inf"` or `"-inf"`, which JSON has no numbers for.
+    pub mod float {
+        use serde::de::{DeserializeOwned, Error};
+        use serde::{Deserialize, Deserializer, Serialize, Serializer};
+        use std::fmt::Display;
+        use std::str::FromStr;
+
+        pub trait Float: Copy + Display + FromStr + Serialize + DeserializeOwned {
+            fn is_finite(self) -> bool;
+        }
+
+        impl Float for f32 {
+            fn is_finite(self) -> bool {
+                f32::is_finite(self)
+            }
+        }
+
+        impl Float for f64 {
+            fn is_finite(self) -> bool {
+                f64::is_finite(self)
+            }
+        }
+
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Repr<T> {
+            Number(T),
+            String(String),
+        }
+
+        pub fn serialize<T: Float, S: Serializer>(x: &T, serializer: S) -> Result<S::Ok, S::Error> {
+            if x.is_finite() {
+                x.serialize(serializer)
+            } else {
+                serializer.collect_str(x)
+            }
+        }
+
+        pub fn deserialize<'de, T: Float, D: Deserializer<'de>>(deserializer: D) -> Result<T, D::Error> {
+            match Repr::<T>::deserialize(deserializer)? {
+                Repr::Number(x) => Ok(x),
+                Repr::String(x) => x.parse().map_err(|_| D::Error::custom(format!("invalid float `{}`", x))),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::lang::c::{
+        ArrayType, CType, CompositeType, DataModel, Documentation, Field, Layout, Meta, PrimitiveType, TagPlacement, TaggedUnionType, TaggedVariant, UnionType,
+    };
+    use crate::lang::rust::CTypeInfo;
+    use crate::patterns::option::FFIOption;
+    use crate::patterns::slice::FFISlice;
+
+    fn field(name: &str, the_type: CType) -> Field {
+        Field::new(name.to_string(), the_type)
+    }
+
+    #[test]
+    fn composite_layout_has_padding() {
+        let composite = CompositeType::new(
+            "Padded".to_string(),
+            vec![
+                field("a", CType::Primitive(PrimitiveType::U8)),
+                field("b", CType::Primitive(PrimitiveType::U32)),
+                field("c", CType::ReadPointer(Box::new(CType::Primitive(PrimitiveType::U8)))),
+                field("d", CType::Primitive(PrimitiveType::U16)),
+            ],
+        );
+
+        assert_eq!(composite.field_offsets(DataModel::LP64), vec![0, 4, 8, 16]);
+        assert_eq!(composite.layout(DataModel::LP64), Layout::new(24, 8));
+        assert_eq!(composite.field_offsets(DataModel::ILP32), vec![0, 4, 8, 12]);
+        assert_eq!(composite.layout(DataModel::ILP32), Layout::new(16, 4));
+    }
+
+    #[test]
+    fn i386_aligns_8_byte_scalars_to_4() {
+        let composite = CompositeType::new(
+            "Mixed".to_string(),
+            vec![field("a", CType::Primitive(PrimitiveType::U32)), field("b", CType::Primitive(PrimitiveType::F64))],
+        );
+
+        assert_eq!(composite.field_offsets(DataModel::ILP32), vec![0, 4]);
+        assert_eq!(composite.layout(DataModel::ILP32), Layout::new(12, 4));
+        assert_eq!(composite.field_offsets(DataModel::ILP32Aligned), vec![0, 8]);
+        assert_eq!(composite.layout(DataModel::ILP32Aligned), Layout::new(16, 8));
+    }
+
+    #[test]
+    fn matches_host_layout() {
+        let slice = FFISlice::<u16>::type_info();
+        let option = FFIOption::<u64>::type_info();
+        let array = <[u16; 3]>::type_info();
+
+        assert_eq!(slice.size_of(), std::mem::size_of::<FFISlice<u16>>());
+        assert_eq!(slice.align_of(), std::mem::align_of::<FFISlice<u16>>());
+        assert_eq!(option.size_of(), std::mem::size_of::<FFIOption<u64>>());
+        assert_eq!(option.align_of(), std::mem::align_of::<FFIOption<u64>>());
+        assert_eq!(array.size_of(), std::mem::size_of::<[u16; 3]>());
+
+        assert_eq!(usize::type_info().size_of(), std::mem::size_of::<usize>());
+        assert_eq!(u128::type_info().size_of(), std::mem::size_of::<u128>());
+        assert_eq!(u128::type_info().align_of(), std::mem::align_of::<u128>());
+        assert_eq!(char::type_info().size_of(), std::mem::size_of::<char>());
+    }
+
+    #[allow(dead_code)]
+    #[repr(C)]
+    union Bits {
+        a: u8,
+        b: [u16; 3],
+        c: u32,
+    }
+
+    #[test]
+    fn union_matches_host_layout() {
+        let union = UnionType::new(
+            "Bits".to_string(),
+            vec![
+                field("a", CType::Primitive(PrimitiveType::U8)),
+                field("b", CType::Array(ArrayType::new(CType::Primitive(PrimitiveType::U16), 3))),
+                field("c", CType::Primitive(PrimitiveType::U32)),
+            ],
+        );
+
+        assert_eq!(
+            union.layout(DataModel::host()),
+            Layout::new(std::mem::size_of::<Bits>(), std::mem::align_of::<Bits>())
+        );
+    }
+
+    #[allow(dead_code)]
+    #[repr(C, u8)]
+    enum Separate {
+        A(u8),
+        B(u64, u16),
+        C,
+    }
+
+    #[allow(dead_code)]
+    #[repr(u16)]
+    enum Inline {
+        A(u8),
+        B(u64, u16),
+        C,
+    }
+
+    fn tagged_union(placement: TagPlacement) -> TaggedUnionType {
+        let variants = vec![
+            TaggedVariant::new("A".to_string(), 0, vec![field("x0", CType::Primitive(PrimitiveType::U8))], Documentation::new()),
+            TaggedVariant::new(
+                "B".to_string(),
+                1,
+                vec![field("x0", CType::Primitive(PrimitiveType::U64)), field("x1", CType::Primitive(PrimitiveType::U16))],
+                Documentation::new(),
+            ),
+            TaggedVariant::new("C".to_string(), 2, vec![], Documentation::new()),
+        ];
+
+        let tag = match placement {
+            TagPlacement::Separate => PrimitiveType::U8,
+            TagPlacement::Inline => PrimitiveType::U16,
+        };
+
+        TaggedUnionType::new("T".to_string(), tag, placement, variants, Meta::new())
+    }
+
+    #[test]
+    fn tagged_union_matches_host_layout() {
+        let separate = tagged_union(TagPlacement::Separate);
+        let inline = tagged_union(TagPlacement::Inline);
+
+        assert_eq!(separate.layout(DataModel::host()).size(), std::mem::size_of::<Separate>());
+        assert_eq!(separate.layout(DataModel::host()).align(), std::mem::align_of::<Separate>());
+        assert_eq!(inline.layout(DataModel::host()).size(), std::mem::size_of::<Inline>());
+        assert_eq!(inline.layout(DataModel::host()).align(), std::mem::align_of::<Inline>());
+        assert_eq!(separate.payload_variants().count(), 2);
+        assert_eq!(inline.variant_composite(&inline.variants()[0]).field_offsets(DataModel::host()), vec![0, 2]);
+    }
+}
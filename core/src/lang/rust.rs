@@ -51,6 +51,95 @@ pub unsafe trait VariantInfo {
     fn variant_info(&self) -> Variant;
 }
 
+/// Reports the layout of a type as seen by the Rust compiler, implemented via [`ffi_type`](crate::ffi_type) and for the
+/// FFI types in [`patterns`](crate::patterns).
+///
+/// Used to verify that [`CTypeInfo`] and the generated bindings agree with what `rustc` actually laid out.
+pub trait LayoutInfo: CTypeInfo {
+    fn layout_info() -> TypeLayout;
+}
+
+/// Size, alignment and field offsets of a Rust type, see [`LayoutInfo`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct TypeLayout {
+    name: String,
+    size: usize,
+    align: usize,
+    fields: Vec<FieldLayout>,
+}
+
+impl TypeLayout {
+    pub fn new(name: String, size: usize, align: usize, fields: Vec<FieldLayout>) -> Self {
+        Self { name, size, align, fields }
+    }
+
+    /// The type's [`name_within_lib`](CType::name_within_lib).
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    pub fn align(&self) -> usize {
+        self.align
+    }
+
+    pub fn fields(&self) -> &[FieldLayout] {
+        &self.fields
+    }
+}
+
+/// Offset of a field within its struct, see [`TypeLayout`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct FieldLayout {
+    name: String,
+    offset: usize,
+}
+
+impl FieldLayout {
+    pub fn new(name: String, offset: usize) -> Self {
+        Self { name, offset }
+    }
+
+    /// The field's name in bindings.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+}
+
+/// Body of [`LayoutInfo::layout_info`] for a `#[repr(C)]` struct with the given fields, named as in bindings.
+///
+/// Fields named differently in bindings, or tuple fields, are given as `member as "name"`.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __layout_info {
+    ($($field:ident),* $(,)?) => {
+        $crate::__layout_info!($($field as stringify!($field)),*)
+    };
+    ($($field:tt as $name:expr),* $(,)?) => {{
+        let uninit = ::std::mem::MaybeUninit::<Self>::uninit();
+        let base = uninit.as_ptr();
+        #[allow(unused_mut)]
+        let mut fields = ::std::vec::Vec::new();
+
+        $({
+            // SAFETY: Only computes the address of the field, nothing is read.
+            let offset = unsafe { ::std::ptr::addr_of!((*base).$field) as usize - base as usize };
+            fields.push($crate::lang::rust::FieldLayout::new($name.to_string(), offset));
+        })*
+
+        let name = <Self as $crate::lang::rust::CTypeInfo>::type_info().name_within_lib();
+
+        $crate::lang::rust::TypeLayout::new(name, ::std::mem::size_of::<Self>(), ::std::mem::align_of::<Self>(), fields)
+    }};
+}
+
 macro_rules! impl_ctype_primitive {
     (
         $rust_type:ty,
@@ -77,6 +166,22 @@ macro_rules! impl_const_value_primitive {
     };
 }
 
+impl From<&str> for ConstantValue {
+    fn from(x: &str) -> Self {
+        Self::String(x.to_string())
+    }
+}
+
+impl<T, const N: usize> From<[T; N]> for ConstantValue
+where
+    T: CTypeInfo + Into<ConstantValue>,
+{
+    fn from(x: [T; N]) -> Self {
+        let array_type = ArrayType::new(T::type_info(), N);
+        Self::Array(array_type, IntoIterator::into_iter(x).map(Into::into).collect())
+    }
+}
+
 impl_const_value_primitive!(u8, PrimitiveValue::U8);
 impl_const_value_primitive!(u16, PrimitiveValue::U16);
 impl_const_value_primitive!(u32, PrimitiveValue::U32);
@@ -85,6 +190,11 @@ impl_const_value_primitive!(i8, PrimitiveValue::I8);
 impl_const_value_primitive!(i16, PrimitiveValue::I16);
 impl_const_value_primitive!(i32, PrimitiveValue::I32);
 impl_const_value_primitive!(i64, PrimitiveValue::I64);
+impl_const_value_primitive!(u128, PrimitiveValue::U128);
+impl_const_value_primitive!(i128, PrimitiveValue::I128);
+impl_const_value_primitive!(usize, PrimitiveValue::USize);
+impl_const_value_primitive!(isize, PrimitiveValue::ISize);
+impl_const_value_primitive!(char, PrimitiveValue::Char);
 impl_const_value_primitive!(f32, PrimitiveValue::F32);
 impl_const_value_primitive!(f64, PrimitiveValue::F64);
 impl_const_value_primitive!(bool, PrimitiveValue::Bool);
@@ -99,6 +209,11 @@ impl_ctype_primitive!(i8, PrimitiveType::I8);
 impl_ctype_primitive!(i16, PrimitiveType::I16);
 impl_ctype_primitive!(i32, PrimitiveType::I32);
 impl_ctype_primitive!(i64, PrimitiveType::I64);
+impl_ctype_primitive!(u128, PrimitiveType::U128);
+impl_ctype_primitive!(i128, PrimitiveType::I128);
+impl_ctype_primitive!(usize, PrimitiveType::USize);
+impl_ctype_primitive!(isize, PrimitiveType::ISize);
+impl_ctype_primitive!(char, PrimitiveType::Char);
 impl_ctype_primitive!(f32, PrimitiveType::F32);
 impl_ctype_primitive!(f64, PrimitiveType::F64);
 impl_ctype_primitive!(bool, PrimitiveType::Bool);
@@ -110,6 +225,10 @@ impl_ctype_primitive!(std::num::NonZeroI8, PrimitiveType::I8);
 impl_ctype_primitive!(std::num::NonZeroI16, PrimitiveType::I16);
 impl_ctype_primitive!(std::num::NonZeroI32, PrimitiveType::I32);
 impl_ctype_primitive!(std::num::NonZeroI64, PrimitiveType::I64);
+impl_ctype_primitive!(std::num::NonZeroU128, PrimitiveType::U128);
+impl_ctype_primitive!(std::num::NonZeroI128, PrimitiveType::I128);
+impl_ctype_primitive!(std::num::NonZeroUsize, PrimitiveType::USize);
+impl_ctype_primitive!(std::num::NonZeroIsize, PrimitiveType::ISize);
 impl_ctype_primitive!(Option<std::num::NonZeroU8>, PrimitiveType::U8);
 impl_ctype_primitive!(Option<std::num::NonZeroU16>, PrimitiveType::U16);
 impl_ctype_primitive!(Option<std::num::NonZeroU32>, PrimitiveType::U32);
@@ -118,6 +237,10 @@ impl_ctype_primitive!(Option<std::num::NonZeroI8>, PrimitiveType::I8);
 impl_ctype_primitive!(Option<std::num::NonZeroI16>, PrimitiveType::I16);
 impl_ctype_primitive!(Option<std::num::NonZeroI32>, PrimitiveType::I32);
 impl_ctype_primitive!(Option<std::num::NonZeroI64>, PrimitiveType::I64);
+impl_ctype_primitive!(Option<std::num::NonZeroU128>, PrimitiveType::U128);
+impl_ctype_primitive!(Option<std::num::NonZeroI128>, PrimitiveType::I128);
+impl_ctype_primitive!(Option<std::num::NonZeroUsize>, PrimitiveType::USize);
+impl_ctype_primitive!(Option<std::num::NonZeroIsize>, PrimitiveType::ISize);
 
 unsafe impl<T> CTypeInfo for NonNull<T>
 where
@@ -81,7 +81,7 @@
 //!
 //! See the [**reference project**](https://github.com/ralfbiedert/interoptopus/tree/master/reference_project/src) for an overview:
 //! - [functions](https://github.com/ralfbiedert/interoptopus/blob/master/reference_project/src/functions.rs) (`extern "C"` functions and delegates)
-//! - [types](https://github.com/ralfbiedert/interoptopus/blob/master/reference_project/src/types.rs) (composites, enums, opaques, references, ...)
+//! - [types](https://github.com/ralfbiedert/interoptopus/blob/master/reference_project/src/types.rs) (composites, enums, tagged unions, opaques, references, ...)
 //! - [constants](https://github.com/ralfbiedert/interoptopus/blob/master/reference_project/src/constants.rs) (primitive constants; results of const evaluation)
 //! - [patterns](https://github.com/ralfbiedert/interoptopus/tree/master/reference_project/src/patterns) (ASCII pointers, options, slices, classes, ...)
 //!
@@ -120,7 +120,9 @@
 //! Gated behind **feature flags**, these enable:
 //!
 //! - `derive` - Proc macros such as `ffi_type`, ...
-//! - `serde` - Serde attributes on internal types.
+//! - `serde` - Serde attributes on internal types, and JSON (de)serialization of an [`Inventory`] via [`Inventory::to_json`], e.g., to generate bindings with the `interoptopus_cli` crate.
+//! - `loader` - Read an inventory [embedded](crate::embed) in a compiled library.
+//! - `registry` - Let items [register themselves](crate::registry) instead of listing them in an inventory function.
 //! - `log` - Invoke [log](https://crates.io/crates/log) on FFI errors.
 //!
 //!
@@ -169,18 +171,26 @@
 //! [docs.rs]: https://docs.rs/interoptopus/
 
 pub use crate::core::{merge_inventories, non_service_functions, Inventory, InventoryBuilder, InventoryItem, Symbol};
-pub use error::Error;
+pub use error::{Error, ErrorContext};
 pub use generators::Interop;
 #[cfg(feature = "derive")]
 #[cfg_attr(docsrs, doc(cfg(feature = "derive")))] // does this work?
 pub use interoptopus_proc::{ffi_constant, ffi_function, ffi_service, ffi_service_ctor, ffi_service_ignore, ffi_service_method, ffi_surrogates, ffi_type};
 
 mod core;
+pub mod diff;
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+pub mod embed;
 mod error;
 mod generators;
 pub mod patterns;
+#[cfg(feature = "registry")]
+#[cfg_attr(docsrs, doc(cfg(feature = "registry")))]
+pub mod registry;
 pub mod testing;
 pub mod util;
+pub mod validate;
 pub mod writer;
 
 pub mod lang {
@@ -364,3 +374,23 @@ macro_rules! pattern {
         $crate::Symbol::Pattern(info)
     }};
 }
+
+/// Records a pattern in the [`registry`](crate::registry), used by macros like [`ffi_vec`](crate::ffi_vec).
+#[cfg(feature = "registry")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __register_pattern {
+    ($x:ident) => {
+        $crate::registry::submit! {
+            $crate::registry::Registration::new(module_path!(), file!(), line!(), || $crate::pattern!($x))
+        }
+    };
+}
+
+/// Records a pattern in the [`registry`](crate::registry), a no-op without the `registry` feature.
+#[cfg(not(feature = "registry"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __register_pattern {
+    ($x:ident) => {};
+}
@@ -23,9 +23,10 @@
 //!     my_inventory().into()
 //! }
 //!
-//! // Inventory of our exports.
+//! // Inventory of our exports, currently at API version 1.0.
 //! pub fn my_inventory() -> Inventory {
 //!     InventoryBuilder::new()
+//!         .api_version(1, 0)
 //!         .register(function!(my_api_guard))
 //!         .inventory()
 //! }
@@ -35,28 +36,79 @@
 //! a library with mismatching bindings:
 //!
 //! ```csharp
-//! Exception: API reports hash X which differs from hash in bindings (Y). You probably forgot to update / copy either the bindings or the library.
+//! Exception: Library has API version 1.2 (hash X), bindings need 1.3 (hash Y); the library is older. You probably forgot to update / copy either the bindings or the library.
 //! ```
 //!
+//! # Versioning
+//!
+//! An [`APIVersion`] holds the `major.minor` version declared via [`InventoryBuilder::api_version`](crate::InventoryBuilder::api_version),
+//! and a 32 bit ABI hash of the inventory. Generated bindings accept a library if
+//!
+//! - both have the same major version, and
+//! - the library has a higher minor version, i.e., it only added items in a backwards compatible way, or
+//! - the library has the same minor version and the same hash.
+//!
+//! In other words, bump the minor version when only adding functions, types or constants, and the major version
+//! for any breaking change. If you never declare a version, `0.0` is used and bindings require an exact hash match.
+//!
+//! On the FFI boundary the version is a single `u64`, with the major version in bits 48 - 63, the minor version
+//! in bits 32 - 47, and the hash in bits 0 - 31.
 //!
 //! # Hash Value
 //!
-//! The hash value
+//! The hash value (of which [`APIVersion`] holds the upper and lower 32 bits XOR'ed together)
 //!
-//! - is based on the signatures of the involved functions, types and constants,
+//! - is based on the ABI-relevant parts of the involved functions, types and constants, i.e., their names,
+//!   types, layouts and values,
 //! - is expected to change when the API changes, e.g., functions, types, fields, ... are added
-//! changed or removed,
+//!   changed or removed,
 //! - will even react to benign API changes (e.g., just adding functions),
-//! - might even react to documentation changes (subject to change; feedback welcome).
+//! - will **not** react to documentation, namespaces, parameter names, field visibility or registration order,
+//! - is stable across Rust versions and platforms, and can be reproduced by other tools as described below.
+//!
+//! # Canonical Form
+//!
+//! The hash is the 64 bit [FNV-1a](http://www.isthe.com/chongo/tech/comp/fnv/) hash (offset basis
+//! `0xcbf29ce484222325`, prime `0x100000001b3`) of the UTF-8 bytes of the inventory's canonical form,
+//! as returned by [`inventory_canonical_form`].
+//!
+//! The canonical form contains one line per function, constant and named type, each line terminated by `\n`,
+//! without duplicates and sorted bytewise. Lines look as follows:
+//!
+//! ```text
+//! fn <name>(<type>, <type>, ...) -> <type>
+//! const <name>: <type> = <value>
+//! opaque <name>
+//! enum <name>: <repr> { <variant> = <value>, ... }
+//! struct <name> (<size>, <align>) { <field>: <type> @ <offset>, ... }
+//! union <name> (<size>, <align>) { <field>: <type>, ... }
+//! tagged_union <name>: <tag> <separate|inline> (<size>, <align>) { <variant> = <value> (<field>: <type>, ...), ... }
+//! ```
+//!
+//! Sizes, alignments and offsets are computed for the [`LP64`](crate::lang::c::DataModel::LP64) data model,
+//! list separators are always `", "`, and an empty list is written as `{  }` or `()` respectively.
+//!
+//! Types are written as follows:
+//!
+//! - primitives by their Rust name, e.g., `u8`, `f32`, `bool` or `()`,
+//! - named types by their name, e.g., `Vec3f32`,
+//! - pointers as `*const T` and `*mut T`, arrays as `[T; N]`, function pointers as `extern "C" fn(A, B) -> R`,
+//! - type patterns as their [fallback type](crate::patterns::TypePattern::fallback_type), e.g., [`APIVersion`] as `u64`.
 //!
-use crate::lang::c::CType;
+//! Values are written as follows:
+//!
+//! - integers in decimal, `bool` as `true` or `false`, `char` as the decimal value of its Unicode scalar,
+//! - `f32` and `f64` as their IEEE 754 bit pattern in lowercase hex, e.g., `0x3f800000` for `1.0f32`,
+//! - strings as `str:` followed by their UTF-8 bytes in lowercase hex, e.g., `str:6869` for `"hi"`,
+//! - arrays as `[a, b, ...]`, structs as `{a, b, ...}` with values in field order.
+//!
+use crate::lang::c::{CType, ConstantValue, DataModel, Field, FnPointerType, FunctionSignature, PrimitiveValue, TagPlacement};
 use crate::lang::rust::CTypeInfo;
 use crate::patterns::TypePattern;
 use crate::Inventory;
-use std::collections::hash_map::DefaultHasher;
-use std::hash::{Hash, Hasher};
+use std::collections::BTreeSet;
 
-/// Holds the API version hash of the given library.
+/// Holds the API version and ABI hash of the given library.
 #[repr(transparent)]
 #[allow(dead_code)]
 #[derive(Debug, Default, PartialOrd, PartialEq, Copy, Clone)]
@@ -65,15 +117,45 @@ pub struct APIVersion {
 }
 
 impl APIVersion {
-    /// Create a new API version from the given hash.
-    pub fn new(version: u64) -> Self {
-        Self { version }
+    /// Create a new API version `major.minor` with the given ABI hash.
+    pub fn new(major: u16, minor: u16, hash: u32) -> Self {
+        Self {
+            version: u64::from(major) << 48 | u64::from(minor) << 32 | u64::from(hash),
+        }
+    }
+
+    /// Create an API version from its FFI representation, e.g., as returned by a guard function.
+    pub fn from_bits(bits: u64) -> Self {
+        Self { version: bits }
     }
 
     /// Create a new API version from the given library.
     pub fn from_inventory(inventory: &Inventory) -> Self {
-        let version = inventory_hash(inventory);
-        Self { version }
+        let (major, minor) = inventory.api_version();
+        let hash = inventory_hash(inventory);
+        Self::new(major, minor, (hash ^ (hash >> 32)) as u32)
+    }
+
+    /// The FFI representation of this version.
+    pub fn bits(&self) -> u64 {
+        self.version
+    }
+
+    pub fn major(&self) -> u16 {
+        (self.version >> 48) as u16
+    }
+
+    pub fn minor(&self) -> u16 {
+        (self.version >> 32) as u16
+    }
+
+    pub fn hash(&self) -> u32 {
+        self.version as u32
+    }
+
+    /// Checks if bindings generated for this version can use a `library` reporting the given version.
+    pub fn accepts(&self, library: APIVersion) -> bool {
+        self.major() == library.major() && (library.minor() > self.minor() || library == *self)
     }
 }
 
@@ -88,29 +170,222 @@ impl From<Inventory> for APIVersion {
         Self::from_inventory(&i)
     }
 }
-/// Returns a unique hash for an inventory; used by backends.
+/// Returns a stable hash for an inventory; used by backends.
+///
+/// See the [module documentation](self) for how it is computed.
 pub fn inventory_hash(inventory: &Inventory) -> u64 {
-    let mut hasher = DefaultHasher::new();
+    fnv1a64(inventory_canonical_form(inventory).as_bytes())
+}
+
+/// Returns the canonical form of an inventory the [`inventory_hash`] is computed over.
+pub fn inventory_canonical_form(inventory: &Inventory) -> String {
+    let mut lines = BTreeSet::new();
+
+    for t in inventory.ctypes() {
+        type_definitions(t, &mut lines);
+    }
+
+    for f in inventory.functions() {
+        lines.insert(format!("fn {}{}", f.name(), signature_name(f.signature())));
+        type_definitions(&CType::FnPointer(FnPointerType::new(f.signature().clone())), &mut lines);
+    }
 
-    // TODO: Do we need to hash patterns as well? They should never impact the 'relevant' API surface?
-    let types = inventory.ctypes();
-    let functions = inventory.functions();
-    let constants = inventory.constants();
+    for c in inventory.constants() {
+        lines.insert(format!("const {}: {} = {}", c.name(), type_name(&c.the_type()), value_name(c.value())));
+        type_definitions(&c.the_type(), &mut lines);
+    }
+
+    lines.into_iter().map(|x| x + "\n").collect()
+}
+
+fn fnv1a64(bytes: &[u8]) -> u64 {
+    let mut hash = 0xcbf2_9ce4_8422_2325_u64;
+
+    for byte in bytes {
+        hash ^= u64::from(*byte);
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+
+    hash
+}
+
+fn signature_name(signature: &FunctionSignature) -> String {
+    let params = signature.params().iter().map(|x| type_name(x.the_type())).collect::<Vec<_>>();
+    format!("({}) -> {}", params.join(", "), type_name(signature.rval()))
+}
+
+pub(crate) fn type_name(t: &CType) -> String {
+    match t {
+        CType::Primitive(x) => x.rust_name().to_string(),
+        CType::Array(x) => format!("[{}; {}]", type_name(x.array_type()), x.len()),
+        CType::Enum(x) => x.rust_name().to_string(),
+        CType::Opaque(x) => x.rust_name().to_string(),
+        CType::Composite(x) => x.rust_name().to_string(),
+        CType::Union(x) => x.rust_name().to_string(),
+        CType::TaggedUnion(x) => x.rust_name().to_string(),
+        CType::FnPointer(x) => format!("extern \"C\" fn{}", signature_name(x.signature())),
+        CType::ReadPointer(x) => format!("*const {}", type_name(x)),
+        CType::ReadWritePointer(x) => format!("*mut {}", type_name(x)),
+        CType::Pattern(x) => type_name(&x.fallback_type()),
+    }
+}
+
+fn fields_with_offsets(fields: &[Field], offsets: Option<Vec<usize>>) -> String {
+    let fields = fields
+        .iter()
+        .enumerate()
+        .map(|(i, x)| match &offsets {
+            Some(offsets) => format!("{}: {} @ {}", x.name(), type_name(x.the_type()), offsets[i]),
+            None => format!("{}: {}", x.name(), type_name(x.the_type())),
+        })
+        .collect::<Vec<_>>();
+
+    fields.join(", ")
+}
 
-    // TODO: Should probably exclude documentation & co.
-    for t in types {
-        t.hash(&mut hasher);
+/// Adds the definitions of all named types in `t` to `lines`.
+fn type_definitions(t: &CType, lines: &mut BTreeSet<String>) {
+    let model = DataModel::LP64;
+
+    match t {
+        CType::Primitive(_) => {}
+        CType::Array(x) => type_definitions(x.array_type(), lines),
+        CType::Enum(x) => {
+            let variants = x.variants().iter().map(|v| format!("{} = {}", v.name(), v.value())).collect::<Vec<_>>();
+            lines.insert(format!("enum {}: {} {{ {} }}", x.rust_name(), x.repr().rust_name(), variants.join(", ")));
+        }
+        CType::Opaque(x) => {
+            lines.insert(format!("opaque {}", x.rust_name()));
+        }
+        CType::Composite(x) => {
+            let layout = x.layout(model);
+            let fields = fields_with_offsets(x.fields(), Some(x.field_offsets(model)));
+            lines.insert(format!("struct {} ({}, {}) {{ {} }}", x.rust_name(), layout.size(), layout.align(), fields));
+            x.fields().iter().for_each(|f| type_definitions(f.the_type(), lines));
+        }
+        CType::Union(x) => {
+            let layout = x.layout(model);
+            let fields = fields_with_offsets(x.fields(), None);
+            lines.insert(format!("union {} ({}, {}) {{ {} }}", x.rust_name(), layout.size(), layout.align(), fields));
+            x.fields().iter().for_each(|f| type_definitions(f.the_type(), lines));
+        }
+        CType::TaggedUnion(x) => {
+            let layout = x.layout(model);
+            let placement = match x.placement() {
+                TagPlacement::Separate => "separate",
+                TagPlacement::Inline => "inline",
+            };
+            let variants = x
+                .variants()
+                .iter()
+                .map(|v| format!("{} = {} ({})", v.name(), v.value(), fields_with_offsets(v.fields(), None)))
+                .collect::<Vec<_>>();
+
+            lines.insert(format!(
+                "tagged_union {}: {} {} ({}, {}) {{ {} }}",
+                x.rust_name(),
+                x.tag().rust_name(),
+                placement,
+                layout.size(),
+                layout.align(),
+                variants.join(", ")
+            ));
+
+            x.variants().iter().flat_map(|v| v.fields()).for_each(|f| type_definitions(f.the_type(), lines));
+        }
+        CType::FnPointer(x) => {
+            x.signature().params().iter().for_each(|p| type_definitions(p.the_type(), lines));
+            type_definitions(x.signature().rval(), lines);
+        }
+        CType::ReadPointer(x) => type_definitions(x, lines),
+        CType::ReadWritePointer(x) => type_definitions(x, lines),
+        CType::Pattern(x) => type_definitions(&x.fallback_type(), lines),
+    }
+}
+
+pub(crate) fn value_name(value: &ConstantValue) -> String {
+    match value {
+        ConstantValue::Primitive(x) => match x {
+            PrimitiveValue::Bool(x) => x.to_string(),
+            PrimitiveValue::U8(x) => x.to_string(),
+            PrimitiveValue::U16(x) => x.to_string(),
+            PrimitiveValue::U32(x) => x.to_string(),
+            PrimitiveValue::U64(x) => x.to_string(),
+            PrimitiveValue::I8(x) => x.to_string(),
+            PrimitiveValue::I16(x) => x.to_string(),
+            PrimitiveValue::I32(x) => x.to_string(),
+            PrimitiveValue::I64(x) => x.to_string(),
+            PrimitiveValue::U128(x) => x.to_string(),
+            PrimitiveValue::I128(x) => x.to_string(),
+            PrimitiveValue::USize(x) => x.to_string(),
+            PrimitiveValue::ISize(x) => x.to_string(),
+            PrimitiveValue::Char(x) => u32::from(*x).to_string(),
+            PrimitiveValue::F32(x) => format!("0x{:08x}", x.to_bits()),
+            PrimitiveValue::F64(x) => format!("0x{:016x}", x.to_bits()),
+        },
+        ConstantValue::String(x) => format!("str:{}", x.bytes().map(|b| format!("{:02x}", b)).collect::<String>()),
+        ConstantValue::Array(_, values) => format!("[{}]", values.iter().map(value_name).collect::<Vec<_>>().join(", ")),
+        ConstantValue::Composite(_, values) => format!("{{{}}}", values.iter().map(value_name).collect::<Vec<_>>().join(", ")),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{fnv1a64, inventory_canonical_form, inventory_hash, APIVersion};
+    use crate::lang::c::{
+        CType, CompositeType, Constant, ConstantValue, Documentation, Field, Function, FunctionSignature, Meta, Parameter, PrimitiveType, PrimitiveValue,
+    };
+    use crate::{Inventory, InventoryBuilder, Symbol};
+
+    fn inventory(documentation: &str, param: &str) -> Inventory {
+        let meta = Meta::with_documentation(Documentation::from_line(documentation));
+        let vec = CompositeType::with_meta(
+            "Vec2".to_string(),
+            vec![
+                Field::new("x".to_string(), CType::Primitive(PrimitiveType::F32)),
+                Field::new("y".to_string(), CType::Primitive(PrimitiveType::U8)),
+            ],
+            meta.clone(),
+        );
+        let signature = FunctionSignature::new(vec![Parameter::new(param.to_string(), CType::Composite(vec))], CType::Primitive(PrimitiveType::Void));
+        let function = Function::new("f".to_string(), signature, meta.clone());
+        let constant = Constant::new("C".to_string(), ConstantValue::Primitive(PrimitiveValue::F32(1.0)), meta);
+
+        InventoryBuilder::new()
+            .register(Symbol::Function(function))
+            .register(Symbol::Constant(constant))
+            .inventory()
+    }
+
+    #[test]
+    fn version_accepts_minor_bumps_only() {
+        let bindings = APIVersion::new(1, 2, 0xabcd);
+
+        assert_eq!(bindings.major(), 1);
+        assert_eq!(bindings.minor(), 2);
+        assert_eq!(bindings.hash(), 0xabcd);
+
+        assert!(bindings.accepts(bindings));
+        assert!(bindings.accepts(APIVersion::new(1, 3, 0x1234)));
+        assert!(!bindings.accepts(APIVersion::new(1, 2, 0x1234)));
+        assert!(!bindings.accepts(APIVersion::new(1, 1, 0xabcd)));
+        assert!(!bindings.accepts(APIVersion::new(2, 2, 0xabcd)));
     }
 
-    // TODO: Should probably exclude documentation & co.
-    for f in functions {
-        f.hash(&mut hasher);
+    #[test]
+    fn fnv1a64_matches_reference() {
+        assert_eq!(fnv1a64(b""), 0xcbf29ce484222325);
+        assert_eq!(fnv1a64(b"a"), 0xaf63dc4c8601ec8c);
     }
 
-    for c in constants {
-        c.name().hash(&mut hasher);
-        c.value().fucking_hash_it_already(&mut hasher);
+    #[test]
+    fn canonical_form_is_documented_form() {
+        let expected = "const C: f32 = 0x3f800000\nfn f(Vec2) -> ()\nstruct Vec2 (8, 4) { x: f32 @ 0, y: u8 @ 4 }\n";
+        assert_eq!(inventory_canonical_form(&inventory("", "x")), expected);
     }
 
-    hasher.finish()
+    #[test]
+    fn hash_ignores_documentation_and_parameter_names() {
+        assert_eq!(inventory_hash(&inventory("", "x")), inventory_hash(&inventory("Documented.", "y")));
+    }
 }
@@ -79,11 +79,49 @@
 //! To fix this, you can replace `pub type CallbackSlice = ...` with a `callback!` call
 //! which should generate a helper type that works.
 //!
+//!
+//! # User Data
+//!
+//! A plain function pointer can't carry state, so C callers can't pass closures and managed
+//! languages must keep their delegates alive on their own. The macro
+//! [**`callback_with_data`**](crate::callback_with_data) instead defines a struct holding the
+//! function pointer, a `*const c_void` user data pointer handed back on each call, and an
+//! optional destructor Rust invokes with that pointer once the struct is dropped:
+//!
+//!```
+//! use interoptopus::{ffi_function, callback_with_data};
+//!
+//! callback_with_data!(SumDelegate(x: u32) -> u32);
+//!
+//! #[ffi_function]
+//! #[no_mangle]
+//! pub extern "C" fn my_function(callback: SumDelegate) -> u32 {
+//!     callback.call(1) + callback.call(2)
+//! }
+//! ```
+//!
+//! C will see the equivalent of
+//!
+//! ```c
+//! typedef struct sumdelegate
+//!     {
+//!     uint32_t (*callback)(uint32_t x, const void* data);
+//!     const void* data;
+//!     void (*destructor)(const void* data);
+//!     } sumdelegate;
+//! ```
+//!
+//! while backends supporting this pattern box a managed closure (e.g., into a C# `GCHandle`) when
+//! converting it to `SumDelegate`, and release it from within the destructor.
+//!
 
-use crate::lang::c::FnPointerType;
+use crate::lang::c::{CType, CompositeType, Documentation, Field, FnPointerType, FunctionSignature, Meta, Parameter, PrimitiveType, Visibility};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
 /// Internal helper naming a generated callback type wrapper.
 #[derive(Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct NamedCallback {
     name: String,
     fnpointer: FnPointerType,
@@ -106,6 +144,75 @@ impl NamedCallback {
     }
 }
 
+/// Internal helper for a callback type carrying user data, generated via [`callback_with_data`](crate::callback_with_data).
+#[derive(Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct CallbackWithData {
+    composite: CompositeType,
+    fnpointer: FnPointerType,
+}
+
+impl CallbackWithData {
+    /// Creates the struct `name` for callbacks implementing `signature`, which must not include the user data.
+    pub fn new(name: String, signature: FunctionSignature) -> Self {
+        let data = || CType::ReadPointer(Box::new(CType::Primitive(PrimitiveType::Void)));
+
+        let mut params = signature.params().to_vec();
+        params.push(Parameter::new("data".to_string(), data()));
+
+        let callback = FnPointerType::new(FunctionSignature::new(params, signature.rval().clone()));
+        let destructor = FnPointerType::new(FunctionSignature::new(
+            vec![Parameter::new("data".to_string(), data())],
+            CType::Primitive(PrimitiveType::Void),
+        ));
+
+        let doc_callback = Documentation::from_line("Function to invoke, receiving `data` as its last argument.");
+        let doc_data = Documentation::from_line("User data passed to `callback` and `destructor`, may be null.");
+        let doc_destructor = Documentation::from_line("Called with `data` once the callback is dropped, may be null.");
+
+        let fields = vec![
+            Field::with_documentation("callback".to_string(), CType::FnPointer(callback), Visibility::Public, doc_callback),
+            Field::with_documentation("data".to_string(), data(), Visibility::Public, doc_data),
+            Field::with_documentation("destructor".to_string(), CType::FnPointer(destructor), Visibility::Public, doc_destructor),
+        ];
+
+        let doc = Documentation::from_line("Callback carrying user data, which is released once the callback is dropped.");
+        let composite = CompositeType::with_meta(name, fields, Meta::with_documentation(doc));
+
+        Self {
+            composite,
+            fnpointer: FnPointerType::new(signature),
+        }
+    }
+
+    /// The struct holding `callback`, `data` and `destructor`.
+    pub fn the_type(&self) -> &CompositeType {
+        &self.composite
+    }
+
+    /// The signature callers implement, without the trailing user data.
+    pub fn fnpointer(&self) -> &FnPointerType {
+        &self.fnpointer
+    }
+
+    /// The function pointer stored in the struct, receiving the user data as its last parameter.
+    pub fn raw_fnpointer(&self) -> &FnPointerType {
+        self.field_fnpointer(0)
+    }
+
+    /// The function pointer releasing the user data.
+    pub fn destructor(&self) -> &FnPointerType {
+        self.field_fnpointer(2)
+    }
+
+    fn field_fnpointer(&self, i: usize) -> &FnPointerType {
+        match self.composite.fields()[i].the_type() {
+            CType::FnPointer(x) => x,
+            _ => panic!("Fields `callback` and `destructor` must be function pointers."),
+        }
+    }
+}
+
 /// Defines a callback type, akin to a `fn f(T) -> R` wrapped in an [Option](std::option).
 ///
 /// A named delegate will be emitted in languages supporting them, otherwise a regular
@@ -181,3 +288,149 @@ macro_rules! callback {
         }
     };
 }
+
+/// Defines a callback type carrying user data, akin to a boxed `FnMut(T) -> R`.
+///
+/// The generated struct holds the function pointer, a user data pointer passed back as last argument
+/// on each call, and an optional destructor which is invoked with the user data when the struct is
+/// dropped. For details, please see the [**callbacks module**](crate::patterns::callbacks).
+///
+/// # Example
+///
+/// This defines a type `MyCallback` with a parameter `value` returning an `u32`.
+///
+/// ```
+/// use interoptopus::callback_with_data;
+///
+/// callback_with_data!(MyCallback(value: u32) -> u32);
+/// ```
+///
+/// Rust calls it like any other callback, while the destructor runs on drop:
+///
+/// ```
+/// # use interoptopus::callback_with_data;
+/// # use std::ffi::c_void;
+/// # callback_with_data!(MyCallback(value: u32) -> u32);
+/// extern "C" fn add(value: u32, data: *const c_void) -> u32 {
+///     value + unsafe { *(data as *const u32) }
+/// }
+///
+/// let offset = 10_u32;
+/// let callback = MyCallback::new(add, &offset as *const u32 as *const c_void, None);
+///
+/// assert_eq!(callback.call(1), 11);
+/// ```
+///
+/// The generated type definition similar to:
+///
+/// ```
+/// # use std::ffi::c_void;
+/// #[repr(C)]
+/// pub struct MyCallback {
+///     callback: Option<extern "C" fn(u32, *const c_void) -> u32>,
+///     data: *const c_void,
+///     destructor: Option<extern "C" fn(*const c_void)>,
+/// }
+/// ```
+#[macro_export]
+macro_rules! callback_with_data {
+    ($name:ident($($param:ident: $ty:ty),*)) => {
+        callback_with_data!($name($($param: $ty),*) -> ());
+    };
+    ($name:ident($($param:ident: $ty:ty),*) -> $rval:ty) => {
+        #[repr(C)]
+        pub struct $name {
+            callback: Option<extern "C" fn($($ty,)* *const ::std::ffi::c_void) -> $rval>,
+            data: *const ::std::ffi::c_void,
+            destructor: Option<extern "C" fn(*const ::std::ffi::c_void)>,
+        }
+
+        impl $name {
+            /// Creates a callback from its parts, `destructor` will be called with `data` once this is dropped.
+            pub fn new(
+                callback: extern "C" fn($($ty,)* *const ::std::ffi::c_void) -> $rval,
+                data: *const ::std::ffi::c_void,
+                destructor: Option<extern "C" fn(*const ::std::ffi::c_void)>,
+            ) -> Self {
+                Self { callback: Some(callback), data, destructor }
+            }
+
+            /// Will call function if it exists, panic otherwise.
+            pub fn call(&self, $($param: $ty),*) -> $rval {
+                self.callback.expect("Assumed function would exist but it didn't.")($($param,)* self.data)
+            }
+
+            /// Will call function only if it exists
+            pub fn call_if_some(&self, $($param: $ty),*) -> Option<$rval> {
+                match self.callback {
+                    Some(c) => Some(c($($param,)* self.data)),
+                    None => None
+                }
+            }
+        }
+
+        impl Default for $name {
+            fn default() -> Self {
+                Self { callback: None, data: ::std::ptr::null(), destructor: None }
+            }
+        }
+
+        impl Drop for $name {
+            fn drop(&mut self) {
+                if let Some(destructor) = self.destructor {
+                    destructor(self.data);
+                }
+            }
+        }
+
+        impl From<extern "C" fn($($ty,)* *const ::std::ffi::c_void) -> $rval> for $name {
+            fn from(x: extern "C" fn($($ty,)* *const ::std::ffi::c_void) -> $rval) -> Self {
+                Self::new(x, ::std::ptr::null(), None)
+            }
+        }
+
+        unsafe impl interoptopus::lang::rust::CTypeInfo for $name {
+            fn type_info() -> interoptopus::lang::c::CType {
+                use interoptopus::lang::rust::CTypeInfo;
+
+                let rval = < $rval as CTypeInfo >::type_info();
+
+                let params = vec![
+                $(
+                    interoptopus::lang::c::Parameter::new(stringify!($param).to_string(), < $ty as CTypeInfo >::type_info()),
+                )*
+                ];
+
+                let sig = interoptopus::lang::c::FunctionSignature::new(params, rval);
+                let callback = interoptopus::patterns::callbacks::CallbackWithData::new(stringify!($name).to_string(), sig);
+
+                interoptopus::lang::c::CType::Pattern(interoptopus::patterns::TypePattern::CallbackWithData(callback))
+            }
+        }
+
+        impl interoptopus::lang::rust::LayoutInfo for $name {
+            fn layout_info() -> interoptopus::lang::rust::TypeLayout {
+                interoptopus::__layout_info!(callback, data, destructor)
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod test {
+    use crate::lang::c::{CType, FunctionSignature, Parameter, PrimitiveType};
+    use crate::patterns::callbacks::CallbackWithData;
+
+    #[test]
+    fn appends_data_parameter() {
+        let params = vec![Parameter::new("x".to_string(), CType::Primitive(PrimitiveType::U32))];
+        let callback = CallbackWithData::new("Add".to_string(), FunctionSignature::new(params, CType::Primitive(PrimitiveType::U32)));
+        let fields = callback.the_type().fields().iter().map(|x| x.name()).collect::<Vec<_>>();
+
+        assert_eq!(callback.the_type().rust_name(), "Add");
+        assert_eq!(fields, vec!["callback", "data", "destructor"]);
+        assert_eq!(callback.fnpointer().signature().params().len(), 1);
+        assert_eq!(callback.raw_fnpointer().signature().params()[1].name(), "data");
+        assert_eq!(callback.destructor().signature().params().len(), 1);
+    }
+}
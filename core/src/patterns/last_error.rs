@@ -0,0 +1,165 @@
+//! Thread-local storage of the last error message, retrievable over FFI.
+//!
+//! Error enums only tell callers _that_ something failed, not _why_. Whenever
+//! [`panics_and_errors_to_ffi_enum`](crate::patterns::result::panics_and_errors_to_ffi_enum),
+//! [`panics_and_errors_to_ffi_result`](crate::patterns::result::panics_and_errors_to_ffi_result)
+//! or a [`service`](crate::patterns::service) method observes an error or a panic, the message
+//! (including the error context, e.g., [`here!()`](crate::here)) is stored in a thread-local.
+//!
+//! Use [`ffi_last_error!`](crate::ffi_last_error) to define a function exposing that message, and
+//! register it via [`pattern`](crate::pattern). Backends supporting the pattern will then include
+//! the message in their exceptions.
+//!
+//! # Example
+//!
+//! ```
+//! use interoptopus::{ffi_last_error, pattern, Inventory, InventoryBuilder};
+//!
+//! // By convention this is called `<prefix>_last_error_message`.
+//! ffi_last_error!(my_library_last_error_message);
+//!
+//! pub fn my_inventory() -> Inventory {
+//!     InventoryBuilder::new()
+//!         .register(pattern!(my_library_last_error_message))
+//!         .inventory()
+//! }
+//! ```
+//!
+//! The message stays valid until the next error on the same thread, callers should copy it
+//! right after observing an error code.
+
+use crate::lang::c::Function;
+use crate::patterns::string::FFIStr;
+use crate::util::log_error;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::any::Any;
+use std::cell::RefCell;
+
+thread_local! {
+    // A `const` initializer would need Rust 1.59.
+    #[allow(clippy::missing_const_for_thread_local)]
+    static LAST_ERROR: RefCell<String> = RefCell::new(String::new());
+}
+
+/// Stores `message` as the last error of the current thread.
+pub fn set_last_error(message: String) {
+    LAST_ERROR.with(|x| *x.borrow_mut() = message);
+}
+
+/// Returns the last error message of the current thread, empty if there was none.
+pub fn last_error() -> String {
+    LAST_ERROR.with(|x| x.borrow().clone())
+}
+
+/// Returns the last error message of the current thread, pointing into thread-local storage.
+///
+/// Used by functions generated via [`ffi_last_error!`](crate::ffi_last_error).
+#[doc(hidden)]
+pub fn last_error_ffi() -> FFIStr<'static> {
+    LAST_ERROR.with(|x| {
+        let message = x.borrow();
+        // The buffer is only freed once the next error is stored, which the pattern documents.
+        let message: &'static str = unsafe { &*(message.as_str() as *const str) };
+        FFIStr::from_str(message)
+    })
+}
+
+/// Logs `message` if compiled with feature `log`, and stores it as the last error.
+#[doc(hidden)]
+pub fn error_occurred(message: String) {
+    log_error(|| message.as_str());
+    set_last_error(message);
+}
+
+/// Extracts the text of a panic payload, as passed to `panic!`.
+#[doc(hidden)]
+pub fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(x) = payload.downcast_ref::<&str>() {
+        x.to_string()
+    } else if let Some(x) = payload.downcast_ref::<String>() {
+        x.clone()
+    } else {
+        "Box<dyn Any>".to_string()
+    }
+}
+
+/// The function exposing the last error message, defined via [`ffi_last_error!`](crate::ffi_last_error).
+#[derive(Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct LastErrorPattern {
+    function: Function,
+}
+
+impl LastErrorPattern {
+    pub fn new(function: Function) -> Self {
+        Self { function }
+    }
+
+    /// The function returning the last error message as a UTF-8 string.
+    pub fn function(&self) -> &Function {
+        &self.function
+    }
+}
+
+/// Defines a function returning this thread's last error message.
+///
+/// The generated `#[no_mangle]` function has the given name and returns an
+/// [`FFIStr`](crate::patterns::string::FFIStr) that is empty if no error happened.
+/// Register it via [`pattern`](crate::pattern), see the [**last error module**](crate::patterns::last_error) for an example.
+///
+/// # Example
+///
+/// ```
+/// use interoptopus::ffi_last_error;
+///
+/// ffi_last_error!(my_library_last_error_message);
+/// ```
+#[macro_export]
+macro_rules! ffi_last_error {
+    ($name:ident) => {
+        /// Returns the message of the last error or panic on this thread.
+        ///
+        /// The string is valid until the next error on this thread, and empty if there was none.
+        #[interoptopus::ffi_function]
+        #[no_mangle]
+        pub extern "C" fn $name() -> interoptopus::patterns::string::FFIStr<'static> {
+            interoptopus::patterns::last_error::last_error_ffi()
+        }
+
+        impl interoptopus::patterns::LibraryPatternInfo for $name {
+            fn pattern_info() -> interoptopus::patterns::LibraryPattern {
+                use interoptopus::lang::rust::FunctionInfo;
+
+                let function = <$name as FunctionInfo>::function_info();
+
+                interoptopus::patterns::last_error::LastErrorPattern::new(function).into()
+            }
+        }
+
+        interoptopus::__register_pattern!($name);
+    };
+}
+
+#[cfg(test)]
+mod test {
+    use crate::patterns::last_error::{last_error, last_error_ffi, panic_message, set_last_error};
+
+    #[test]
+    fn stores_per_thread() {
+        set_last_error("Error in (here): Bad".to_string());
+
+        assert_eq!(last_error(), "Error in (here): Bad");
+        assert_eq!(last_error_ffi().as_str().unwrap(), "Error in (here): Bad");
+        assert_eq!(std::thread::spawn(last_error).join().unwrap(), "");
+    }
+
+    #[test]
+    fn extracts_panic_messages() {
+        let literal = std::panic::catch_unwind(|| panic!("Oh no")).unwrap_err();
+        let formatted = std::panic::catch_unwind(|| panic!("Oh {}", "no")).unwrap_err();
+
+        assert_eq!(panic_message(&*literal), "Oh no");
+        assert_eq!(panic_message(&*formatted), "Oh no");
+    }
+}
@@ -71,25 +71,37 @@
 //! marked <sup>🚧</sup> should be considered particularly work-in-progress.
 
 use crate::lang::c::{CType, CompositeType, PrimitiveType};
-use crate::patterns::callbacks::NamedCallback;
-use crate::patterns::result::FFIErrorEnum;
+use crate::patterns::callbacks::{CallbackWithData, NamedCallback};
+use crate::patterns::last_error::LastErrorPattern;
+use crate::patterns::result::{FFIErrorEnum, FFIResultType};
 use crate::patterns::service::Service;
+use crate::patterns::string::StringPattern;
+use crate::patterns::vec::VecPattern;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
 #[doc(hidden)]
 pub mod api_entry;
 pub mod api_guard;
 pub mod callbacks;
+pub mod last_error;
 pub mod option;
 pub mod primitives;
 pub mod result;
 pub mod service;
 pub mod slice;
 pub mod string;
+pub mod vec;
 
 /// A pattern on a library level, usually involving both methods and types.
 #[derive(Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum LibraryPattern {
     Service(Service),
+    Vec(VecPattern),
+    String(StringPattern),
+    LastError(LastErrorPattern),
 }
 
 /// Used mostly internally and provides pattern info for auto generated structs.
@@ -104,18 +116,45 @@ impl From<Service> for LibraryPattern {
     }
 }
 
+impl From<VecPattern> for LibraryPattern {
+    fn from(x: VecPattern) -> Self {
+        Self::Vec(x)
+    }
+}
+
+impl From<StringPattern> for LibraryPattern {
+    fn from(x: StringPattern) -> Self {
+        Self::String(x)
+    }
+}
+
+impl From<LastErrorPattern> for LibraryPattern {
+    fn from(x: LastErrorPattern) -> Self {
+        Self::LastError(x)
+    }
+}
+
 /// A pattern on a type level.
 #[derive(Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum TypePattern {
     AsciiPointer,
+    #[cfg_attr(feature = "serde", serde(rename = "api_version"))]
     APIVersion,
+    #[cfg_attr(feature = "serde", serde(rename = "ffi_error_enum"))]
     FFIErrorEnum(FFIErrorEnum),
     Slice(CompositeType),
     SliceMut(CompositeType),
+    Vec(CompositeType),
+    Str(CompositeType),
+    String(CompositeType),
     Option(CompositeType),
+    Result(FFIResultType),
     Bool,
     CChar,
     NamedCallback(NamedCallback),
+    CallbackWithData(CallbackWithData),
 }
 
 impl TypePattern {
@@ -129,8 +168,13 @@ impl TypePattern {
             TypePattern::FFIErrorEnum(e) => CType::Enum(e.the_enum().clone()),
             TypePattern::Slice(x) => CType::Composite(x.clone()),
             TypePattern::SliceMut(x) => CType::Composite(x.clone()),
+            TypePattern::Vec(x) => CType::Composite(x.clone()),
+            TypePattern::Str(x) => CType::Composite(x.clone()),
+            TypePattern::String(x) => CType::Composite(x.clone()),
             TypePattern::Option(x) => CType::Composite(x.clone()),
+            TypePattern::Result(x) => CType::Composite(x.the_type().clone()),
             TypePattern::NamedCallback(x) => CType::FnPointer(x.fnpointer().clone()),
+            TypePattern::CallbackWithData(x) => CType::Composite(x.the_type().clone()),
             TypePattern::Bool => CType::Primitive(PrimitiveType::U8),
             TypePattern::CChar => CType::Primitive(PrimitiveType::I8),
             TypePattern::APIVersion => CType::Primitive(PrimitiveType::U64),
@@ -28,7 +28,7 @@
 //! ```
 //!
 use crate::lang::c::{CType, CompositeType, Documentation, Field, Meta, PrimitiveType, Visibility};
-use crate::lang::rust::CTypeInfo;
+use crate::lang::rust::{CTypeInfo, LayoutInfo, TypeLayout};
 
 use crate::patterns::primitives::FFIBool;
 use crate::patterns::TypePattern;
@@ -151,6 +151,15 @@ where
     }
 }
 
+impl<T> LayoutInfo for FFIOption<T>
+where
+    T: CTypeInfo,
+{
+    fn layout_info() -> TypeLayout {
+        crate::__layout_info!(t, is_some)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use crate::patterns::option::FFIOption;
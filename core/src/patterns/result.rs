@@ -28,8 +28,12 @@
 //! }
 //! ```
 
-use crate::lang::c::{EnumType, Variant};
-use crate::util::log_error;
+use crate::lang::c::{CType, CompositeType, Documentation, EnumType, Field, Meta, Variant, Visibility};
+use crate::lang::rust::{CTypeInfo, LayoutInfo, TypeLayout};
+use crate::patterns::last_error::{error_occurred, panic_message};
+use crate::patterns::TypePattern;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use std::fmt::Debug;
 use std::panic::AssertUnwindSafe;
 
@@ -85,19 +89,189 @@ pub trait FFIError: Sized {
     const PANIC: Self;
 }
 
-// #[repr(C)]
-// #[cfg_attr(feature = "serde", derive(Debug, Copy, Clone, PartialEq, Default, Deserialize, Serialize))]
-// #[cfg_attr(not(feature = "serde"), derive(Debug, Copy, Clone, PartialEq, Default))]
-// pub struct FFIResult<T, E>
-// where
-//     E: FFIError,
-// {
-//     t: T,
-//     e: E,
-// }
+/// A value-or-error return type, the FFI equivalent of `Result<T, E>`.
+///
+/// If `err` is the [`FFIError::SUCCESS`] variant the field `t` holds a valid value,
+/// otherwise `t` is `T::default()` and must not be relied upon. Backends supporting
+/// exceptions unwrap this into a plain return value or a thrown error.
+///
+/// # Example
+///
+/// Inside a [`service`](crate::patterns::service), methods returning `Result<T, Error>`
+/// are exported as returning an `FFIResult<T, E>`:
+///
+/// ```
+/// # use interoptopus::patterns::result::FFIError;
+/// # use interoptopus::{ffi_service, ffi_service_ctor, ffi_type};
+/// #
+/// # #[derive(Debug)]
+/// # pub enum Error {
+/// #     Bad,
+/// # }
+/// #
+/// # #[ffi_type(patterns(ffi_error))]
+/// # #[repr(C)]
+/// # pub enum MyFFIError {
+/// #     Ok = 0,
+/// #     Null = 100,
+/// #     Panic = 200,
+/// #     Fail = 300,
+/// # }
+/// #
+/// # impl FFIError for MyFFIError {
+/// #     const SUCCESS: Self = Self::Ok;
+/// #     const NULL: Self = Self::Null;
+/// #     const PANIC: Self = Self::Panic;
+/// # }
+/// #
+/// # impl From<Error> for MyFFIError {
+/// #     fn from(x: Error) -> Self {
+/// #         match x {
+/// #             Error::Bad => Self::Fail,
+/// #         }
+/// #     }
+/// # }
+/// #
+/// #[ffi_type(opaque)]
+/// pub struct Counter {
+///     value: u32,
+/// }
+///
+/// #[ffi_service(error = "MyFFIError", prefix = "counter_")]
+/// impl Counter {
+///     #[ffi_service_ctor]
+///     pub fn new() -> Result<Self, Error> {
+///         Ok(Self { value: 0 })
+///     }
+///
+///     // Exported as `counter_next(...) -> FFIResult<u32, MyFFIError>`.
+///     pub fn next(&mut self) -> Result<u32, Error> {
+///         self.value = self.value.checked_add(1).ok_or(Error::Bad)?;
+///         Ok(self.value)
+///     }
+/// }
+/// ```
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct FFIResult<T, E>
+where
+    E: FFIError,
+{
+    t: T,
+    err: E,
+}
+
+impl<T, E> FFIResult<T, E>
+where
+    E: FFIError,
+{
+    /// Creates a successful result holding `t`.
+    pub fn ok(t: T) -> Self {
+        Self { t, err: E::SUCCESS }
+    }
+
+    /// Creates a failed result, `t` will be defaulted.
+    pub fn error(err: E) -> Self
+    where
+        T: Default,
+    {
+        Self { t: T::default(), err }
+    }
+
+    /// The contained value, only meaningful if the result was successful.
+    pub fn value(&self) -> &T {
+        &self.t
+    }
+
+    /// The error code, [`FFIError::SUCCESS`] if the result was successful.
+    pub fn error_code(&self) -> &E {
+        &self.err
+    }
+}
+
+impl<T, E, X> From<Result<T, X>> for FFIResult<T, E>
+where
+    T: Default,
+    E: FFIError + From<X>,
+{
+    fn from(x: Result<T, X>) -> Self {
+        match x {
+            Ok(t) => Self::ok(t),
+            Err(e) => Self::error(e.into()),
+        }
+    }
+}
+
+unsafe impl<T, E> CTypeInfo for FFIResult<T, E>
+where
+    T: CTypeInfo,
+    E: CTypeInfo + FFIError,
+{
+    fn type_info() -> CType {
+        let error = match E::type_info() {
+            CType::Pattern(TypePattern::FFIErrorEnum(e)) => e,
+            _ => panic!("The error type of an `FFIResult` must be an `ffi_error` enum."),
+        };
+
+        let doc_t = Documentation::from_line("Element that is only valid if `err` signals success.");
+        let doc_err = Documentation::from_line("Error code, success variant if `t` is valid.");
+
+        let fields = vec![
+            Field::with_documentation("t".to_string(), T::type_info(), Visibility::Public, doc_t),
+            Field::with_documentation("err".to_string(), E::type_info(), Visibility::Public, doc_err),
+        ];
+
+        let doc = Documentation::from_line("Result type containing either a value or an error code.");
+        let meta = Meta::with_namespace_documentation(T::type_info().namespace().map(|e| e.into()).unwrap_or_default(), doc);
+        let name = format!("Result{}{}", T::type_info().name_within_lib(), error.the_enum().rust_name());
+        let composite = CompositeType::with_meta(name, fields, meta);
+
+        CType::Pattern(TypePattern::Result(FFIResultType::new(composite, error)))
+    }
+}
+
+impl<T, E> LayoutInfo for FFIResult<T, E>
+where
+    T: CTypeInfo,
+    E: CTypeInfo + FFIError,
+{
+    fn layout_info() -> TypeLayout {
+        crate::__layout_info!(t, err)
+    }
+}
+
+/// The struct of an [`FFIResult`], along with the error enum it uses.
+#[derive(Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct FFIResultType {
+    composite: CompositeType,
+    error: FFIErrorEnum,
+}
+
+impl FFIResultType {
+    pub fn new(composite: CompositeType, error: FFIErrorEnum) -> Self {
+        Self { composite, error }
+    }
+
+    /// The C struct holding value and error.
+    pub fn the_type(&self) -> &CompositeType {
+        &self.composite
+    }
+
+    /// The error enum signalling success or failure.
+    pub fn error(&self) -> &FFIErrorEnum {
+        &self.error
+    }
+
+    /// The type of the value held on success.
+    pub fn value_type(&self) -> &CType {
+        self.composite.fields()[0].the_type()
+    }
+}
 
 /// Internal helper derived for enums that are an [`FFIError`].
 #[derive(Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct FFIErrorEnum {
     the_enum: EnumType,
     success_variant: Variant,
@@ -122,6 +296,8 @@ impl FFIErrorEnum {
 /// This function executes the given closure `f`. If `f` returns `Ok(())` the `SUCCESS`
 /// variant is returned. On a panic or `Err` the respective error variant is returned instead.
 ///
+/// The error or panic message is stored as the [last error](crate::patterns::last_error).
+///
 /// # Feature Flags
 ///
 /// If the `log` crate option is enabled this will invoke `log::error` on errors and panics.
@@ -192,7 +368,6 @@ impl FFIErrorEnum {
 /// Once [`FFIError::PANIC`] has been observed the enum's recipient should stop calling this API
 /// (and probably gracefully shutdown or restart), as any subsequent call risks causing a
 /// process abort.
-#[allow(unused_variables)]
 pub fn panics_and_errors_to_ffi_enum<E: Debug, FE: FFIError>(f: impl FnOnce() -> Result<(), E>, error_context: &str) -> FE
 where
     FE: From<E>,
@@ -200,13 +375,13 @@ where
     let result: Result<(), E> = match std::panic::catch_unwind(AssertUnwindSafe(f)) {
         Ok(x) => x,
         Err(e) => {
-            log_error(|| format!("Panic in ({}): {:?}", error_context, e));
+            error_occurred(format!("Panic in ({}): {}", error_context, panic_message(&*e)));
             return FE::PANIC;
         }
     };
 
     if let Err(e) = &result {
-        log_error(|| format!("Error in ({}): {:?}", error_context, e));
+        error_occurred(format!("Error in ({}): {:?}", error_context, e));
     }
 
     match result {
@@ -214,3 +389,76 @@ where
         Err(e) => FE::from(e),
     }
 }
+
+/// Helper to transform [`Result`] types to [`FFIResult`]s inside `extern "C"` functions.
+///
+/// Works like [`panics_and_errors_to_ffi_enum`], but also passes the `Ok` value through.
+/// On a panic or `Err` the respective error variant is returned along with `T::default()`.
+pub fn panics_and_errors_to_ffi_result<T: Default, E: Debug, FE>(f: impl FnOnce() -> Result<T, E>, error_context: &str) -> FFIResult<T, FE>
+where
+    FE: FFIError + From<E>,
+{
+    let result: Result<T, E> = match std::panic::catch_unwind(AssertUnwindSafe(f)) {
+        Ok(x) => x,
+        Err(e) => {
+            error_occurred(format!("Panic in ({}): {}", error_context, panic_message(&*e)));
+            return FFIResult::error(FE::PANIC);
+        }
+    };
+
+    if let Err(e) = &result {
+        error_occurred(format!("Error in ({}): {:?}", error_context, e));
+    }
+
+    FFIResult::from(result)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::patterns::result::{panics_and_errors_to_ffi_result, FFIError, FFIResult};
+
+    #[derive(Debug, Copy, Clone, PartialEq)]
+    enum TestError {
+        Ok,
+        Null,
+        Panic,
+        Fail,
+    }
+
+    impl FFIError for TestError {
+        const SUCCESS: Self = Self::Ok;
+        const NULL: Self = Self::Null;
+        const PANIC: Self = Self::Panic;
+    }
+
+    impl From<()> for TestError {
+        fn from(_: ()) -> Self {
+            Self::Fail
+        }
+    }
+
+    #[test]
+    fn ok_and_error_round_trip() {
+        let ok: FFIResult<u32, TestError> = Ok::<_, ()>(123).into();
+        let err: FFIResult<u32, TestError> = Err(()).into();
+
+        assert_eq!(ok.value(), &123);
+        assert_eq!(ok.error_code(), &TestError::Ok);
+        assert_eq!(err.value(), &0);
+        assert_eq!(err.error_code(), &TestError::Fail);
+    }
+
+    #[test]
+    #[allow(unreachable_code)]
+    fn panics_become_errors() {
+        let rval: FFIResult<u32, TestError> = panics_and_errors_to_ffi_result(
+            || {
+                panic!("Oh no");
+                Ok::<_, ()>(1)
+            },
+            "test",
+        );
+
+        assert_eq!(rval.error_code(), &TestError::Panic);
+    }
+}
@@ -115,10 +115,13 @@
 use crate::lang::c::{CType, Function, OpaqueType};
 use crate::patterns::TypePattern;
 use crate::util::longest_common_prefix;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use std::fmt::Debug;
 
 /// Combines a receiver, constructor, destructor and multiple methods in one entity.
 #[derive(Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 pub struct Service {
     the_type: OpaqueType,
     constructors: Vec<Function>,
@@ -241,6 +244,8 @@ fn extract_obvious_opaque_from_parameter(param: &CType) -> Option<OpaqueType> {
         CType::Enum(_) => None,
         CType::Opaque(x) => Some(x.clone()),
         CType::Composite(_) => None,
+        CType::Union(_) => None,
+        CType::TaggedUnion(_) => None,
         CType::FnPointer(_) => None,
         CType::ReadPointer(x) => extract_obvious_opaque_from_parameter(x),
         CType::ReadWritePointer(x) => extract_obvious_opaque_from_parameter(x),
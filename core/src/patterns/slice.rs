@@ -39,7 +39,7 @@
 //!
 
 use crate::lang::c::{CType, CompositeType, Documentation, Field, Meta, PrimitiveType, Visibility};
-use crate::lang::rust::CTypeInfo;
+use crate::lang::rust::{CTypeInfo, LayoutInfo, TypeLayout};
 use crate::patterns::TypePattern;
 use std::marker::PhantomData;
 use std::ops::{Deref, DerefMut};
@@ -134,6 +134,15 @@ where
     }
 }
 
+impl<'a, T> LayoutInfo for FFISlice<'a, T>
+where
+    T: CTypeInfo,
+{
+    fn layout_info() -> TypeLayout {
+        crate::__layout_info!(data, len)
+    }
+}
+
 /// A representation of a mutable array passed over an FFI boundary
 #[repr(C)]
 pub struct FFISliceMut<'a, T> {
@@ -242,6 +251,15 @@ where
     }
 }
 
+impl<'a, T> LayoutInfo for FFISliceMut<'a, T>
+where
+    T: CTypeInfo,
+{
+    fn layout_info() -> TypeLayout {
+        crate::__layout_info!(data, len)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use crate::patterns::slice::{FFISlice, FFISliceMut};
@@ -252,7 +270,7 @@ mod test {
         let empty = FFISlice::<u8>::empty();
         let some = FFISlice::<u8>::from_slice(slice);
 
-        assert_eq!(empty.as_slice(), &[]);
+        assert!(empty.as_slice().is_empty());
         assert_eq!(some.as_slice(), slice);
     }
 
@@ -266,7 +284,7 @@ mod test {
         sub[0] = 6;
         some[0] = 5;
 
-        assert_eq!(empty.as_slice(), &[]);
+        assert!(empty.as_slice().is_empty());
         assert_eq!(slice, &[5, 6, 2, 3, 5]);
     }
 }
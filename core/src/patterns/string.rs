@@ -1,4 +1,4 @@
-//! Raw `*const char` pointer on C-level but ASCII `string` like in languages that support it.
+//! Strings passed as `*const char` (ASCII) or with an explicit length (UTF-8), and `string` in languages supporting them.
 //!
 //! # Example
 //!
@@ -27,12 +27,56 @@
 //! void call_with_string(uint8_t* s);
 //! ```
 //!
-use crate::lang::c::CType;
-use crate::lang::rust::CTypeInfo;
+//! # UTF-8 Strings
+//!
+//! An [`AsciiPointer`] can't contain NUL, and returning one requires you to keep a `CString` alive somewhere.
+//! To exchange arbitrary text accept an [`FFIStr`] instead, and return an owned [`FFIString`] you define a
+//! destructor for via [`ffi_string`](crate::ffi_string):
+//!
+//! ```
+//! use interoptopus::{ffi_function, ffi_string, function, pattern, Inventory, InventoryBuilder};
+//! use interoptopus::patterns::string::{FFIStr, FFIString};
+//!
+//! ffi_string!(my_string_destroy);
+//!
+//! #[ffi_function]
+//! #[no_mangle]
+//! pub extern "C" fn shout(s: FFIStr) -> FFIString {
+//!     FFIString::from(s.as_str().unwrap_or_default().to_uppercase())
+//! }
+//!
+//! pub fn my_inventory() -> Inventory {
+//!     InventoryBuilder::new()
+//!         .register(function!(shout))
+//!         .register(pattern!(my_string_destroy))
+//!         .inventory()
+//! }
+//! ```
+//!
+//! Backends supporting this pattern convert from and to their native strings, and free returned strings for
+//! you, in C# something like:
+//!
+//! ```csharp
+//! string shout(string s);
+//! ```
+//!
+//! In C both become structs with a `data` pointer and a `len` in bytes, not including any NUL terminator.
+//!
+//! # Safety
+//!
+//! An [`FFIStr`] from foreign code must point to `len` bytes valid for as long as Rust borrows them. Callers must
+//! pass every [`FFIString`] they receive to the destructor exactly once, and must not change its fields. Strings
+//! handed from foreign code to Rust by value are dropped (i.e., freed) by Rust, so they must stem from this library
+//! as well.
+use crate::lang::c::{CType, CompositeType, Documentation, Field, Function, Meta, PrimitiveType, Visibility};
+use crate::lang::rust::{CTypeInfo, LayoutInfo, TypeLayout};
 use crate::patterns::TypePattern;
 use crate::Error;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use std::ffi::CStr;
 use std::marker::PhantomData;
+use std::ops::Deref;
 use std::option::Option::None;
 use std::os::raw::c_char;
 use std::ptr::null;
@@ -118,9 +162,260 @@ unsafe impl<'a> CTypeInfo for AsciiPointer<'a> {
     }
 }
 
+/// A borrowed UTF-8 string with an explicit length, which may contain NUL.
+#[repr(C)]
+#[derive(Copy, Clone, Debug)]
+pub struct FFIStr<'a> {
+    data: *const u8,
+    len: u64,
+    _phantom: PhantomData<&'a str>,
+}
+
+impl<'a> Default for FFIStr<'a> {
+    fn default() -> Self {
+        Self::from_str("")
+    }
+}
+
+impl<'a> FFIStr<'a> {
+    /// Borrows the given string.
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(s: &'a str) -> Self {
+        Self {
+            data: s.as_ptr(),
+            len: s.len() as u64,
+            _phantom: Default::default(),
+        }
+    }
+
+    /// Returns the underlying bytes, which might not be valid UTF-8 if they came from foreign code.
+    ///
+    /// Empty strings may be passed with a null pointer, only non-empty ones fail with [`Error::Null`].
+    pub fn as_bytes(&self) -> Result<&'a [u8], Error> {
+        match (self.data.is_null(), self.len) {
+            (_, 0) => Ok(&[]),
+            (true, _) => Err(Error::Null),
+            // The creator of this guarantees `data` is valid for `len` bytes for `'a`.
+            (false, len) => unsafe { Ok(std::slice::from_raw_parts(self.data, len as usize)) },
+        }
+    }
+
+    /// Attempts to return a Rust `str`.
+    pub fn as_str(&self) -> Result<&'a str, Error> {
+        Ok(std::str::from_utf8(self.as_bytes()?)?)
+    }
+}
+
+impl<'a> From<&'a str> for FFIStr<'a> {
+    fn from(s: &'a str) -> Self {
+        Self::from_str(s)
+    }
+}
+
+unsafe impl<'a> CTypeInfo for FFIStr<'a> {
+    #[rustfmt::skip]
+    fn type_info() -> CType {
+        let doc_data = Documentation::from_line("Pointer to the first byte, may be null if empty.");
+        let doc_len = Documentation::from_line("Number of bytes.");
+
+        let fields = vec![
+            Field::with_documentation("data".to_string(), CType::ReadPointer(Box::new(CType::Primitive(PrimitiveType::U8))), Visibility::Private, doc_data),
+            Field::with_documentation("len".to_string(), CType::Primitive(PrimitiveType::U64), Visibility::Private, doc_len),
+        ];
+
+        let doc = Documentation::from_line("A UTF-8 string borrowed for the duration of a call, not necessarily NUL terminated.");
+        let composite = CompositeType::with_meta("Utf8Str".to_string(), fields, Meta::with_documentation(doc));
+        CType::Pattern(TypePattern::Str(composite))
+    }
+}
+
+impl<'a> LayoutInfo for FFIStr<'a> {
+    fn layout_info() -> TypeLayout {
+        crate::__layout_info!(data, len)
+    }
+}
+
+/// An owned UTF-8 string passed over an FFI boundary, freed via a destructor defined by [`ffi_string`](crate::ffi_string).
+#[repr(C)]
+#[derive(Debug)]
+pub struct FFIString {
+    data: *mut u8,
+    len: u64,
+    capacity: u64,
+}
+
+impl FFIString {
+    /// Takes ownership of the given string.
+    pub fn from_string(s: String) -> Self {
+        let mut s = std::mem::ManuallyDrop::new(s.into_bytes());
+
+        Self {
+            data: s.as_mut_ptr(),
+            len: s.len() as u64,
+            capacity: s.capacity() as u64,
+        }
+    }
+
+    /// Turns this back into a regular string.
+    pub fn into_string(self) -> String {
+        let this = std::mem::ManuallyDrop::new(self);
+
+        if this.data.is_null() {
+            String::new()
+        } else {
+            // Strings from foreign code must stem from `from_string` unchanged, see `# Safety` in the module docs.
+            unsafe { String::from_raw_parts(this.data, this.len as usize, this.capacity as usize) }
+        }
+    }
+
+    /// Returns the contained string.
+    pub fn as_str(&self) -> &str {
+        if self.data.is_null() {
+            ""
+        } else {
+            unsafe { std::str::from_utf8_unchecked(std::slice::from_raw_parts(self.data, self.len as usize)) }
+        }
+    }
+}
+
+impl Default for FFIString {
+    fn default() -> Self {
+        Self::from_string(String::new())
+    }
+}
+
+impl From<String> for FFIString {
+    fn from(s: String) -> Self {
+        Self::from_string(s)
+    }
+}
+
+impl From<&str> for FFIString {
+    fn from(s: &str) -> Self {
+        Self::from_string(s.to_string())
+    }
+}
+
+impl Drop for FFIString {
+    fn drop(&mut self) {
+        if !self.data.is_null() {
+            unsafe { drop(String::from_raw_parts(self.data, self.len as usize, self.capacity as usize)) }
+        }
+    }
+}
+
+impl Deref for FFIString {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        self.as_str()
+    }
+}
+
+impl FFIString {
+    /// The struct this string is represented by.
+    #[doc(hidden)]
+    #[rustfmt::skip]
+    pub fn composite_type() -> CompositeType {
+        let doc_data = Documentation::from_line("Pointer to the first byte of owned data.");
+        let doc_len = Documentation::from_line("Number of bytes.");
+        let doc_capacity = Documentation::from_line("Number of bytes allocated.");
+
+        let fields = vec![
+            Field::with_documentation("data".to_string(), CType::ReadWritePointer(Box::new(CType::Primitive(PrimitiveType::U8))), Visibility::Private, doc_data),
+            Field::with_documentation("len".to_string(), CType::Primitive(PrimitiveType::U64), Visibility::Private, doc_len),
+            Field::with_documentation("capacity".to_string(), CType::Primitive(PrimitiveType::U64), Visibility::Private, doc_capacity),
+        ];
+
+        let doc = Documentation::from_line("A UTF-8 string owned by this library, not NUL terminated, must be freed by the caller.");
+        CompositeType::with_meta("Utf8String".to_string(), fields, Meta::with_documentation(doc))
+    }
+}
+
+unsafe impl CTypeInfo for FFIString {
+    fn type_info() -> CType {
+        CType::Pattern(TypePattern::String(Self::composite_type()))
+    }
+}
+
+impl LayoutInfo for FFIString {
+    fn layout_info() -> TypeLayout {
+        crate::__layout_info!(data, len, capacity)
+    }
+}
+
+/// The function freeing [`FFIString`]s, produced by [`ffi_string`](crate::ffi_string).
+#[derive(Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct StringPattern {
+    destructor: Function,
+}
+
+impl StringPattern {
+    pub fn new(destructor: Function) -> Self {
+        Self { destructor }
+    }
+
+    /// The struct representing the string, contained in a [`TypePattern::String`] in signatures.
+    pub fn the_type(&self) -> CompositeType {
+        FFIString::composite_type()
+    }
+
+    /// The function freeing a string, accepting it as its only parameter.
+    pub fn destructor(&self) -> &Function {
+        &self.destructor
+    }
+}
+
+/// Defines a destructor for [`FFIString`](crate::patterns::string::FFIString).
+///
+/// The generated `#[no_mangle]` function has the given name and accepts a single `s` parameter it frees.
+/// Register it via [`pattern`](crate::pattern), see the [**string module**](crate::patterns::string) for an example.
+///
+/// # Example
+///
+/// ```
+/// use interoptopus::ffi_string;
+///
+/// ffi_string!(my_string_destroy);
+/// ```
+///
+/// The generated function is similar to:
+///
+/// ```
+/// # use interoptopus::patterns::string::FFIString;
+/// #[no_mangle]
+/// pub extern "C" fn my_string_destroy(s: FFIString) {
+///     drop(s);
+/// }
+/// ```
+#[macro_export]
+macro_rules! ffi_string {
+    ($destructor:ident) => {
+        /// Frees a string returned by this library.
+        #[interoptopus::ffi_function]
+        #[no_mangle]
+        pub extern "C" fn $destructor(s: interoptopus::patterns::string::FFIString) {
+            ::std::mem::drop(s);
+        }
+
+        impl interoptopus::patterns::LibraryPatternInfo for $destructor {
+            fn pattern_info() -> interoptopus::patterns::LibraryPattern {
+                use interoptopus::lang::rust::FunctionInfo;
+
+                let destructor = <$destructor as FunctionInfo>::function_info();
+
+                interoptopus::patterns::string::StringPattern::new(destructor).into()
+            }
+        }
+
+        interoptopus::__register_pattern!($destructor);
+    };
+}
+
 #[cfg(test)]
 mod test {
-    use crate::patterns::string::AsciiPointer;
+    use crate::patterns::string::{AsciiPointer, FFIStr, FFIString};
     use std::ffi::CString;
 
     #[test]
@@ -148,4 +443,31 @@ mod test {
 
         assert!(ptr_some.is_err());
     }
+
+    #[test]
+    fn ffi_str_keeps_nul() {
+        let s = "hello\0world";
+
+        assert_eq!(FFIStr::from_str(s).as_str().unwrap(), s);
+    }
+
+    #[test]
+    fn ffi_str_rejects_invalid() {
+        let bytes = [0xff_u8, 0xfe];
+        let ffi_str = FFIStr {
+            data: bytes.as_ptr(),
+            len: 2,
+            _phantom: Default::default(),
+        };
+
+        assert!(ffi_str.as_str().is_err());
+    }
+
+    #[test]
+    fn ffi_string_round_trips() {
+        let s = FFIString::from("grüße\0");
+
+        assert_eq!(s.as_str(), "grüße\0");
+        assert_eq!(s.into_string(), "grüße\0");
+    }
 }
@@ -0,0 +1,299 @@
+//! Like a regular `Vec<T>` but FFI safe, handing ownership of a buffer to the caller.
+//!
+//! An FFI vec is a pointer, length and capacity owned by the Rust allocator. Returning it moves the buffer to
+//! the caller, who must hand it back to a _destructor_ of your library once done. The [`ffi_vec`](crate::ffi_vec)
+//! macro defines such a destructor for an element type, which you then register like other library patterns.
+//!
+//! # Example
+//!
+//! Here we return a variable number of `u32`, and define `my_vec_u32_destroy` to free them:
+//!
+//! ```
+//! use interoptopus::{ffi_function, ffi_vec, function, pattern, Inventory, InventoryBuilder};
+//! use interoptopus::patterns::vec::FFIVec;
+//!
+//! ffi_vec!(my_vec_u32_destroy, u32);
+//!
+//! #[ffi_function]
+//! #[no_mangle]
+//! pub extern "C" fn primes_below(n: u32) -> FFIVec<u32> {
+//!     let primes = (2..n).filter(|x| (2..*x).all(|d| x % d != 0)).collect::<Vec<_>>();
+//!     FFIVec::from_vec(primes)
+//! }
+//!
+//! pub fn my_inventory() -> Inventory {
+//!     InventoryBuilder::new()
+//!         .register(function!(primes_below))
+//!         .register(pattern!(my_vec_u32_destroy))
+//!         .inventory()
+//! }
+//! ```
+//!
+//! Backends supporting this pattern copy the elements into a native collection and call the destructor
+//! for you, in C# something like:
+//!
+//! ```csharp
+//! uint[] primes = Interop.primes_below_checked(100);
+//! ```
+//!
+//! In C and unsupported backends the equivalent of this code will be emitted:
+//!
+//! ```c
+//! typedef struct vecu32
+//!     {
+//!     uint32_t* data;
+//!     uint64_t len;
+//!     uint64_t capacity;
+//!     } vecu32;
+//!
+//! vecu32 primes_below(uint32_t n);
+//! void my_vec_u32_destroy(vecu32 vec);
+//! ```
+//!
+//! # Safety
+//!
+//! Callers must pass every vec they receive to the destructor exactly once, and must not change its fields.
+//! Vecs handed from foreign code to Rust by value are dropped (i.e., freed) by Rust, so they must stem from
+//! this library as well.
+
+use crate::lang::c::{CType, CompositeType, Documentation, Field, Function, Meta, PrimitiveType, Visibility};
+use crate::lang::rust::{CTypeInfo, LayoutInfo, TypeLayout};
+use crate::patterns::TypePattern;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::mem::ManuallyDrop;
+use std::ops::{Deref, DerefMut};
+
+/// An owned array passed over an FFI boundary, freed via a destructor defined by [`ffi_vec`](crate::ffi_vec).
+#[repr(C)]
+pub struct FFIVec<T> {
+    data: *mut T,
+    len: u64,
+    capacity: u64,
+}
+
+impl<T> FFIVec<T> {
+    /// Takes ownership of the given vec.
+    pub fn from_vec(vec: Vec<T>) -> Self {
+        let mut vec = ManuallyDrop::new(vec);
+
+        Self {
+            data: vec.as_mut_ptr(),
+            len: vec.len() as u64,
+            capacity: vec.capacity() as u64,
+        }
+    }
+
+    /// Creates a new empty vec.
+    pub fn empty() -> Self {
+        Self::from_vec(Vec::new())
+    }
+
+    /// Turns this back into a regular vec.
+    pub fn into_vec(self) -> Vec<T> {
+        let this = ManuallyDrop::new(self);
+
+        if this.data.is_null() {
+            Vec::new()
+        } else {
+            // Vecs from foreign code must stem from `from_vec` unchanged, see `# Safety` in the module docs.
+            unsafe { Vec::from_raw_parts(this.data, this.len as usize, this.capacity as usize) }
+        }
+    }
+
+    /// Returns the contained elements.
+    pub fn as_slice(&self) -> &[T] {
+        if self.data.is_null() {
+            &[]
+        } else {
+            unsafe { std::slice::from_raw_parts(self.data, self.len as usize) }
+        }
+    }
+
+    /// Returns the contained elements.
+    pub fn as_slice_mut(&mut self) -> &mut [T] {
+        if self.data.is_null() {
+            &mut []
+        } else {
+            unsafe { std::slice::from_raw_parts_mut(self.data, self.len as usize) }
+        }
+    }
+}
+
+impl<T> Default for FFIVec<T> {
+    fn default() -> Self {
+        Self::empty()
+    }
+}
+
+impl<T> From<Vec<T>> for FFIVec<T> {
+    fn from(vec: Vec<T>) -> Self {
+        Self::from_vec(vec)
+    }
+}
+
+impl<T> Drop for FFIVec<T> {
+    fn drop(&mut self) {
+        if !self.data.is_null() {
+            unsafe { drop(Vec::from_raw_parts(self.data, self.len as usize, self.capacity as usize)) }
+        }
+    }
+}
+
+impl<T> Deref for FFIVec<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &Self::Target {
+        self.as_slice()
+    }
+}
+
+impl<T> DerefMut for FFIVec<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.as_slice_mut()
+    }
+}
+
+impl<T> FFIVec<T>
+where
+    T: CTypeInfo,
+{
+    /// The struct this vec is represented by.
+    #[doc(hidden)]
+    #[rustfmt::skip]
+    pub fn composite_type() -> CompositeType {
+        let doc_data = Documentation::from_line("Pointer to start of owned data.");
+        let doc_len = Documentation::from_line("Number of elements.");
+        let doc_capacity = Documentation::from_line("Number of elements allocated.");
+
+        let fields = vec![
+            Field::with_documentation("data".to_string(), CType::ReadWritePointer(Box::new(T::type_info())), Visibility::Private, doc_data),
+            Field::with_documentation("len".to_string(), CType::Primitive(PrimitiveType::U64), Visibility::Private, doc_len),
+            Field::with_documentation("capacity".to_string(), CType::Primitive(PrimitiveType::U64), Visibility::Private, doc_capacity),
+        ];
+
+        let doc = Documentation::from_line("An array of data owned by this library, must be freed by the caller.");
+        let meta = Meta::with_namespace_documentation(T::type_info().namespace().map(|e| e.into()).unwrap_or_else(String::new), doc);
+        CompositeType::with_meta(format!("Vec{}", T::type_info().name_within_lib()), fields, meta)
+    }
+}
+
+unsafe impl<T> CTypeInfo for FFIVec<T>
+where
+    T: CTypeInfo,
+{
+    fn type_info() -> CType {
+        CType::Pattern(TypePattern::Vec(Self::composite_type()))
+    }
+}
+
+impl<T> LayoutInfo for FFIVec<T>
+where
+    T: CTypeInfo,
+{
+    fn layout_info() -> TypeLayout {
+        crate::__layout_info!(data, len, capacity)
+    }
+}
+
+/// An [`FFIVec`] type and the function freeing it, produced by [`ffi_vec`](crate::ffi_vec).
+#[derive(Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+pub struct VecPattern {
+    the_type: CompositeType,
+    destructor: Function,
+}
+
+impl VecPattern {
+    pub fn new(the_type: CompositeType, destructor: Function) -> Self {
+        Self { the_type, destructor }
+    }
+
+    /// The struct representing the vec, contained in a [`TypePattern::Vec`] in signatures.
+    pub fn the_type(&self) -> &CompositeType {
+        &self.the_type
+    }
+
+    /// The type of the vec's elements.
+    pub fn element_type(&self) -> &CType {
+        vec_element_type(&self.the_type)
+    }
+
+    /// The function freeing a vec, accepting it as its only parameter.
+    pub fn destructor(&self) -> &Function {
+        &self.destructor
+    }
+}
+
+/// Returns the element type of a [`TypePattern::Vec`] struct.
+///
+/// # Panics
+///
+/// Panics if `the_type` was not produced by [`FFIVec`].
+pub fn vec_element_type(the_type: &CompositeType) -> &CType {
+    match the_type.fields().first().map(|x| x.the_type()) {
+        Some(CType::ReadWritePointer(x)) => x,
+        _ => panic!("Vec type `{}` must start with a data pointer.", the_type.rust_name()),
+    }
+}
+
+/// Defines a destructor for [`FFIVec<T>`](crate::patterns::vec::FFIVec) of the given element type.
+///
+/// The generated `#[no_mangle]` function has the given name and accepts a single `vec` parameter it frees.
+/// Register it via [`pattern`](crate::pattern), see the [**vec module**](crate::patterns::vec) for an example.
+///
+/// # Example
+///
+/// ```
+/// use interoptopus::ffi_vec;
+///
+/// ffi_vec!(my_vec_u8_destroy, u8);
+/// ```
+///
+/// The generated function is similar to:
+///
+/// ```
+/// # use interoptopus::patterns::vec::FFIVec;
+/// #[no_mangle]
+/// pub extern "C" fn my_vec_u8_destroy(vec: FFIVec<u8>) {
+///     drop(vec);
+/// }
+/// ```
+#[macro_export]
+macro_rules! ffi_vec {
+    ($destructor:ident, $t:ty) => {
+        /// Frees a vec returned by this library.
+        #[interoptopus::ffi_function]
+        #[no_mangle]
+        pub extern "C" fn $destructor(vec: interoptopus::patterns::vec::FFIVec<$t>) {
+            ::std::mem::drop(vec);
+        }
+
+        impl interoptopus::patterns::LibraryPatternInfo for $destructor {
+            fn pattern_info() -> interoptopus::patterns::LibraryPattern {
+                use interoptopus::lang::rust::FunctionInfo;
+
+                let the_type = interoptopus::patterns::vec::FFIVec::<$t>::composite_type();
+                let destructor = <$destructor as FunctionInfo>::function_info();
+
+                interoptopus::patterns::vec::VecPattern::new(the_type, destructor).into()
+            }
+        }
+
+        interoptopus::__register_pattern!($destructor);
+    };
+}
+
+#[cfg(test)]
+mod test {
+    use crate::patterns::vec::FFIVec;
+
+    #[test]
+    fn can_round_trip() {
+        let vec = FFIVec::from_vec(vec![1, 2, 3]);
+
+        assert_eq!(vec.as_slice(), &[1, 2, 3]);
+        assert_eq!(vec.into_vec(), vec![1, 2, 3]);
+        assert!(FFIVec::<u8>::empty().is_empty());
+    }
+}
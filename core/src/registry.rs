@@ -0,0 +1,110 @@
+//! Registers FFI items automatically, so you don't have to list them in an inventory function.
+//!
+//! Listing every item via [`InventoryBuilder::register`] gets tedious for large libraries, and forgetting one silently
+//! drops it from all bindings. With the `registry` feature enabled, [`#[ffi_function]`](crate::ffi_function),
+//! [`#[ffi_constant]`](crate::ffi_constant), [`#[ffi_type]`](crate::ffi_type) and [`#[ffi_service]`](crate::ffi_service)
+//! also record their item in a global registry, from which [`InventoryBuilder::register_module`] and
+//! [`InventoryBuilder::register_where`] pick what they need.
+//!
+//! # Example
+//!
+//! ```
+//! # #[cfg(feature = "registry")]
+//! # mod x {
+//! use interoptopus::{ffi_function, ffi_type, Inventory, InventoryBuilder};
+//!
+//! pub mod ffi {
+//!     use interoptopus::{ffi_function, ffi_type};
+//!
+//!     #[ffi_type]
+//!     #[repr(C)]
+//!     pub struct Vec2 {
+//!         pub x: f32,
+//!         pub y: f32,
+//!     }
+//!
+//!     #[ffi_function]
+//!     #[no_mangle]
+//!     pub extern "C" fn my_function(input: Vec2) -> Vec2 {
+//!         input
+//!     }
+//! }
+//!
+//! // Contains `my_function` and `Vec2`, and whatever else is added to `ffi` later on.
+//! pub fn my_inventory() -> Inventory {
+//!     InventoryBuilder::new().register_module(concat!(module_path!(), "::ffi")).inventory()
+//! }
+//! # }
+//! ```
+//!
+//! # Caveats
+//!
+//! - Items are recorded by static constructors, which the linker might drop if nothing else in the same object file
+//!   is used. This is rarely a problem for FFI items (they are exported), but if items seem to be missing, check
+//!   them against [`Inventory::validate`](crate::Inventory::validate) or a manual inventory.
+//! - Generic types can't register themselves, as we don't know their type arguments; they are still picked up when
+//!   used by a registered function.
+//! - Items are registered in source order, so bindings don't change between builds.
+use crate::Symbol;
+
+#[doc(hidden)]
+pub use inventory::submit;
+
+/// An item recorded by one of the proc macros.
+pub struct Registration {
+    module_path: &'static str,
+    file: &'static str,
+    line: u32,
+    symbol: fn() -> Symbol,
+}
+
+impl Registration {
+    #[doc(hidden)]
+    pub const fn new(module_path: &'static str, file: &'static str, line: u32, symbol: fn() -> Symbol) -> Self {
+        Self { module_path, file, line, symbol }
+    }
+
+    /// The module the item was defined in, e.g., `my_crate::ffi`.
+    pub fn module_path(&self) -> &'static str {
+        self.module_path
+    }
+
+    /// Returns `true` if the item was defined in module `path` or one of its submodules.
+    pub fn is_in_module(&self, path: &str) -> bool {
+        match self.module_path.strip_prefix(path) {
+            Some(rest) => rest.is_empty() || rest.starts_with("::"),
+            None => false,
+        }
+    }
+
+    /// Produces the item's [`Symbol`].
+    pub fn symbol(&self) -> Symbol {
+        (self.symbol)()
+    }
+}
+
+inventory::collect!(Registration);
+
+/// Returns all registered items in source order.
+pub(crate) fn registrations() -> Vec<&'static Registration> {
+    let mut rval = inventory::iter::<Registration>.into_iter().collect::<Vec<_>>();
+    rval.sort_by_key(|x| (x.file, x.line));
+    rval
+}
+
+#[cfg(test)]
+mod test {
+    use crate::lang::c::CType;
+    use crate::registry::Registration;
+    use crate::Symbol;
+
+    #[test]
+    fn is_in_module() {
+        let registration = Registration::new("my_crate::ffi", file!(), line!(), || Symbol::Type(CType::void()));
+
+        assert!(registration.is_in_module("my_crate"));
+        assert!(registration.is_in_module("my_crate::ffi"));
+        assert!(!registration.is_in_module("my_crate::ff"));
+        assert!(!registration.is_in_module("my_crate::ffi::inner"));
+    }
+}
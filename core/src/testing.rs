@@ -2,11 +2,16 @@
 
 use std::fs::read_to_string;
 
+pub mod exports;
+pub mod snapshot;
+
 /// Used by backends to verify a `file.ext` matches an existing `file.ext.expected`.
+///
+/// Prints a diff on mismatch, and updates `file.ext.expected` instead if [`UPDATE_SNAPSHOTS`](snapshot::UPDATE_SNAPSHOTS)
+/// is set, see [`snapshot`] for details.
 #[track_caller]
 pub fn assert_file_matches_generated(file: &str) {
     let actual = read_to_string(file).unwrap_or_else(|_| panic!("Must be able to read file '{}'", file));
-    let expected = read_to_string(format!("{}.expected", file)).unwrap_or_else(|_| panic!("Must be able to read pre-generated file for '{}'", file));
 
-    assert_eq!(expected, actual);
+    snapshot::assert_matches_snapshot(&actual, format!("{}.expected", file));
 }
@@ -0,0 +1,284 @@
+//! Checks that a built library actually exports the functions of its inventory.
+//!
+//! A function registered via [`function!`](crate::function) but lacking `#[no_mangle]` or `pub` compiles fine, the
+//! resulting bindings however fail once C# or Python try to load the symbol. [`compare_exports`] reads the dynamic
+//! symbol table of a compiled ELF library (e.g., a Linux `.so`) and reports such functions, as well as exported
+//! functions the inventory does not know about:
+//!
+//! ```no_run
+//! use interoptopus::testing::exports::assert_exports_match;
+//! # use interoptopus::Inventory;
+//! # fn my_inventory() -> Inventory { Inventory::default() }
+//!
+//! assert_exports_match(&my_inventory(), "target/debug/libmy_library.so");
+//! ```
+//!
+//! Only functions with default or protected visibility and global or weak binding are considered exported, the symbol
+//! of [`embed_inventory`](crate::embed_inventory) is ignored.
+use crate::{Error, Inventory};
+use std::collections::BTreeSet;
+use std::convert::TryFrom;
+use std::fmt::{Display, Formatter};
+use std::path::Path;
+
+const SHT_DYNSYM: u32 = 11;
+const STT_FUNC: u8 = 2;
+const STB_GLOBAL: u8 = 1;
+const STB_WEAK: u8 = 2;
+const STV_DEFAULT: u8 = 0;
+const STV_PROTECTED: u8 = 3;
+const SHN_UNDEF: u16 = 0;
+
+/// Symbol exported by [`embed_inventory`](crate::embed_inventory), which is never part of an inventory.
+const INVENTORY_SYMBOL: &str = "interoptopus_inventory_json";
+
+/// Differences between the functions of an inventory and the ones exported by a library.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ExportMismatch {
+    missing: Vec<String>,
+    unregistered: Vec<String>,
+}
+
+impl ExportMismatch {
+    /// Inventory functions the library does not export, usually lacking `#[no_mangle]` or `pub`.
+    pub fn missing(&self) -> &[String] {
+        &self.missing
+    }
+
+    /// Functions exported by the library but missing from the inventory.
+    pub fn unregistered(&self) -> &[String] {
+        &self.unregistered
+    }
+
+    /// Returns `true` if inventory and library agree.
+    pub fn is_empty(&self) -> bool {
+        self.missing.is_empty() && self.unregistered.is_empty()
+    }
+}
+
+impl Display for ExportMismatch {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for x in &self.missing {
+            writeln!(f, "fn {}: in inventory but not exported", x)?;
+        }
+
+        for x in &self.unregistered {
+            writeln!(f, "fn {}: exported but not in inventory", x)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns the names of all functions exported by the ELF library at `path`, sorted by name.
+pub fn exported_functions<P: AsRef<Path>>(path: P) -> Result<Vec<String>, Error> {
+    let bytes = std::fs::read(path)?;
+    let elf = Elf::new(&bytes)?;
+
+    elf.exported_functions()
+}
+
+/// Compares the functions of `inventory` with the ones exported by the ELF library at `path`.
+pub fn compare_exports<P: AsRef<Path>>(inventory: &Inventory, path: P) -> Result<ExportMismatch, Error> {
+    let exported = exported_functions(path)?.into_iter().collect::<BTreeSet<_>>();
+    let registered = inventory.functions().iter().map(|x| x.name().to_string()).collect::<BTreeSet<_>>();
+
+    Ok(ExportMismatch {
+        missing: registered.difference(&exported).cloned().collect(),
+        unregistered: exported.difference(&registered).filter(|x| *x != INVENTORY_SYMBOL).cloned().collect(),
+    })
+}
+
+/// Panics if the functions of `inventory` and the ones exported by the ELF library at `path` differ.
+#[track_caller]
+pub fn assert_exports_match<P: AsRef<Path>>(inventory: &Inventory, path: P) {
+    let path = path.as_ref();
+    let mismatch = compare_exports(inventory, path).unwrap_or_else(|e| panic!("Must be able to read symbols of '{}': {:?}", path.display(), e));
+
+    assert!(mismatch.is_empty(), "Exports of '{}' don't match inventory:\n{}", path.display(), mismatch);
+}
+
+/// Just enough of an ELF reader to walk the dynamic symbol table.
+struct Elf<'a> {
+    bytes: &'a [u8],
+    is_64: bool,
+    is_le: bool,
+}
+
+/// The fields of a section header we care about.
+struct Section {
+    kind: u32,
+    offset: usize,
+    size: usize,
+    link: usize,
+    entry_size: usize,
+}
+
+impl<'a> Elf<'a> {
+    fn new(bytes: &'a [u8]) -> Result<Self, Error> {
+        if bytes.len() < 16 || &bytes[0..4] != b"\x7fELF" {
+            return Err(Error::InvalidLibrary);
+        }
+
+        let is_64 = match bytes[4] {
+            1 => false,
+            2 => true,
+            _ => return Err(Error::InvalidLibrary),
+        };
+
+        let is_le = match bytes[5] {
+            1 => true,
+            2 => false,
+            _ => return Err(Error::InvalidLibrary),
+        };
+
+        Ok(Self { bytes, is_64, is_le })
+    }
+
+    fn slice(&self, offset: usize, len: usize) -> Result<&'a [u8], Error> {
+        let end = offset.checked_add(len).ok_or(Error::InvalidLibrary)?;
+        self.bytes.get(offset..end).ok_or(Error::InvalidLibrary)
+    }
+
+    fn u8(&self, offset: usize) -> Result<u8, Error> {
+        Ok(self.slice(offset, 1)?[0])
+    }
+
+    fn u16(&self, offset: usize) -> Result<u16, Error> {
+        let mut x = [0; 2];
+        x.copy_from_slice(self.slice(offset, 2)?);
+        Ok(if self.is_le { u16::from_le_bytes(x) } else { u16::from_be_bytes(x) })
+    }
+
+    fn u32(&self, offset: usize) -> Result<u32, Error> {
+        let mut x = [0; 4];
+        x.copy_from_slice(self.slice(offset, 4)?);
+        Ok(if self.is_le { u32::from_le_bytes(x) } else { u32::from_be_bytes(x) })
+    }
+
+    fn u64(&self, offset: usize) -> Result<u64, Error> {
+        let mut x = [0; 8];
+        x.copy_from_slice(self.slice(offset, 8)?);
+        Ok(if self.is_le { u64::from_le_bytes(x) } else { u64::from_be_bytes(x) })
+    }
+
+    /// Reads a pointer-sized value, `u32` for ELF32 and `u64` for ELF64.
+    fn word(&self, offset: usize) -> Result<usize, Error> {
+        let x = if self.is_64 { self.u64(offset)? } else { u64::from(self.u32(offset)?) };
+        usize::try_from(x).map_err(|_| Error::InvalidLibrary)
+    }
+
+    /// The offset of entry `i` in the table at `offset`, making sure the whole entry lies within the library.
+    fn entry(&self, offset: usize, i: usize, entry_size: usize) -> Result<usize, Error> {
+        let start = i.checked_mul(entry_size).and_then(|x| x.checked_add(offset)).ok_or(Error::InvalidLibrary)?;
+        self.slice(start, entry_size)?;
+        Ok(start)
+    }
+
+    fn sections(&self) -> Result<Vec<Section>, Error> {
+        let (offset, entry_size, count) = if self.is_64 {
+            (self.word(0x28)?, self.u16(0x3a)?, self.u16(0x3c)?)
+        } else {
+            (self.word(0x20)?, self.u16(0x2e)?, self.u16(0x30)?)
+        };
+
+        (0..usize::from(count))
+            .map(|i| {
+                let header = self.entry(offset, i, usize::from(entry_size))?;
+
+                if self.is_64 {
+                    Ok(Section {
+                        kind: self.u32(header + 4)?,
+                        offset: self.word(header + 24)?,
+                        size: self.word(header + 32)?,
+                        link: self.u32(header + 40)? as usize,
+                        entry_size: self.word(header + 56)?,
+                    })
+                } else {
+                    Ok(Section {
+                        kind: self.u32(header + 4)?,
+                        offset: self.word(header + 16)?,
+                        size: self.word(header + 20)?,
+                        link: self.u32(header + 24)? as usize,
+                        entry_size: self.word(header + 36)?,
+                    })
+                }
+            })
+            .collect()
+    }
+
+    /// Reads the `NUL` terminated string at `offset` of the string table `strings`.
+    fn string(&self, strings: &Section, offset: usize) -> Result<String, Error> {
+        let table = self.slice(strings.offset, strings.size)?;
+        let tail = table.get(offset..).ok_or(Error::InvalidLibrary)?;
+        let len = tail.iter().position(|x| *x == 0).ok_or(Error::InvalidLibrary)?;
+
+        Ok(std::str::from_utf8(&tail[..len])?.to_string())
+    }
+
+    fn exported_functions(&self) -> Result<Vec<String>, Error> {
+        let sections = self.sections()?;
+        let mut rval = BTreeSet::new();
+
+        for symbols in sections.iter().filter(|x| x.kind == SHT_DYNSYM) {
+            let strings = sections.get(symbols.link).ok_or(Error::InvalidLibrary)?;
+
+            if symbols.entry_size == 0 {
+                return Err(Error::InvalidLibrary);
+            }
+
+            for i in 0..symbols.size / symbols.entry_size {
+                let symbol = self.entry(symbols.offset, i, symbols.entry_size)?;
+
+                let (name, info, other, section) = if self.is_64 {
+                    (self.u32(symbol)?, self.u8(symbol + 4)?, self.u8(symbol + 5)?, self.u16(symbol + 6)?)
+                } else {
+                    (self.u32(symbol)?, self.u8(symbol + 12)?, self.u8(symbol + 13)?, self.u16(symbol + 14)?)
+                };
+
+                let is_function = info & 0xf == STT_FUNC;
+                let is_global = matches!(info >> 4, STB_GLOBAL | STB_WEAK);
+                let is_visible = matches!(other & 0x3, STV_DEFAULT | STV_PROTECTED);
+
+                if is_function && is_global && is_visible && section != SHN_UNDEF {
+                    rval.insert(self.string(strings, name as usize)?);
+                }
+            }
+        }
+
+        Ok(rval.into_iter().collect())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::testing::exports::Elf;
+    use crate::Error;
+
+    #[test]
+    fn rejects_non_elf_files() {
+        assert!(matches!(Elf::new(b"MZ\x90\x00"), Err(Error::InvalidLibrary)));
+        assert!(matches!(
+            Elf::new(b"\x7fELF\x03\x01\x01\x00\x00\x00\x00\x00\x00\x00\x00\x00"),
+            Err(Error::InvalidLibrary)
+        ));
+    }
+
+    #[test]
+    fn rejects_truncated_files() {
+        let elf = Elf::new(b"\x7fELF\x02\x01\x01\x00\x00\x00\x00\x00\x00\x00\x00\x00").unwrap();
+        assert!(matches!(elf.exported_functions(), Err(Error::InvalidLibrary)));
+    }
+
+    #[test]
+    fn rejects_overflowing_offsets() {
+        let mut bytes = b"\x7fELF\x02\x01\x01\x00\x00\x00\x00\x00\x00\x00\x00\x00".to_vec();
+        bytes.resize(64, 0);
+        bytes[0x28..0x30].copy_from_slice(&(u64::MAX - 8).to_le_bytes());
+        bytes[0x3a] = 64;
+        bytes[0x3c] = 2;
+
+        let elf = Elf::new(&bytes).unwrap();
+        assert!(matches!(elf.exported_functions(), Err(Error::InvalidLibrary)));
+    }
+}
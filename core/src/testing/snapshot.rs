@@ -0,0 +1,244 @@
+//! Compares generated bindings against checked-in `.expected` files.
+//!
+//! On a mismatch the assertions print a unified diff of the two, instead of both files in full. When the
+//! [`UPDATE_SNAPSHOTS`] environment variable is set (to anything but `0`) they rewrite the `.expected` file instead,
+//! so after an intended change to a backend you can run
+//!
+//! ```text
+//! INTEROPTOPUS_UPDATE_SNAPSHOTS=1 cargo test
+//! ```
+//!
+//! and review the changes with `git diff`. Bindings can be compared without writing them to disk first:
+//!
+//! ```no_run
+//! use interoptopus::testing::snapshot::assert_generated_matches_snapshot;
+//! # use interoptopus::Interop;
+//! # fn f(generator: impl Interop) {
+//!
+//! assert_generated_matches_snapshot(&generator, "tests/output/bindings.py.expected");
+//! # }
+//! ```
+use crate::writer::IndentWriter;
+use crate::Interop;
+use std::fmt::Write;
+use std::path::Path;
+
+/// Environment variable which, if set, makes snapshot assertions update `.expected` files instead of failing.
+pub const UPDATE_SNAPSHOTS: &str = "INTEROPTOPUS_UPDATE_SNAPSHOTS";
+
+/// Lines of context around each change in a diff.
+const CONTEXT_LINES: usize = 3;
+
+/// Above this many compared line pairs diffs fall back to replacing the whole differing region.
+const MAX_DIFF_CELLS: usize = 16_000_000;
+
+/// Returns `true` if snapshots should be updated, see [`UPDATE_SNAPSHOTS`].
+pub fn update_snapshots() -> bool {
+    std::env::var_os(UPDATE_SNAPSHOTS).map(|x| x != "0" && !x.is_empty()).unwrap_or(false)
+}
+
+/// Asserts `actual` matches the contents of `expected_file`, printing a diff otherwise.
+///
+/// With [`UPDATE_SNAPSHOTS`] set, writes `actual` to `expected_file` instead.
+#[track_caller]
+pub fn assert_matches_snapshot<P: AsRef<Path>>(actual: &str, expected_file: P) {
+    let expected_file = expected_file.as_ref();
+
+    if update_snapshots() {
+        std::fs::write(expected_file, actual).unwrap_or_else(|e| panic!("Must be able to update snapshot '{}': {}", expected_file.display(), e));
+        return;
+    }
+
+    let expected = std::fs::read_to_string(expected_file).unwrap_or_else(|e| {
+        panic!(
+            "Must be able to read snapshot '{}' ({}), run with {}=1 to create it",
+            expected_file.display(),
+            e,
+            UPDATE_SNAPSHOTS
+        )
+    });
+
+    if expected != actual {
+        panic!(
+            "Snapshot '{}' does not match, run with {}=1 to update it:\n{}",
+            expected_file.display(),
+            UPDATE_SNAPSHOTS,
+            unified_diff(&expected, actual)
+        );
+    }
+}
+
+/// Asserts the bindings `generator` writes match the contents of `expected_file`, see [`assert_matches_snapshot`].
+#[track_caller]
+pub fn assert_generated_matches_snapshot<P: AsRef<Path>>(generator: &impl Interop, expected_file: P) {
+    let mut buffer = Vec::new();
+    let mut writer = IndentWriter::new(&mut buffer);
+
+    generator
+        .write_to(&mut writer)
+        .unwrap_or_else(|e| panic!("Must be able to generate bindings: {:?}", e));
+
+    let actual = String::from_utf8(buffer).expect("Bindings must be valid UTF-8.");
+
+    assert_matches_snapshot(&actual, expected_file);
+}
+
+/// A line of a diff.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Line<'a> {
+    Same(&'a str),
+    Removed(&'a str),
+    Added(&'a str),
+}
+
+/// Produces a unified diff (`---`, `+++`, `@@ ... @@` hunks) turning `expected` into `actual`.
+///
+/// Returns an empty string if both are equal.
+pub fn unified_diff(expected: &str, actual: &str) -> String {
+    let old = expected.lines().collect::<Vec<_>>();
+    let new = actual.lines().collect::<Vec<_>>();
+    let lines = diff_lines(&old, &new);
+    let mut rval = String::new();
+
+    if lines.iter().all(|x| matches!(x, Line::Same(_))) {
+        if expected != actual {
+            rval.push_str("(files only differ in line endings or trailing newline)\n");
+        }
+
+        return rval;
+    }
+
+    writeln!(rval, "--- expected").unwrap();
+    writeln!(rval, "+++ actual").unwrap();
+
+    let changed = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, x)| !matches!(x, Line::Same(_)))
+        .map(|(i, _)| i)
+        .collect::<Vec<_>>();
+    let mut hunk_start = 0;
+
+    // Group changes closer than twice the context into one hunk.
+    for (i, index) in changed.iter().enumerate() {
+        let is_last = i + 1 == changed.len();
+
+        if is_last || changed[i + 1] - index > 2 * CONTEXT_LINES {
+            let first = changed[hunk_start].saturating_sub(CONTEXT_LINES);
+            let last = (index + CONTEXT_LINES + 1).min(lines.len());
+
+            write_hunk(&mut rval, &lines, first, last);
+            hunk_start = i + 1;
+        }
+    }
+
+    rval
+}
+
+fn write_hunk(rval: &mut String, lines: &[Line], first: usize, last: usize) {
+    let old_start = lines[..first].iter().filter(|x| !matches!(x, Line::Added(_))).count();
+    let new_start = lines[..first].iter().filter(|x| !matches!(x, Line::Removed(_))).count();
+    let old_len = lines[first..last].iter().filter(|x| !matches!(x, Line::Added(_))).count();
+    let new_len = lines[first..last].iter().filter(|x| !matches!(x, Line::Removed(_))).count();
+
+    writeln!(rval, "@@ -{},{} +{},{} @@", old_start + 1, old_len, new_start + 1, new_len).unwrap();
+
+    for line in &lines[first..last] {
+        match line {
+            Line::Same(x) => writeln!(rval, " {}", x).unwrap(),
+            Line::Removed(x) => writeln!(rval, "-{}", x).unwrap(),
+            Line::Added(x) => writeln!(rval, "+{}", x).unwrap(),
+        }
+    }
+}
+
+/// Computes a line diff via the longest common subsequence of the region between common prefix and suffix.
+fn diff_lines<'a>(old: &[&'a str], new: &[&'a str]) -> Vec<Line<'a>> {
+    let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..].iter().rev().zip(new[prefix..].iter().rev()).take_while(|(a, b)| a == b).count();
+
+    let old_middle = &old[prefix..old.len() - suffix];
+    let new_middle = &new[prefix..new.len() - suffix];
+
+    let mut rval = old[..prefix].iter().map(|x| Line::Same(x)).collect::<Vec<_>>();
+
+    if old_middle.len().saturating_mul(new_middle.len()) > MAX_DIFF_CELLS {
+        rval.extend(old_middle.iter().map(|x| Line::Removed(x)));
+        rval.extend(new_middle.iter().map(|x| Line::Added(x)));
+    } else {
+        rval.extend(lcs_diff(old_middle, new_middle));
+    }
+
+    rval.extend(old[old.len() - suffix..].iter().map(|x| Line::Same(x)));
+    rval
+}
+
+fn lcs_diff<'a>(old: &[&'a str], new: &[&'a str]) -> Vec<Line<'a>> {
+    let width = new.len() + 1;
+
+    // lengths[i * width + j] is the LCS length of old[i..] and new[j..].
+    let mut lengths = vec![0u32; (old.len() + 1) * width];
+
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lengths[i * width + j] = if old[i] == new[j] {
+                lengths[(i + 1) * width + j + 1] + 1
+            } else {
+                lengths[(i + 1) * width + j].max(lengths[i * width + j + 1])
+            };
+        }
+    }
+
+    let mut rval = Vec::new();
+    let (mut i, mut j) = (0, 0);
+
+    while i < old.len() && j < new.len() {
+        if old[i] == new[j] {
+            rval.push(Line::Same(old[i]));
+            i += 1;
+            j += 1;
+        } else if lengths[(i + 1) * width + j] >= lengths[i * width + j + 1] {
+            rval.push(Line::Removed(old[i]));
+            i += 1;
+        } else {
+            rval.push(Line::Added(new[j]));
+            j += 1;
+        }
+    }
+
+    rval.extend(old[i..].iter().map(|x| Line::Removed(x)));
+    rval.extend(new[j..].iter().map(|x| Line::Added(x)));
+    rval
+}
+
+#[cfg(test)]
+mod test {
+    use crate::testing::snapshot::unified_diff;
+
+    #[test]
+    fn equal_inputs_have_no_diff() {
+        assert_eq!(unified_diff("a\nb\n", "a\nb\n"), "");
+    }
+
+    #[test]
+    fn diff_shows_changes_with_context() {
+        let expected = "1\n2\n3\n4\n5\n6\n7\n8\n9\n10\n";
+        let actual = "1\n2\n3\n4\nfive\n6\n7\n8\n9\n10\n11\n";
+
+        let diff = unified_diff(expected, actual);
+
+        assert_eq!(diff, "--- expected\n+++ actual\n@@ -2,9 +2,10 @@\n 2\n 3\n 4\n-5\n+five\n 6\n 7\n 8\n 9\n 10\n+11\n");
+    }
+
+    #[test]
+    fn distant_changes_get_separate_hunks() {
+        let expected = (1..=20).map(|x| format!("{}\n", x)).collect::<String>();
+        let actual = expected.replacen("2\n", "two\n", 1).replace("19\n", "nineteen\n");
+
+        let diff = unified_diff(&expected, &actual);
+
+        assert_eq!(diff.matches("@@ -").count(), 2);
+        assert!(diff.contains("@@ -1,5 +1,5 @@\n 1\n-2\n+two\n"));
+        assert!(diff.contains("-19\n+nineteen\n 20\n"));
+    }
+}
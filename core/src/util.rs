@@ -2,6 +2,9 @@
 
 use crate::lang::c::{CType, Function};
 use crate::patterns::TypePattern;
+use crate::Inventory;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 use std::iter::FromIterator;
 
@@ -138,6 +141,18 @@ pub(crate) fn ctypes_from_type_recursive(start: &CType, types: &mut HashSet<CTyp
                 ctypes_from_type_recursive(field.the_type(), types);
             }
         }
+        CType::Union(inner) => {
+            for field in inner.fields() {
+                ctypes_from_type_recursive(field.the_type(), types);
+            }
+        }
+        CType::TaggedUnion(inner) => {
+            for variant in inner.variants() {
+                for field in variant.fields() {
+                    ctypes_from_type_recursive(field.the_type(), types);
+                }
+            }
+        }
         CType::Array(inner) => ctypes_from_type_recursive(inner.array_type(), types),
         CType::FnPointer(inner) => {
             ctypes_from_type_recursive(inner.signature().rval(), types);
@@ -174,11 +189,28 @@ pub(crate) fn ctypes_from_type_recursive(start: &CType, types: &mut HashSet<CTyp
                     ctypes_from_type_recursive(field.the_type(), types);
                 }
             }
+            TypePattern::Vec(x) => {
+                for field in x.fields() {
+                    ctypes_from_type_recursive(field.the_type(), types);
+                }
+            }
+            TypePattern::Str(_) => {}
+            TypePattern::String(_) => {}
             TypePattern::Option(x) => {
                 for field in x.fields() {
                     ctypes_from_type_recursive(field.the_type(), types);
                 }
             }
+            TypePattern::Result(x) => {
+                for field in x.the_type().fields() {
+                    ctypes_from_type_recursive(field.the_type(), types);
+                }
+            }
+            TypePattern::CallbackWithData(x) => {
+                for field in x.the_type().fields() {
+                    ctypes_from_type_recursive(field.the_type(), types);
+                }
+            }
             TypePattern::Bool => {}
             TypePattern::CChar => {}
             TypePattern::APIVersion => {}
@@ -202,6 +234,12 @@ pub(crate) fn extract_namespaces_from_types(types: &[CType], into: &mut HashSet<
             CType::Composite(x) => {
                 into.insert(x.meta().namespace().to_string());
             }
+            CType::Union(x) => {
+                into.insert(x.meta().namespace().to_string());
+            }
+            CType::TaggedUnion(x) => {
+                into.insert(x.meta().namespace().to_string());
+            }
             CType::FnPointer(_) => {}
             CType::ReadPointer(_) => {}
             CType::ReadWritePointer(_) => {}
@@ -217,23 +255,102 @@ pub(crate) fn extract_namespaces_from_types(types: &[CType], into: &mut HashSet<
                 TypePattern::SliceMut(x) => {
                     into.insert(x.meta().namespace().to_string());
                 }
+                TypePattern::Vec(x) => {
+                    into.insert(x.meta().namespace().to_string());
+                }
+                TypePattern::Str(x) => {
+                    into.insert(x.meta().namespace().to_string());
+                }
+                TypePattern::String(x) => {
+                    into.insert(x.meta().namespace().to_string());
+                }
                 TypePattern::Option(x) => {
                     into.insert(x.meta().namespace().to_string());
                 }
+                TypePattern::Result(x) => {
+                    into.insert(x.the_type().meta().namespace().to_string());
+                }
                 TypePattern::Bool => {}
                 TypePattern::CChar => {}
                 TypePattern::NamedCallback(_) => {}
+                TypePattern::CallbackWithData(x) => {
+                    into.insert(x.the_type().meta().namespace().to_string());
+                }
             },
         }
     }
 }
 
+/// Returns all distinct named types with their Rust name, resolving patterns to their fallback type.
+///
+/// A name is listed once per distinct definition, e.g., twice for two generic instantiations ending up with the same name.
+pub(crate) fn named_types(inventory: &Inventory) -> Vec<(String, CType)> {
+    let mut rval = Vec::new();
+
+    for t in inventory.ctypes() {
+        let t = match t {
+            CType::Pattern(x) => x.fallback_type(),
+            _ => t.clone(),
+        };
+
+        let name = match &t {
+            CType::Enum(x) => x.rust_name().to_string(),
+            CType::Opaque(x) => x.rust_name().to_string(),
+            CType::Composite(x) => x.rust_name().to_string(),
+            CType::Union(x) => x.rust_name().to_string(),
+            CType::TaggedUnion(x) => x.rust_name().to_string(),
+            _ => continue,
+        };
+
+        if !rval.contains(&(name.clone(), t.clone())) {
+            rval.push((name, t));
+        }
+    }
+
+    rval
+}
+
+/// The kind of type as used in messages, e.g., `struct` in `struct Vec3`.
+pub(crate) fn kind_name(t: &CType) -> &'static str {
+    match t {
+        CType::Enum(_) => "enum",
+        CType::Opaque(_) => "opaque",
+        CType::Composite(_) => "struct",
+        CType::Union(_) => "union",
+        CType::TaggedUnion(_) => "tagged union",
+        CType::FnPointer(_) => "fn pointer",
+        _ => "type",
+    }
+}
+
 /// Maps an internal namespace like `common` to a language namespace like `Company.Common`.
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Deserialize, Serialize),
+    serde(from = "HashMap<String, String>", into = "HashMap<String, String>")
+)]
 pub struct NamespaceMappings {
     mappings: HashMap<String, String>,
 }
 
+/// Creates mappings from `id => namespace` pairs, where the default namespace has id `""`.
+impl From<HashMap<String, String>> for NamespaceMappings {
+    fn from(mut mappings: HashMap<String, String>) -> Self {
+        if let Some(default) = mappings.get("").cloned() {
+            mappings.entry("_global".to_string()).or_insert(default);
+        }
+
+        Self { mappings }
+    }
+}
+
+impl From<NamespaceMappings> for HashMap<String, String> {
+    fn from(x: NamespaceMappings) -> Self {
+        x.mappings
+    }
+}
+
 impl NamespaceMappings {
     /// Creates a new mapping, assinging namespace id `""` to `default`.
     pub fn new(default: &str) -> Self {
@@ -305,6 +422,8 @@ pub fn is_global_type(t: &CType) -> bool {
         CType::Enum(_) => false,
         CType::Opaque(_) => false,
         CType::Composite(_) => false,
+        CType::Union(_) => false,
+        CType::TaggedUnion(_) => false,
         CType::FnPointer(_) => false,
         CType::ReadPointer(x) => is_global_type(x),
         CType::ReadWritePointer(x) => is_global_type(x),
@@ -314,10 +433,15 @@ pub fn is_global_type(t: &CType) -> bool {
             TypePattern::FFIErrorEnum(_) => false,
             TypePattern::Slice(x) => x.fields().iter().all(|x| is_global_type(x.the_type())),
             TypePattern::SliceMut(x) => x.fields().iter().all(|x| is_global_type(x.the_type())),
+            TypePattern::Vec(_) => false,
+            TypePattern::Str(_) => false,
+            TypePattern::String(_) => false,
             TypePattern::Option(x) => x.fields().iter().all(|x| is_global_type(x.the_type())),
+            TypePattern::Result(_) => false,
             TypePattern::Bool => true,
             TypePattern::CChar => true,
             TypePattern::NamedCallback(_) => false,
+            TypePattern::CallbackWithData(_) => false,
         },
     }
 }
@@ -348,6 +472,27 @@ pub fn log_error<S: AsRef<str>, F: Fn() -> S>(f: F) {
 #[inline(always)]
 pub fn log_error<S: AsRef<str>, F: Fn() -> S>(_f: F) {}
 
+/// Builders for small inventories shared by unit tests.
+#[cfg(test)]
+pub(crate) mod fixtures {
+    use crate::lang::c::{CType, CompositeType, Field, Function, FunctionSignature, Meta, Parameter, PrimitiveType};
+    use crate::{Inventory, InventoryBuilder, Symbol};
+
+    pub(crate) fn function(name: &str, params: &[(&str, CType)]) -> Symbol {
+        let params = params.iter().map(|(name, t)| Parameter::new(name.to_string(), t.clone())).collect();
+        Symbol::Function(Function::new(name.to_string(), FunctionSignature::new(params, CType::void()), Meta::new()))
+    }
+
+    pub(crate) fn inventory(symbols: Vec<Symbol>) -> Inventory {
+        symbols.into_iter().fold(InventoryBuilder::new(), |b, s| b.register(s)).inventory()
+    }
+
+    pub(crate) fn composite(name: &str, fields: &[(&str, PrimitiveType)]) -> CType {
+        let fields = fields.iter().map(|(name, t)| Field::new(name.to_string(), CType::Primitive(*t))).collect();
+        CType::Composite(CompositeType::new(name.to_string(), fields))
+    }
+}
+
 #[cfg(test)]
 mod test {
     use crate::util::IdPrettifier;
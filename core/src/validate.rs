@@ -0,0 +1,533 @@
+//! Checks an inventory for problems that would otherwise only show up as broken bindings.
+//!
+//! Some inventories can be produced from valid Rust code, but can't be turned into working bindings, for example
+//! when two generic instantiations end up with the same type name. Backends usually don't detect this and emit
+//! code that fails to compile, or panic half way through. [`Inventory::validate`] checks for such problems upfront,
+//! and each backend's [`Interop::validate`](crate::Interop::validate) adds its own, language specific rules.
+//!
+//! # Example
+//!
+//! ```
+//! # use interoptopus::Inventory;
+//! # let inventory = Inventory::default();
+//! let validation = inventory.validate();
+//!
+//! // Prints lines like `error: type Vec3: defined 2 times with different definitions ...`.
+//! eprint!("{}", validation);
+//!
+//! assert!(!validation.has_errors());
+//! ```
+//!
+//! # Rules
+//!
+//! | Problem | Level |
+//! | --- | --- |
+//! | Two different types, functions or constants with the same name | [`Error`](Level::Error) |
+//! | Type name that isn't a valid identifier, e.g., `SliceOption*const i8` | [`Error`](Level::Error) |
+//! | Two different types with the same name after [`safe_name`](crate::util::safe_name) | [`Error`](Level::Error) |
+//! | Service without constructor | [`Error`](Level::Error) |
+//! | [`FFIVec`](crate::patterns::vec::FFIVec) without destructor registered via [`ffi_vec`](crate::ffi_vec) | [`Warning`](Level::Warning) |
+//! | [`FFIString`](crate::patterns::string::FFIString) without destructor registered via [`ffi_string`](crate::ffi_string) | [`Warning`](Level::Warning) |
+//! | Variant field named `tag` in a tagged union with [`TagPlacement::Inline`], clashing with the injected tag | [`Error`](Level::Error) |
+//! | Struct without fields, which is not valid C99 (see [`CompositeType::is_empty`]) | [`Warning`](Level::Warning) |
+//!
+//! Backends add rules for things like reserved words and patterns they can't express, helped by
+//! [`Validation::check_reserved_words`] and [`Validation::check_unsupported_primitives`].
+use crate::lang::c::{CType, PrimitiveType, TagPlacement};
+use crate::patterns::{LibraryPattern, TypePattern};
+use crate::util::{kind_name, named_types, safe_name};
+use crate::{Error, Inventory};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::{Display, Formatter};
+
+/// How serious a [`Diagnostic`] is.
+#[derive(Copy, Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub enum Level {
+    /// Bindings can be generated, but might not be what you expect in some languages.
+    Warning,
+    /// Bindings will be broken, or the backend will fail generating them.
+    Error,
+}
+
+impl Display for Level {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Level::Warning => write!(f, "warning"),
+            Level::Error => write!(f, "error"),
+        }
+    }
+}
+
+/// A single problem found in an inventory.
+#[derive(Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub struct Diagnostic {
+    level: Level,
+    item: String,
+    message: String,
+}
+
+impl Diagnostic {
+    pub fn new(level: Level, item: String, message: String) -> Self {
+        Self { level, item, message }
+    }
+
+    pub fn level(&self) -> Level {
+        self.level
+    }
+
+    /// The affected item, e.g., `fn my_function` or `struct Vec3`.
+    pub fn item(&self) -> &str {
+        &self.item
+    }
+
+    /// Human readable explanation of the problem and how to fix it.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}: {}", self.level, self.item, self.message)
+    }
+}
+
+/// All problems found in an inventory, produced by [`Inventory::validate`] and backends.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Validation {
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Validation {
+    /// Records a problem, used by backends to add their own rules.
+    pub fn push(&mut self, level: Level, item: impl Into<String>, message: impl Into<String>) {
+        self.diagnostics.push(Diagnostic::new(level, item.into(), message.into()));
+    }
+
+    /// All problems found, in no particular order.
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
+
+    /// Returns `true` if any problem is an [`Level::Error`].
+    pub fn has_errors(&self) -> bool {
+        self.diagnostics.iter().any(|x| x.level == Level::Error)
+    }
+
+    /// Returns `true` if no problems were found.
+    pub fn is_empty(&self) -> bool {
+        self.diagnostics.is_empty()
+    }
+
+    /// Returns [`Error::Invalid`] if any problem is an [`Level::Error`], used by backends before writing bindings.
+    pub fn into_result(self) -> Result<Self, Error> {
+        if self.has_errors() {
+            Err(Error::Invalid(self))
+        } else {
+            Ok(self)
+        }
+    }
+
+    /// Reports every function, parameter, type, field, enum variant and constant named like one of `reserved`.
+    ///
+    /// Meant for backends emitting these names verbatim, `language` is used in the message, e.g., `"C#"`.
+    pub fn check_reserved_words(&mut self, inventory: &Inventory, language: &str, reserved: &[&str]) {
+        let mut check = |item: &str, what: &str, name: &str| {
+            if reserved.contains(&name) {
+                let message = format!("{} `{}` is a reserved word in {}", what, name, language);
+                self.push(Level::Error, item, message);
+            }
+        };
+
+        for function in inventory.functions() {
+            let item = format!("fn {}", function.name());
+            check(&item, "function", function.name());
+
+            for param in function.signature().params() {
+                check(&item, "parameter", param.name());
+            }
+        }
+
+        for constant in inventory.constants() {
+            check(&format!("const {}", constant.name()), "constant", constant.name());
+        }
+
+        for (name, t) in named_types(inventory) {
+            let item = format!("{} {}", kind_name(&t), name);
+            check(&item, kind_name(&t), &name);
+
+            match &t {
+                CType::Enum(x) => x.variants().iter().for_each(|v| check(&item, "variant", v.name())),
+                CType::Composite(x) => x.fields().iter().for_each(|f| check(&item, "field", f.name())),
+                CType::Union(x) => x.fields().iter().for_each(|f| check(&item, "field", f.name())),
+                CType::TaggedUnion(x) => {
+                    for variant in x.variants() {
+                        check(&item, "variant", variant.name());
+                        variant.fields().iter().for_each(|f| check(&item, "field", f.name()));
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Reports every parameter, return value, field and constant whose type involves one of `unsupported`.
+    ///
+    /// Meant for backends lacking an equivalent of some primitives, `language` is used in the message, e.g., `"C#"`.
+    pub fn check_unsupported_primitives(&mut self, inventory: &Inventory, language: &str, unsupported: &[PrimitiveType]) {
+        let uses = |t: &CType| {
+            let mut types = t.embedded_types();
+            types.push(t.clone());
+            types.into_iter().find_map(|x| match x {
+                CType::Primitive(x) if unsupported.contains(&x) => Some(x),
+                _ => None,
+            })
+        };
+
+        let mut check = |item: &str, what: String, t: &CType| {
+            if let Some(primitive) = uses(t) {
+                let message = format!("{} uses `{}`, which {} has no equivalent for", what, primitive.rust_name(), language);
+                self.push(Level::Error, item, message);
+            }
+        };
+
+        for function in inventory.functions() {
+            let item = format!("fn {}", function.name());
+
+            for param in function.signature().params() {
+                check(&item, format!("parameter `{}`", param.name()), param.the_type());
+            }
+
+            check(&item, "return value".to_string(), function.signature().rval());
+        }
+
+        for constant in inventory.constants() {
+            check(&format!("const {}", constant.name()), "value".to_string(), &constant.the_type());
+        }
+
+        for (name, t) in named_types(inventory) {
+            let item = format!("{} {}", kind_name(&t), name);
+            let fields = match &t {
+                CType::Composite(x) => x.fields().to_vec(),
+                CType::Union(x) => x.fields().to_vec(),
+                CType::TaggedUnion(x) => x.variants().iter().flat_map(|v| v.fields().to_vec()).collect(),
+                _ => continue,
+            };
+
+            for field in fields {
+                check(&item, format!("field `{}`", field.name()), field.the_type());
+            }
+        }
+    }
+}
+
+impl Display for Validation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for diagnostic in &self.diagnostics {
+            writeln!(f, "{}", diagnostic)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Runs the backend independent rules listed in the [module documentation](self).
+pub(crate) fn validate(inventory: &Inventory) -> Validation {
+    let mut validation = Validation::default();
+
+    validate_unique_names(inventory, &mut validation);
+    validate_identifiers(inventory, &mut validation);
+    validate_safe_names(inventory, &mut validation);
+    validate_composites(inventory, &mut validation);
+    validate_patterns(inventory, &mut validation);
+
+    validation
+}
+
+fn validate_unique_names(inventory: &Inventory, validation: &mut Validation) {
+    let mut types = BTreeMap::<String, usize>::new();
+
+    for (name, _) in named_types(inventory) {
+        *types.entry(name).or_default() += 1;
+    }
+
+    for (name, count) in types.iter().filter(|(_, count)| **count > 1) {
+        let message = format!(
+            "defined {} times with different definitions, e.g., by different generic instantiations; give each a distinct name via `#[ffi_type(name = \"...\")]`",
+            count
+        );
+        validation.push(Level::Error, format!("type {}", name), message);
+    }
+
+    let mut functions = BTreeMap::<&str, Vec<_>>::new();
+
+    for function in inventory.functions() {
+        let same = functions.entry(function.name()).or_default();
+
+        if !same.contains(&function) {
+            same.push(function);
+        }
+    }
+
+    for (name, same) in functions.iter().filter(|(_, same)| same.len() > 1) {
+        let message = format!("exported {} times with different signatures, rename all but one", same.len());
+        validation.push(Level::Error, format!("fn {}", name), message);
+    }
+
+    let mut constants = BTreeMap::<&str, Vec<_>>::new();
+
+    for constant in inventory.constants() {
+        let same = constants.entry(constant.name()).or_default();
+
+        if !same.contains(&constant) {
+            same.push(constant);
+        }
+    }
+
+    for (name, same) in constants.iter().filter(|(_, same)| same.len() > 1) {
+        let message = format!("defined {} times with different values, rename all but one", same.len());
+        validation.push(Level::Error, format!("const {}", name), message);
+    }
+}
+
+fn validate_identifiers(inventory: &Inventory, validation: &mut Validation) {
+    for (name, t) in named_types(inventory) {
+        let valid = name.chars().all(|x| x.is_ascii_alphanumeric() || x == '_') && !name.starts_with(|x: char| x.is_ascii_digit());
+
+        if !valid {
+            let message = "isn't a valid identifier, e.g., because a generic argument is a pointer; name it via `#[ffi_type(name = \"...\")]`";
+            validation.push(Level::Error, format!("{} {}", kind_name(&t), name), message);
+        }
+    }
+}
+
+/// Backends derive names of function pointers via [`safe_name`], check they don't clash with each other or named types.
+fn validate_safe_names(inventory: &Inventory, validation: &mut Validation) {
+    let mut names = BTreeMap::<String, Vec<String>>::new();
+
+    for (name, t) in named_types(inventory) {
+        names.entry(safe_name(&name)).or_default().push(format!("{} {}", kind_name(&t), name));
+    }
+
+    // Function pointers only differing in parameter names are the same type in bindings.
+    let mut fn_pointers = BTreeSet::new();
+
+    for t in inventory.ctypes() {
+        let t = match t {
+            CType::Pattern(x) => x.fallback_type(),
+            _ => t.clone(),
+        };
+
+        if let CType::FnPointer(x) = t {
+            fn_pointers.insert(x.internal_name());
+        }
+    }
+
+    for name in fn_pointers {
+        names.entry(safe_name(&name)).or_default().push(format!("fn pointer `{}`", name));
+    }
+
+    for (safe, items) in names.iter().filter(|(_, items)| items.len() > 1) {
+        for item in items.iter().filter(|x| x.starts_with("fn pointer")) {
+            let others = items.iter().filter(|x| *x != item).cloned().collect::<Vec<_>>().join(", ");
+            let message = format!("is named `{}` in bindings, just like {}", safe, others);
+            validation.push(Level::Error, item.clone(), message);
+        }
+    }
+}
+
+fn validate_composites(inventory: &Inventory, validation: &mut Validation) {
+    for (name, t) in named_types(inventory) {
+        if let CType::Composite(x) = &t {
+            if x.is_empty() {
+                let message = "has no fields, which isn't valid C99; C bindings will only declare it, consider making it `#[ffi_type(opaque)]`";
+                validation.push(Level::Warning, format!("struct {}", name), message);
+            }
+        }
+
+        if let CType::TaggedUnion(x) = &t {
+            if x.placement() != TagPlacement::Inline {
+                continue;
+            }
+
+            for variant in x.variants().iter().filter(|v| v.fields().iter().any(|f| f.name() == "tag")) {
+                let message = format!(
+                    "variant {} has a field `tag`, which clashes with the tag placed inline in each variant; rename the field",
+                    variant.name()
+                );
+                validation.push(Level::Error, format!("tagged union {}", name), message);
+            }
+        }
+    }
+}
+
+fn validate_patterns(inventory: &Inventory, validation: &mut Validation) {
+    for pattern in inventory.patterns() {
+        match pattern {
+            LibraryPattern::Service(x) => {
+                if x.constructors().is_empty() {
+                    let message = "has no constructor, add one via `#[ffi_service_ctor]`";
+                    validation.push(Level::Error, format!("service {}", x.the_type().rust_name()), message);
+                }
+            }
+            LibraryPattern::Vec(_) => {}
+            LibraryPattern::String(_) => {}
+            LibraryPattern::LastError(_) => {}
+        }
+    }
+
+    for t in inventory.ctypes() {
+        if let CType::Pattern(TypePattern::Vec(x)) = t {
+            let has_destructor = inventory.patterns().iter().any(|p| matches!(p, LibraryPattern::Vec(p) if p.the_type() == x));
+
+            if !has_destructor {
+                let message = "has no destructor, callers can't free it; define one via `ffi_vec!` and register it via `pattern!`";
+                validation.push(Level::Warning, format!("struct {}", x.rust_name()), message);
+            }
+        }
+
+        if let CType::Pattern(TypePattern::String(x)) = t {
+            let has_destructor = inventory.patterns().iter().any(|p| matches!(p, LibraryPattern::String(_)));
+
+            if !has_destructor {
+                let message = "has no destructor, callers can't free it; define one via `ffi_string!` and register it via `pattern!`";
+                validation.push(Level::Warning, format!("struct {}", x.rust_name()), message);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::lang::c::{CType, Field, FnPointerType, Function, FunctionSignature, Meta, Parameter, PrimitiveType, TagPlacement};
+    use crate::util::fixtures::{composite, function, inventory};
+    use crate::validate::{Level, Validation};
+    use crate::{Error, InventoryBuilder, Symbol};
+
+    #[test]
+    fn valid_inventory_is_empty() {
+        let a = composite("Vec2", &[("x", PrimitiveType::F32), ("y", PrimitiveType::F32)]);
+        let validation = inventory(vec![function("f", &[("x", a)])]).validate();
+
+        assert!(validation.is_empty(), "{}", validation);
+    }
+
+    #[test]
+    fn duplicate_type_names_are_errors() {
+        let a = composite("Generic", &[("x", PrimitiveType::U32)]);
+        let b = composite("Generic", &[("x", PrimitiveType::U8)]);
+        let validation = inventory(vec![function("f", &[("x", a), ("y", b)])]).validate();
+
+        assert!(validation.has_errors());
+        assert!(validation.diagnostics().iter().any(|x| x.item() == "type Generic"));
+    }
+
+    #[test]
+    fn duplicate_function_names_are_errors() {
+        let validation = inventory(vec![function("f", &[]), function("f", &[("x", CType::Primitive(PrimitiveType::U8))])]).validate();
+
+        assert_eq!(validation.diagnostics().len(), 1);
+        assert_eq!(validation.diagnostics()[0].item(), "fn f");
+    }
+
+    #[test]
+    fn safe_name_collisions_are_errors() {
+        let callback = CType::FnPointer(FnPointerType::new(FunctionSignature::new(vec![], CType::Primitive(PrimitiveType::U8))));
+        let clash = composite("fn__rval_u8", &[("x", PrimitiveType::U8)]);
+        let validation = inventory(vec![function("f", &[("x", callback), ("y", clash)])]).validate();
+
+        assert!(validation.has_errors());
+        assert_eq!(validation.diagnostics()[0].item(), "fn pointer `fn() -> u8`");
+    }
+
+    #[test]
+    fn empty_composites_are_warnings() {
+        let validation = inventory(vec![function("f", &[("x", composite("Empty", &[]))])]).validate();
+
+        assert!(!validation.has_errors());
+        assert_eq!(validation.diagnostics()[0].level(), Level::Warning);
+    }
+
+    #[test]
+    fn inline_tag_clashes_are_errors() {
+        use crate::lang::c::{Documentation, TaggedUnionType, TaggedVariant};
+
+        let tagged = |placement| {
+            let fields = vec![Field::new("tag".to_string(), CType::Primitive(PrimitiveType::U8))];
+            let variants = vec![TaggedVariant::new("A".to_string(), 0, fields, Documentation::new())];
+            CType::TaggedUnion(TaggedUnionType::new("T".to_string(), PrimitiveType::U8, placement, variants, Meta::new()))
+        };
+
+        let validation = inventory(vec![function("f", &[("x", tagged(TagPlacement::Inline))])]).validate();
+
+        assert_eq!(
+            validation.to_string(),
+            "error: tagged union T: variant A has a field `tag`, which clashes with the tag placed inline in each variant; rename the field\n"
+        );
+        assert!(inventory(vec![function("f", &[("x", tagged(TagPlacement::Separate))])]).validate().is_empty());
+    }
+
+    #[test]
+    fn vecs_without_destructor_are_warnings() {
+        use crate::lang::rust::CTypeInfo;
+        use crate::patterns::vec::{FFIVec, VecPattern};
+
+        let vec = FFIVec::<u8>::type_info();
+        let validation = inventory(vec![function("f", &[("x", vec.clone())])]).validate();
+
+        assert_eq!(
+            validation.to_string(),
+            "warning: struct Vecu8: has no destructor, callers can't free it; define one via `ffi_vec!` and register it via `pattern!`\n"
+        );
+
+        let destroy = Function::new(
+            "destroy".to_string(),
+            FunctionSignature::new(vec![Parameter::new("x".to_string(), vec)], CType::void()),
+            Meta::new(),
+        );
+        let pattern = VecPattern::new(FFIVec::<u8>::composite_type(), destroy);
+        let validation = InventoryBuilder::new().register(Symbol::Pattern(pattern.into())).inventory().validate();
+
+        assert!(validation.is_empty(), "{}", validation);
+    }
+
+    #[test]
+    fn reserved_words() {
+        let mut validation = inventory(vec![function("f", &[("x", composite("S", &[("from", PrimitiveType::U8)]))])]).validate();
+        validation.check_reserved_words(&inventory(vec![]), "Python", &["from"]);
+        assert!(validation.is_empty());
+
+        let s = composite("S", &[("from", PrimitiveType::U8)]);
+        validation.check_reserved_words(&inventory(vec![function("f", &[("x", s)])]), "Python", &["from"]);
+        assert_eq!(validation.to_string(), "error: struct S: field `from` is a reserved word in Python\n");
+    }
+
+    #[test]
+    fn unsupported_primitives() {
+        let s = composite("S", &[("x", PrimitiveType::U128)]);
+        let mut validation = Validation::default();
+        validation.check_unsupported_primitives(&inventory(vec![function("f", &[("x", s)])]), "C#", &[PrimitiveType::U128]);
+
+        let diagnostics = validation.diagnostics().iter().map(|x| x.to_string()).collect::<Vec<_>>();
+        assert_eq!(
+            diagnostics,
+            [
+                "error: fn f: parameter `x` uses `u128`, which C# has no equivalent for",
+                "error: struct S: field `x` uses `u128`, which C# has no equivalent for",
+            ]
+        );
+    }
+
+    #[test]
+    fn only_errors_fail_into_result() {
+        let warning = inventory(vec![function("f", &[("x", composite("Empty", &[]))])]).validate();
+        assert!(warning.into_result().is_ok());
+
+        let mut validation = Validation::default();
+        validation.push(Level::Error, "fn f", "uses `u128`");
+
+        let error = validation.into_result().unwrap_err();
+        assert!(matches!(error, Error::Invalid(_)));
+        assert_eq!(error.to_string(), "inventory is invalid:\nerror: fn f: uses `u128`");
+    }
+}
@@ -7,12 +7,17 @@
 extern "C" {
 #endif
 
+#include <stddef.h>
 #include <stdint.h>
 #include <stdbool.h>
 
 
 // Custom attribute.
+#ifdef _WIN32
 #define __FUNCTION_ATTR __declspec( dllimport )
+#else
+#define __FUNCTION_ATTR
+#endif
     
 
 /// Call for a friend.
@@ -117,6 +117,11 @@ namespace My.Company
         {
             Error = error;
         }
+
+        public InteropException(T error, string message): base(string.IsNullOrEmpty(message) ? $"Something went wrong: {error}" : $"Something went wrong: {error} ({message})")
+        {
+            Error = error;
+        }
     }
 
 }
@@ -32,7 +32,11 @@ fn bindings_c() -> Result<(), Error> {
 
     let custom_defines = r"
 // Custom attribute.
+#ifdef _WIN32
 #define __FUNCTION_ATTR __declspec( dllimport )
+#else
+#define __FUNCTION_ATTR
+#endif
     "
     .to_string();
 
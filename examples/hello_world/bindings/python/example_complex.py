@@ -34,6 +34,12 @@ def _errcheck(returned, success):
     else: raise Exception(f"Function returned error: {returned}")
 
 
+def _errcheck_result(returned, success):
+    """Unwraps results, converting FFIErrors to an exception."""
+    if returned.err == success: return returned.t
+    else: raise Exception(f"Function returned error: {returned.err}")
+
+
 class CallbackVars(object):
     """Helper to be used `lambda x: setattr(cv, "x", x)` when getting values from callbacks."""
     def __str__(self):
@@ -1,20 +1,102 @@
 use darling::FromMeta;
 use proc_macro2::TokenStream;
 use quote::quote;
-use syn::{AttributeArgs, ItemConst};
+use syn::{AttributeArgs, Expr, ItemConst, Member};
 
-use crate::util::extract_doc_lines;
+use crate::util::{extract_doc_lines, registration};
 
 #[derive(Debug, FromMeta)]
-pub struct Attributes {}
+pub struct Attributes {
+    #[darling(default)]
+    name: Option<String>,
 
-pub fn ffi_constant(_attr: AttributeArgs, input: TokenStream) -> TokenStream {
+    #[darling(default)]
+    namespace: Option<String>,
+}
+
+/// Produces a `ConstantValue` for the value at `path`, following struct and array literals in `expr`.
+///
+/// Struct literals can't be converted via `From` since we don't know their fields, so for them (and for arrays which
+/// might contain them) we instead convert each field or element individually, e.g., `CONST.field[2]`.
+fn constant_value(expr: &Expr, path: TokenStream) -> TokenStream {
+    match expr {
+        Expr::Struct(x) => {
+            if x.rest.is_some() {
+                panic!("Struct update syntax `..x` is not supported in constants.");
+            }
+
+            let names = x
+                .fields
+                .iter()
+                .map(|field| match &field.member {
+                    Member::Named(ident) => ident.to_string(),
+                    Member::Unnamed(index) => format!("x{}", index.index),
+                })
+                .collect::<Vec<_>>();
+
+            let values = x
+                .fields
+                .iter()
+                .map(|field| {
+                    let member = &field.member;
+                    constant_value(&field.expr, quote! { #path.#member })
+                })
+                .collect::<Vec<_>>();
+
+            quote! {{
+                let composite = match type_info_of(&#path) {
+                    ::interoptopus::lang::c::CType::Composite(x) => x,
+                    _ => panic!("Struct constants must be `#[ffi_type]` structs."),
+                };
+
+                let mut values: ::std::vec::Vec<(&str, ::interoptopus::lang::c::ConstantValue)> = ::std::vec::Vec::new();
+                #( values.push((#names, #values)); )*
+
+                let values = composite
+                    .fields()
+                    .iter()
+                    .map(|field| values.iter().find(|x| x.0 == field.name()).expect("Constant must set all fields.").1.clone())
+                    .collect();
+
+                ::interoptopus::lang::c::ConstantValue::Composite(composite, values)
+            }}
+        }
+        Expr::Array(x) => {
+            let values = x
+                .elems
+                .iter()
+                .enumerate()
+                .map(|(i, elem)| constant_value(elem, quote! { #path[#i] }))
+                .collect::<Vec<_>>();
+
+            quote! {{
+                let array = match type_info_of(&#path) {
+                    ::interoptopus::lang::c::CType::Array(x) => x,
+                    _ => panic!("Array constants must be fixed-size arrays."),
+                };
+
+                ::interoptopus::lang::c::ConstantValue::Array(array, ::std::vec![ #(#values),* ])
+            }}
+        }
+        Expr::Group(x) => constant_value(&x.expr, path),
+        Expr::Paren(x) => constant_value(&x.expr, path),
+        _ => quote! { ::interoptopus::lang::c::ConstantValue::from(#path) },
+    }
+}
+
+pub fn ffi_constant(attr: AttributeArgs, input: TokenStream) -> TokenStream {
+    let attributes: Attributes = Attributes::from_list(&attr).unwrap();
     let const_item: ItemConst = syn::parse2(input.clone()).expect("Must be item.");
 
     let const_ident = const_item.ident;
-    let const_name = const_ident.to_string();
+    let const_name = attributes.name.unwrap_or_else(|| const_ident.to_string());
+    let namespace = attributes.namespace.unwrap_or_default();
 
     let doc_line = extract_doc_lines(&const_item.attrs).join("\n");
+    let value = constant_value(&const_item.expr, quote! { #const_ident });
+    let registration = registration(quote! {
+        ::interoptopus::Symbol::Constant(<#const_ident as ::interoptopus::lang::rust::ConstantInfo>::constant_info())
+    });
 
     quote! {
         #input
@@ -25,13 +107,19 @@ pub fn ffi_constant(_attr: AttributeArgs, input: TokenStream) -> TokenStream {
 
         unsafe impl ::interoptopus::lang::rust::ConstantInfo for #const_ident {
             fn constant_info() -> interoptopus::lang::c::Constant {
+                #[allow(dead_code)]
+                fn type_info_of<T: ::interoptopus::lang::rust::CTypeInfo>(_: &T) -> ::interoptopus::lang::c::CType {
+                    T::type_info()
+                }
 
                 let documentation = ::interoptopus::lang::c::Documentation::from_line(#doc_line);
-                let meta = ::interoptopus::lang::c::Meta::with_documentation(documentation);
-                let value = ::interoptopus::lang::c::ConstantValue::from(#const_ident);
+                let meta = ::interoptopus::lang::c::Meta::with_namespace_documentation(#namespace.to_string(), documentation);
+                let value = #value;
 
                 ::interoptopus::lang::c::Constant::new(#const_name.to_string(), value, meta)
             }
         }
+
+        #registration
     }
 }
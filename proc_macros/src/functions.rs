@@ -7,6 +7,12 @@ mod freestanding;
 
 #[derive(Debug, FromMeta)]
 pub struct Attributes {
+    #[darling(default)]
+    name: Option<String>,
+
+    #[darling(default)]
+    namespace: Option<String>,
+
     #[darling(default)]
     debug: bool,
 }
@@ -1,7 +1,7 @@
 use proc_macro2::TokenStream;
 use quote::{quote, quote_spanned, ToTokens};
 use syn::spanned::Spanned;
-use syn::{FnArg, GenericParam, ItemFn, Pat, ReturnType, Signature, Type};
+use syn::{parse_quote, FnArg, GenericParam, ItemFn, Pat, ReturnType, Signature, Type};
 
 use crate::functions::Attributes;
 use crate::surrogates::read_surrogates;
@@ -64,9 +64,20 @@ pub fn rval_tokens(return_type: &ReturnType) -> TokenStream {
     }
 }
 
-pub fn ffi_function_freestanding(_ffi_attributes: &Attributes, input: TokenStream) -> TokenStream {
-    let item_fn: ItemFn = syn::parse2(input.clone()).expect("Must be item.");
+pub fn ffi_function_freestanding(ffi_attributes: &Attributes, input: TokenStream) -> TokenStream {
+    let mut item_fn: ItemFn = syn::parse2(input.clone()).expect("Must be item.");
     let docs = util::extract_doc_lines(&item_fn.attrs);
+    let namespace = ffi_attributes.namespace.clone().unwrap_or_default();
+
+    // A renamed function must also be exported under its new name, otherwise bindings would point to a missing symbol.
+    let input = match &ffi_attributes.name {
+        Some(name) => {
+            item_fn.attrs.retain(|x| !x.path.is_ident("no_mangle"));
+            item_fn.attrs.push(parse_quote!(#[export_name = #name]));
+            item_fn.to_token_stream()
+        }
+        None => input,
+    };
 
     let mut args_name = Vec::new();
     let mut args_type = Vec::new();
@@ -89,7 +100,7 @@ pub fn ffi_function_freestanding(_ffi_attributes: &Attributes, input: TokenStrea
     }
 
     let function_ident = item_fn.sig.ident;
-    let function_ident_str = function_ident.to_string();
+    let function_ident_str = ffi_attributes.name.clone().unwrap_or_else(|| function_ident.to_string());
     let mut generic_params = quote! {};
     let mut phantom_fields = quote! {};
 
@@ -144,6 +155,10 @@ pub fn ffi_function_freestanding(_ffi_attributes: &Attributes, input: TokenStrea
         }
     }
 
+    let registration = util::registration(quote! {
+        ::interoptopus::Symbol::Function(<#function_ident as ::interoptopus::lang::rust::FunctionInfo>::function_info())
+    });
+
     let rval = quote! {
         #input
 
@@ -169,11 +184,13 @@ pub fn ffi_function_freestanding(_ffi_attributes: &Attributes, input: TokenStrea
 
                 let mut signature = ::interoptopus::lang::c::FunctionSignature::new(params, #rval);
                 let documentation = ::interoptopus::lang::c::Documentation::from_lines(doc_lines);
-                let meta = ::interoptopus::lang::c::Meta::with_documentation(documentation);
+                let meta = ::interoptopus::lang::c::Meta::with_namespace_documentation(#namespace.to_string(), documentation);
 
                 ::interoptopus::lang::c::Function::new(#function_ident_str.to_string(), signature, meta)
             }
         }
+
+        #registration
     };
 
     rval
@@ -40,13 +40,37 @@ use syn::{parse_macro_input, AttributeArgs};
 /// e.g., C# will emit field visibility and generate classes from service patterns.
 ///
 ///
+/// # Enums
+///
+/// Field-less enums must be `#[repr(C)]`, which is a C `int`, or use a fixed-size integer such as `#[repr(u8)]`,
+/// which backends then emit with the same width. Discriminants may be negative.
+///
+/// # Enums with Data
+///
+/// Enums where at least one variant carries fields become a tagged union, a tag enum plus one struct per
+/// variant. They must be `#[repr(C)]`, `#[repr(C, u8)]` (tag followed by a union of payloads) or `#[repr(u8)]`
+/// (union of payloads, each starting with the tag), or the same with another integer type. Tuple variant fields
+/// are named `x0`, `x1`, ... and `skip` is not supported.
+///
+/// # Unions
+///
+/// Unions must be `#[repr(C)]` and must not be generic. All fields start at offset 0, and `name`, `namespace`,
+/// `skip` and `visibility` work the same as for structs.
+///
 /// # Types and the Inventory
 ///
 /// In contrast to functions and constants most types annotated with `#[ffi_type]` will be detected
 /// automatically and need no mention in the inventory function.
 ///
 /// The exception are types that do not show up as fields of another type, or inside a function
-/// signature.
+/// signature. With the `registry` feature, non-generic types also register themselves and are picked up by
+/// `InventoryBuilder::register_module`.
+///
+/// # Layout
+///
+/// Non-opaque structs, unions and enums also implement [`LayoutInfo`](https://docs.rs/interoptopus/latest/interoptopus/lang/rust/trait.LayoutInfo.html),
+/// which backends can use to check their bindings against the layout `rustc` produced. Enums only report their size
+/// and alignment.
 ///
 ///
 /// # Patterns
@@ -88,7 +112,8 @@ pub fn ffi_type(attr: TokenStream, item: TokenStream) -> TokenStream {
 /// This will derive [`FunctionInfo`](https://docs.rs/interoptopus/latest/interoptopus/lang/rust/trait.FunctionInfo.html) for a helper struct
 /// of the same name containing the function's name, parameters and return value.
 ///
-/// In order to appear in generated bindings the function also has to be mentioned in the inventory function.
+/// In order to appear in generated bindings the function also has to be mentioned in the inventory function,
+/// unless the `registry` feature is enabled and the inventory is built via `InventoryBuilder::register_module`.
 ///
 /// # Parameters
 ///
@@ -96,6 +121,8 @@ pub fn ffi_type(attr: TokenStream, item: TokenStream) -> TokenStream {
 ///
 /// | Parameter |  Explanation |
 /// | --- | ---  |
+/// | `name="X"` | Uses `X` as the function name in bindings, and exports the symbol under that name via `#[export_name]`.
+/// | `namespace="X"` | Associate this function with namespace `X`, which backends might use to emit it into a separate file or module.
 /// | `debug` | Print generated helper code in console.
 ///
 /// # Safety
@@ -132,7 +159,21 @@ pub fn ffi_function(attr: TokenStream, item: TokenStream) -> TokenStream {
 ///
 /// Constant evaluation is supported.
 ///
-/// In order to appear in generated bindings the constant also has to be mentioned in the inventory function.
+/// Besides primitives, constants can be `&str`, fixed-size arrays, and `#[ffi_type]` structs. Struct constants
+/// must be written as struct literals (optionally nested in arrays), since their fields are inspected
+/// one by one; struct update syntax (`..x`) is not supported.
+///
+/// In order to appear in generated bindings the constant also has to be mentioned in the inventory function,
+/// unless it is picked up through the `registry` feature.
+///
+/// # Parameters
+///
+/// The following parameters can be provided:
+///
+/// | Parameter |  Explanation |
+/// | --- | ---  |
+/// | `name="X"` | Uses `X` as the constant name in bindings.
+/// | `namespace="X"` | Associate this constant with namespace `X`.
 ///
 /// # Examples
 ///
@@ -146,6 +187,11 @@ pub fn ffi_function(attr: TokenStream, item: TokenStream) -> TokenStream {
 /// #[ffi_constant]
 /// const COMPUTED_CONST: u8 = double(12); // will export 24
 ///
+/// #[ffi_constant]
+/// const NAME: &str = "interoptopus";
+///
+/// #[ffi_constant]
+/// const PRIMES: [u16; 4] = [2, 3, 5, 7];
 /// ```
 #[proc_macro_attribute] // Can now be used as `#[my_attribute]`
 pub fn ffi_constant(attr: TokenStream, item: TokenStream) -> TokenStream {
@@ -161,7 +207,8 @@ pub fn ffi_constant(attr: TokenStream, item: TokenStream) -> TokenStream {
 ///
 /// See the [service module](https://docs.rs/interoptopus/latest/interoptopus/patterns/service/index.html) for an introduction into services.
 ///
-/// In order to appear in generated bindings the service also has to be mentioned in the inventory function.
+/// In order to appear in generated bindings the service also has to be mentioned in the inventory function,
+/// unless it is picked up through the `registry` feature.
 ///
 /// # Requirements
 ///
@@ -328,7 +375,9 @@ pub fn ffi_service_ctor(_attr: TokenStream, item: TokenStream) -> TokenStream {
 ///
 /// By default service methods
 /// must return a `Result<(), Error>` return type that will be mapped to an `FFIError` and
-/// transparently checked in languages supporting the pattern.
+/// transparently checked in languages supporting the pattern. Methods returning a `Result<T, Error>`
+/// are mapped to an [`FFIResult<T, FFIError>`](https://docs.rs/interoptopus/latest/interoptopus/patterns/result/struct.FFIResult.html)
+/// instead, which is unwrapped into a value or an exception in languages supporting it.
 /// However, sometimes you might want to return an actual value. Using this attribute and specifying
 /// an `on_panic` behavior allows you to opt out of error mapping, and instead return values as-is.
 ///
@@ -349,7 +398,7 @@ pub fn ffi_service_ctor(_attr: TokenStream, item: TokenStream) -> TokenStream {
 ///
 /// | Mode |  Explanation |
 /// | --- | ---  |
-/// | `ffi_error` | Method must return `Result<(), Error>` and maps that to an `FFIError`. Default behavior.
+/// | `ffi_error` | Method must return `Result<(), Error>` or `Result<T, Error>` and maps that to an `FFIError` or `FFIResult<T, FFIError>`, where `T: Default`. Default behavior.
 /// | `return_default` | Method can return any `T: Default`. If a panic occurs [`T::default()`](Default::default) will be returned, see below.
 /// | `undefined_behavior` | Method can return any `T`. If a panic occurs undefined behavior happens. Slightly faster (nanoseconds) and mostly an escape hatch when running into lifetime issues in autogenerated code, e.g., when returning an `AsciiPointer` from a service. In the long term our proc macro code gen should be fixed to handle this situation.
 ///
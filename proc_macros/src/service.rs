@@ -1,4 +1,5 @@
 use crate::service::function_impl::{generate_service_dtor, generate_service_method};
+use crate::util::{purge_lifetimes_from_type, registration};
 use darling::FromMeta;
 use function_impl::MethodType;
 use proc_macro2::TokenStream;
@@ -62,6 +63,11 @@ pub fn ffi_service(attr: AttributeArgs, input: TokenStream) -> TokenStream {
     let lifetimes = item.generics.lifetimes();
     let lt = quote! { #(#lifetimes),* };
 
+    let service_type_without_lifetimes = purge_lifetimes_from_type(service_type);
+    let registration = registration(quote! {
+        ::interoptopus::Symbol::Pattern(<#service_type_without_lifetimes as ::interoptopus::patterns::LibraryPatternInfo>::pattern_info())
+    });
+
     let rval = quote! {
         #input
 
@@ -109,6 +115,8 @@ pub fn ffi_service(attr: AttributeArgs, input: TokenStream) -> TokenStream {
                 ::interoptopus::patterns::LibraryPattern::Service(service)
             }
         }
+
+        #registration
     };
 
     if attributes.debug {
@@ -5,7 +5,7 @@ use proc_macro2::{Ident, TokenStream};
 use quote::quote_spanned;
 use std::ops::Deref;
 use syn::spanned::Spanned;
-use syn::{Attribute, FnArg, GenericParam, ImplItemMethod, ItemImpl, Pat, ReturnType};
+use syn::{Attribute, FnArg, GenericArgument, GenericParam, ImplItemMethod, ItemImpl, Pat, PathArguments, ReturnType, Type};
 
 pub struct Descriptor {
     pub ffi_function_tokens: TokenStream,
@@ -69,6 +69,29 @@ fn method_type(attrs: &[Attribute]) -> MethodType {
     }
 }
 
+/// If the method returns a `Result<T, E>` with `T` other than `()`, returns that `T`.
+fn result_value_type(output: &ReturnType) -> Option<&Type> {
+    let path = match output {
+        ReturnType::Type(_, x) => match x.deref() {
+            Type::Path(x) => &x.path,
+            _ => return None,
+        },
+        ReturnType::Default => return None,
+    };
+
+    let segment = path.segments.last().filter(|x| x.ident == "Result")?;
+    let args = match &segment.arguments {
+        PathArguments::AngleBracketed(x) => &x.args,
+        _ => return None,
+    };
+
+    match args.first() {
+        Some(GenericArgument::Type(Type::Tuple(x))) if x.elems.is_empty() => None,
+        Some(GenericArgument::Type(x)) => Some(x),
+        _ => None,
+    }
+}
+
 pub fn generate_service_method(attributes: &Attributes, impl_block: &ItemImpl, function: &ImplItemMethod) -> Option<Descriptor> {
     let orig_fn_ident = &function.sig.ident;
     let service_type = &impl_block.self_ty;
@@ -159,12 +182,12 @@ pub fn generate_service_method(attributes: &Attributes, impl_block: &ItemImpl, f
                         }
 
                         Ok(Err(e)) => {
-                            ::interoptopus::util::log_error(|| format!("Error in ({}): {:?}", stringify!(#ffi_fn_ident), e));
+                            ::interoptopus::patterns::last_error::error_occurred(format!("Error in ({}): {:?}", stringify!(#ffi_fn_ident), e));
                             e.into()
                         }
 
                         Err(e) => {
-                            ::interoptopus::util::log_error(|| format!("Panic in ({}): {:?}", stringify!(#ffi_fn_ident), e));
+                            ::interoptopus::patterns::last_error::error_occurred(format!("Panic in ({}): {}", stringify!(#ffi_fn_ident), ::interoptopus::patterns::last_error::panic_message(&*e)));
                             <#error_ident as ::interoptopus::patterns::result::FFIError>::PANIC
                         }
                     }
@@ -177,13 +200,14 @@ pub fn generate_service_method(attributes: &Attributes, impl_block: &ItemImpl, f
                     #[interoptopus::ffi_function]
                     #[no_mangle]
                     #[allow(unused_mut, unsafe_op_in_unsafe_fn)]
-                    #[allow(clippy::needless_lifetimes, clippy::extra_unused_lifetimes)]
+                    #[allow(clippy::needless_lifetimes, clippy::extra_unused_lifetimes, clippy::redundant_locals)]
                     #(
                         #[doc = #doc_lines]
                     )*
                     pub extern "C" fn #ffi_fn_ident #generics( #(#inputs),* ) -> #rval {
                         let result_result = ::std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
-                            // Make sure we only have a FnOnce closure and prevent lifetime errors.
+                            // Make sure we only have a FnOnce closure and prevent lifetime errors, `move` alone would
+                            // leave closures returning borrows of `context` as FnMut.
                             #(
                                 let #arg_names = #arg_names;
                             )*
@@ -193,7 +217,7 @@ pub fn generate_service_method(attributes: &Attributes, impl_block: &ItemImpl, f
                         match result_result {
                             Ok(x) => x,
                             Err(e) => {
-                                ::interoptopus::util::log_error(|| format!("Panic in ({}): {:?}", stringify!(#ffi_fn_ident), e));
+                                ::interoptopus::patterns::last_error::error_occurred(format!("Panic in ({}): {}", stringify!(#ffi_fn_ident), ::interoptopus::patterns::last_error::panic_message(&*e)));
                                 <#rval>::default()
                             }
                         }
@@ -219,18 +243,35 @@ pub fn generate_service_method(attributes: &Attributes, impl_block: &ItemImpl, f
                     <#without_lifetimes>::#orig_fn_ident( #(#arg_names),* )
                 };
 
-                quote_spanned! { span_function =>
-                    #[interoptopus::ffi_function]
-                    #[no_mangle]
-                    #[allow(unused_mut, unsafe_op_in_unsafe_fn)]
-                    #[allow(clippy::needless_lifetimes, clippy::extra_unused_lifetimes)]
-                    #(
-                        #[doc = #doc_lines]
-                    )*
-                    pub extern "C" fn #ffi_fn_ident #generics( #(#inputs),* ) -> #error_ident {
-                        ::interoptopus::patterns::result::panics_and_errors_to_ffi_enum(move || {
-                            #block
-                        }, stringify!(#ffi_fn_ident))
+                if let Some(value_type) = result_value_type(&function.sig.output) {
+                    quote_spanned! { span_function =>
+                        #[interoptopus::ffi_function]
+                        #[no_mangle]
+                        #[allow(unused_mut, unsafe_op_in_unsafe_fn)]
+                        #[allow(clippy::needless_lifetimes, clippy::extra_unused_lifetimes)]
+                        #(
+                            #[doc = #doc_lines]
+                        )*
+                        pub extern "C" fn #ffi_fn_ident #generics( #(#inputs),* ) -> ::interoptopus::patterns::result::FFIResult<#value_type, #error_ident> {
+                            ::interoptopus::patterns::result::panics_and_errors_to_ffi_result(move || {
+                                #block
+                            }, stringify!(#ffi_fn_ident))
+                        }
+                    }
+                } else {
+                    quote_spanned! { span_function =>
+                        #[interoptopus::ffi_function]
+                        #[no_mangle]
+                        #[allow(unused_mut, unsafe_op_in_unsafe_fn)]
+                        #[allow(clippy::needless_lifetimes, clippy::extra_unused_lifetimes)]
+                        #(
+                            #[doc = #doc_lines]
+                        )*
+                        pub extern "C" fn #ffi_fn_ident #generics( #(#inputs),* ) -> #error_ident {
+                            ::interoptopus::patterns::result::panics_and_errors_to_ffi_enum(move || {
+                                #block
+                            }, stringify!(#ffi_fn_ident))
+                        }
                     }
                 }
             }
@@ -278,7 +319,7 @@ pub fn generate_service_dtor(attributes: &Attributes, impl_block: &ItemImpl) ->
             match result_result {
                 Ok(_) => <#error_ident as ::interoptopus::patterns::result::FFIError>::SUCCESS,
                 Err(e) => {
-                    ::interoptopus::util::log_error(|| format!("Panic in ({}): {:?}", stringify!(#ffi_fn_ident), e));
+                    ::interoptopus::patterns::last_error::error_occurred(format!("Panic in ({}): {}", stringify!(#ffi_fn_ident), ::interoptopus::patterns::last_error::panic_message(&*e)));
                     <#error_ident as ::interoptopus::patterns::result::FFIError>::PANIC
                 }
             }
@@ -1,13 +1,16 @@
 use crate::types::enums::ffi_type_enum;
 use crate::types::structs::ffi_type_struct;
+use crate::types::unions::ffi_type_union;
+use crate::util::registration;
 use darling::FromMeta;
 use proc_macro2::TokenStream;
 use quote::quote;
 use std::collections::HashMap;
-use syn::{AttributeArgs, Field, ItemEnum, ItemStruct, ItemType, Visibility};
+use syn::{AttributeArgs, Field, GenericParam, Generics, Ident, ItemEnum, ItemStruct, ItemType, ItemUnion, Visibility};
 
 mod enums;
 mod structs;
+mod unions;
 
 #[derive(Debug, FromMeta, Clone)]
 pub struct Attributes {
@@ -64,13 +67,21 @@ pub fn ffi_type(attr: AttributeArgs, input: TokenStream) -> TokenStream {
     let attributes: Attributes = Attributes::from_list(&attr).unwrap();
 
     let rval = if let Ok(item) = syn::parse2::<ItemStruct>(input.clone()) {
-        ffi_type_struct(&attributes, input, item)
+        let registration = type_registration(&item.ident, &item.generics);
+        let rval = ffi_type_struct(&attributes, input, item);
+        quote! { #rval #registration }
     } else if let Ok(item) = syn::parse2::<ItemEnum>(input.clone()) {
-        ffi_type_enum(&attributes, input, item)
+        let registration = type_registration(&item.ident, &item.generics);
+        let rval = ffi_type_enum(&attributes, input, item);
+        quote! { #rval #registration }
+    } else if let Ok(item) = syn::parse2::<ItemUnion>(input.clone()) {
+        let registration = type_registration(&item.ident, &item.generics);
+        let rval = ffi_type_union(&attributes, input, item);
+        quote! { #rval #registration }
     } else if let Ok(_item) = syn::parse2::<ItemType>(input.clone()) {
         input
     } else {
-        panic!("Annotation #[ffi_type] only works with structs and enum types.")
+        panic!("Annotation #[ffi_type] only works with structs, enums and unions.")
     };
 
     if attributes.debug {
@@ -79,3 +90,20 @@ pub fn ffi_type(attr: AttributeArgs, input: TokenStream) -> TokenStream {
 
     rval
 }
+
+/// Generic types can't register themselves, we don't know which instances exist. Types only generic over
+/// lifetimes look the same for all of them, so they register as `'static`.
+fn type_registration(ident: &Ident, generics: &Generics) -> TokenStream {
+    if generics.params.iter().any(|x| !matches!(x, GenericParam::Lifetime(_))) {
+        return quote! {};
+    }
+
+    let lifetimes = generics.params.iter().map(|_| quote! { 'static });
+    let ty = if generics.params.is_empty() {
+        quote! { #ident }
+    } else {
+        quote! { #ident<#(#lifetimes),*> }
+    };
+
+    registration(quote! { ::interoptopus::Symbol::Type(<#ty as ::interoptopus::lang::rust::CTypeInfo>::type_info()) })
+}
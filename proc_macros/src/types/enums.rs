@@ -3,7 +3,9 @@ use crate::util::extract_doc_lines;
 use darling::ToTokens;
 use proc_macro2::{Ident, TokenStream};
 use quote::quote;
-use syn::{Expr, ItemEnum, Lit};
+use std::convert::TryFrom;
+use syn::spanned::Spanned;
+use syn::{Expr, ExprGroup, ExprLit, ExprParen, ExprUnary, Fields, ItemEnum, Lit, Meta, NestedMeta, UnOp, Variant};
 
 fn assert_valid_repr(_attributes: &Attributes, item: &ItemEnum) {
     if !item.attrs.iter().any(|x| x.to_token_stream().to_string().contains("repr")) {
@@ -11,7 +13,89 @@ fn assert_valid_repr(_attributes: &Attributes, item: &ItemEnum) {
     }
 }
 
-fn derive_variant_info(item: ItemEnum, idents: &[Ident], names: &[String], values: &[i32], docs: &[String]) -> TokenStream {
+/// Returns all idents inside `#[repr(...)]`, e.g., `["C", "u8"]`.
+fn repr_idents(item: &ItemEnum) -> Vec<String> {
+    let mut rval = Vec::new();
+
+    for attr in item.attrs.iter().filter(|x| x.path.is_ident("repr")) {
+        if let Ok(Meta::List(list)) = attr.parse_meta() {
+            for nested in list.nested {
+                if let NestedMeta::Meta(Meta::Path(path)) = nested {
+                    rval.push(path.to_token_stream().to_string());
+                }
+            }
+        }
+    }
+
+    rval
+}
+
+/// Maps the integer type inside `#[repr(...)]` to a `PrimitiveType`, if there is one.
+fn repr_primitive(repr: &[String]) -> Option<TokenStream> {
+    let primitive = match repr.iter().find(|x| x.as_str() != "C")?.as_str() {
+        "u8" => quote! { ::interoptopus::lang::c::PrimitiveType::U8 },
+        "u16" => quote! { ::interoptopus::lang::c::PrimitiveType::U16 },
+        "u32" => quote! { ::interoptopus::lang::c::PrimitiveType::U32 },
+        "u64" => quote! { ::interoptopus::lang::c::PrimitiveType::U64 },
+        "i8" => quote! { ::interoptopus::lang::c::PrimitiveType::I8 },
+        "i16" => quote! { ::interoptopus::lang::c::PrimitiveType::I16 },
+        "i32" => quote! { ::interoptopus::lang::c::PrimitiveType::I32 },
+        "i64" => quote! { ::interoptopus::lang::c::PrimitiveType::I64 },
+        "u128" => quote! { ::interoptopus::lang::c::PrimitiveType::U128 },
+        "i128" => quote! { ::interoptopus::lang::c::PrimitiveType::I128 },
+        "usize" => quote! { ::interoptopus::lang::c::PrimitiveType::USize },
+        "isize" => quote! { ::interoptopus::lang::c::PrimitiveType::ISize },
+        _ => return None,
+    };
+
+    Some(primitive)
+}
+
+/// The range of discriminants the integer type inside `#[repr(...)]` can hold, a C `int` for a plain `#[repr(C)]`.
+fn repr_range(repr: &[String]) -> (i128, i128) {
+    match repr.iter().find(|x| x.as_str() != "C").map(String::as_str) {
+        Some("u8") => (0, u8::MAX.into()),
+        Some("u16") => (0, u16::MAX.into()),
+        Some("u32") => (0, u32::MAX.into()),
+        Some("u64" | "usize") => (0, u64::MAX.into()),
+        Some("u128") => (0, i128::MAX),
+        Some("i8") => (i8::MIN.into(), i8::MAX.into()),
+        Some("i16") => (i16::MIN.into(), i16::MAX.into()),
+        Some("i64" | "isize") => (i64::MIN.into(), i64::MAX.into()),
+        Some("i128") => (i128::MIN, i128::MAX),
+        _ => (i32::MIN.into(), i32::MAX.into()),
+    }
+}
+
+/// Parses a literal discriminant such as `3` or `-1`.
+fn literal_discriminant(e: &Expr) -> syn::Result<i128> {
+    match e {
+        Expr::Lit(ExprLit { lit: Lit::Int(x), .. }) => x.base10_parse(),
+        Expr::Unary(ExprUnary { op: UnOp::Neg(_), expr, .. }) => Ok(-literal_discriminant(expr)?),
+        Expr::Group(ExprGroup { expr, .. }) | Expr::Paren(ExprParen { expr, .. }) => literal_discriminant(expr),
+        _ => Err(syn::Error::new_spanned(e, "Discriminant must be an integer literal.")),
+    }
+}
+
+/// Computes the discriminant of `variant`, advancing `next_id` the way Rust does.
+fn variant_discriminant(variant: &Variant, next_id: &mut i128, repr: &[String]) -> syn::Result<i64> {
+    let (number, span) = match &variant.discriminant {
+        Some((_, e)) => (literal_discriminant(e)?, e.span()),
+        None => (*next_id, variant.ident.span()),
+    };
+
+    let (min, max) = repr_range(repr);
+
+    if number < min || number > max {
+        return Err(syn::Error::new(span, format!("Discriminant {} doesn't fit into this enum's `#[repr()]`.", number)));
+    }
+
+    *next_id = number + 1;
+
+    i64::try_from(number).map_err(|_| syn::Error::new(span, "Discriminants above `i64::MAX` are not supported."))
+}
+
+fn derive_variant_info(item: ItemEnum, idents: &[Ident], names: &[String], values: &[i64], docs: &[String]) -> TokenStream {
     let name = item.ident.to_string();
     let name_ident = syn::Ident::new(&name, item.ident.span());
 
@@ -22,7 +106,7 @@ fn derive_variant_info(item: ItemEnum, idents: &[Ident], names: &[String], value
                     #(
                        Self::#idents => {
                             let documentation = ::interoptopus::lang::c::Documentation::from_line(#docs);
-                            ::interoptopus::lang::c::Variant::new(#names.to_string(), #values as usize, documentation)
+                            ::interoptopus::lang::c::Variant::new(#names.to_string(), #values, documentation)
                        },
                     )*
                 }
@@ -36,6 +120,18 @@ pub fn ffi_type_enum(attributes: &Attributes, input: TokenStream, item: ItemEnum
 
     assert_valid_repr(attributes, &item);
 
+    if item.variants.iter().any(|x| !x.fields.is_empty()) {
+        return ffi_type_tagged_union(attributes, input, item);
+    }
+
+    let repr = repr_idents(&item);
+    let repr_type = match repr_primitive(&repr) {
+        Some(x) => x,
+        // Everything else is `#[repr(C)]`, which stores the discriminant as a C `int`.
+        None if repr.iter().all(|x| x == "C") => quote! { ::interoptopus::lang::c::PrimitiveType::I32 },
+        None => panic!("Enum `{}` has an unsupported `#[repr()]`, must be `C` or a fixed-size integer.", item.ident),
+    };
+
     let span = item.ident.span();
     let name = item.ident.to_string();
     let ffi_name = attributes.name.clone().unwrap_or_else(|| name.clone());
@@ -52,22 +148,12 @@ pub fn ffi_type_enum(attributes: &Attributes, input: TokenStream, item: ItemEnum
         let ident = variant.ident.to_string();
         let variant_doc_line = extract_doc_lines(&variant.attrs).join("\n");
 
-        let this_id = if let Some((_, e)) = &variant.discriminant {
-            match e {
-                Expr::Lit(e) => match &e.lit {
-                    Lit::Int(x) => {
-                        let number = x.base10_parse().expect("Must be number");
-                        next_id = number + 1;
-                        number
-                    }
-                    _ => panic!("Unknown token."),
-                },
-                _ => panic!("Unknown token."),
+        let this_id = match variant_discriminant(variant, &mut next_id, &repr) {
+            Ok(x) => x,
+            Err(e) => {
+                let error = e.to_compile_error();
+                return quote! { #input #error };
             }
-        } else {
-            let id = next_id;
-            next_id += 1;
-            id
         };
 
         if !attributes.skip.contains_key(&ident) {
@@ -97,6 +183,13 @@ pub fn ffi_type_enum(attributes: &Attributes, input: TokenStream, item: ItemEnum
 
         #variant_infos
 
+        impl ::interoptopus::lang::rust::LayoutInfo for #name_ident {
+            fn layout_info() -> ::interoptopus::lang::rust::TypeLayout {
+                let name = <Self as ::interoptopus::lang::rust::CTypeInfo>::type_info().name_within_lib();
+                ::interoptopus::lang::rust::TypeLayout::new(name, ::std::mem::size_of::<Self>(), ::std::mem::align_of::<Self>(), ::std::vec::Vec::new())
+            }
+        }
+
         unsafe impl ::interoptopus::lang::rust::CTypeInfo for #name_ident {
             fn type_info() -> ::interoptopus::lang::c::CType {
                 use ::interoptopus::lang::rust::VariantInfo;
@@ -109,10 +202,117 @@ pub fn ffi_type_enum(attributes: &Attributes, input: TokenStream, item: ItemEnum
                     variants.push(Self::#variant_idents.variant_info());
                 })*
 
-                let rval = ::interoptopus::lang::c::EnumType::new(#ffi_name.to_string(), variants, meta);
+                let rval = ::interoptopus::lang::c::EnumType::with_repr(#ffi_name.to_string(), variants, #repr_type, meta);
 
                 #ctype_info_return
             }
         }
     }
 }
+
+/// Handles enums where at least one variant carries data, e.g., `#[repr(C, u8)] enum Shape { Circle(f32), Empty }`.
+fn ffi_type_tagged_union(attributes: &Attributes, input: TokenStream, item: ItemEnum) -> TokenStream {
+    let doc_line = extract_doc_lines(&item.attrs).join("\n");
+
+    if attributes.patterns.contains_key("ffi_error") {
+        panic!("Enum `{}` carries data and can't be used as an `ffi_error`.", item.ident);
+    }
+
+    if !item.generics.params.is_empty() {
+        panic!("Enum `{}` carries data and must not be generic.", item.ident);
+    }
+
+    let repr = repr_idents(&item);
+    let is_repr_c = repr.iter().any(|x| x == "C");
+    let tag = match repr_primitive(&repr) {
+        Some(x) => x,
+        // A plain `#[repr(C)]` stores the tag as a C `int`.
+        None if repr.iter().all(|x| x == "C") && is_repr_c => quote! { ::interoptopus::lang::c::PrimitiveType::I32 },
+        None => panic!(
+            "Enum `{}` carries data and must be `#[repr(C)]`, `#[repr(C, u8)]`, `#[repr(u8)]` or similar.",
+            item.ident
+        ),
+    };
+
+    // Rust only uses a separate tag / union pair if `C` is part of the `repr`, otherwise each variant starts with the tag.
+    let placement = if is_repr_c {
+        quote! { ::interoptopus::lang::c::TagPlacement::Separate }
+    } else {
+        quote! { ::interoptopus::lang::c::TagPlacement::Inline }
+    };
+
+    let name = item.ident.to_string();
+    let ffi_name = attributes.name.clone().unwrap_or_else(|| name.clone());
+    let name_ident = syn::Ident::new(&name, item.ident.span());
+    let namespace = attributes.namespace.clone().unwrap_or_default();
+
+    let mut variants = Vec::new();
+    let mut next_id = 0;
+
+    for variant in &item.variants {
+        let variant_name = variant.ident.to_string();
+        let variant_doc_line = extract_doc_lines(&variant.attrs).join("\n");
+        let this_id = match variant_discriminant(variant, &mut next_id, &repr) {
+            Ok(x) => x,
+            Err(e) => {
+                let error = e.to_compile_error();
+                return quote! { #input #error };
+            }
+        };
+
+        let fields = match &variant.fields {
+            Fields::Named(x) => x.named.iter().collect::<Vec<_>>(),
+            Fields::Unnamed(x) => x.unnamed.iter().collect::<Vec<_>>(),
+            Fields::Unit => Vec::new(),
+        };
+
+        let field_names = fields
+            .iter()
+            .enumerate()
+            .map(|(i, x)| x.ident.as_ref().map(|x| x.to_string()).unwrap_or_else(|| format!("x{}", i)))
+            .collect::<Vec<_>>();
+        let field_docs = fields.iter().map(|x| extract_doc_lines(&x.attrs).join("\n")).collect::<Vec<_>>();
+        let field_types = fields.iter().map(|x| &x.ty).collect::<Vec<_>>();
+
+        variants.push(quote! {
+            {
+                let mut fields: ::std::vec::Vec<::interoptopus::lang::c::Field> = ::std::vec::Vec::new();
+
+                #({
+                    let documentation = ::interoptopus::lang::c::Documentation::from_line(#field_docs);
+                    let the_type = < #field_types as ::interoptopus::lang::rust::CTypeInfo >::type_info();
+                    let field = ::interoptopus::lang::c::Field::with_documentation(#field_names.to_string(), the_type, ::interoptopus::lang::c::Visibility::Public, documentation);
+                    fields.push(field);
+                })*
+
+                let documentation = ::interoptopus::lang::c::Documentation::from_line(#variant_doc_line);
+                variants.push(::interoptopus::lang::c::TaggedVariant::new(#variant_name.to_string(), #this_id, fields, documentation));
+            }
+        });
+    }
+
+    // Variant fields live in nested structs, so only size and alignment are reported.
+    quote! {
+        #input
+
+        impl ::interoptopus::lang::rust::LayoutInfo for #name_ident {
+            fn layout_info() -> ::interoptopus::lang::rust::TypeLayout {
+                let name = <Self as ::interoptopus::lang::rust::CTypeInfo>::type_info().name_within_lib();
+                ::interoptopus::lang::rust::TypeLayout::new(name, ::std::mem::size_of::<Self>(), ::std::mem::align_of::<Self>(), ::std::vec::Vec::new())
+            }
+        }
+
+        unsafe impl ::interoptopus::lang::rust::CTypeInfo for #name_ident {
+            fn type_info() -> ::interoptopus::lang::c::CType {
+                let mut variants = ::std::vec::Vec::new();
+                let documentation = ::interoptopus::lang::c::Documentation::from_line(#doc_line);
+                let meta = ::interoptopus::lang::c::Meta::with_namespace_documentation(#namespace.to_string(), documentation);
+
+                #(#variants)*
+
+                let rval = ::interoptopus::lang::c::TaggedUnionType::new(#ffi_name.to_string(), #tag, #placement, variants, meta);
+                ::interoptopus::lang::c::CType::TaggedUnion(rval)
+            }
+        }
+    }
+}
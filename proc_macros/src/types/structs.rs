@@ -80,6 +80,7 @@ pub fn ffi_type_struct(attributes: &Attributes, input: TokenStream, item: ItemSt
     let surrogates = read_surrogates(&item.attrs);
 
     let mut field_names = Vec::new();
+    let mut field_members = Vec::new();
     let mut field_type_info = Vec::new();
     let mut field_types = Vec::new();
     let mut field_docs = Vec::new();
@@ -147,6 +148,10 @@ pub fn ffi_type_struct(attributes: &Attributes, input: TokenStream, item: ItemSt
         let visibility = attributes.visibility_for_field(field, &name);
 
         field_names.push(name.clone());
+        field_members.push(match &field.ident {
+            Some(ident) => ident.to_token_stream(),
+            None => syn::Index::from(i).to_token_stream(),
+        });
         field_docs.push(extract_doc_lines(&field.attrs).join("\n"));
         field_visibilities.push(visibility);
 
@@ -224,6 +229,18 @@ pub fn ffi_type_struct(attributes: &Attributes, input: TokenStream, item: ItemSt
         }
     };
 
+    let layout_info = if attributes.opaque {
+        quote! {}
+    } else {
+        quote! {
+            impl #param_param ::interoptopus::lang::rust::LayoutInfo for #struct_ident #param_struct #param_where {
+                fn layout_info() -> ::interoptopus::lang::rust::TypeLayout {
+                    ::interoptopus::__layout_info!(#(#field_members as #field_names),*)
+                }
+            }
+        }
+    };
+
     match type_repr {
         TypeRepr::C | TypeRepr::Opaque => {
             quote! {
@@ -244,6 +261,8 @@ pub fn ffi_type_struct(attributes: &Attributes, input: TokenStream, item: ItemSt
                         #rval_builder
                     }
                 }
+
+                #layout_info
             }
         }
         TypeRepr::Transparent => {
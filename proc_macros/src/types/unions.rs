@@ -0,0 +1,107 @@
+use crate::types::Attributes;
+use crate::util::extract_doc_lines;
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use syn::ItemUnion;
+
+// Unions are always `Copy` in Rust, so they tend to be simple; we currently only support
+// non-generic unions with named fields, e.g.,
+//
+// ```
+// #[repr(C)]
+// pub union Bits {
+//     pub float: f32,
+//     pub int: u32,
+// }
+// ```
+//
+pub fn ffi_type_union(attributes: &Attributes, input: TokenStream, item: ItemUnion) -> TokenStream {
+    let namespace = attributes.namespace.clone().unwrap_or_default();
+    let doc_line = extract_doc_lines(&item.attrs).join("\n");
+
+    let union_ident = item.ident.clone();
+    let union_ident_c = attributes.name.clone().unwrap_or_else(|| union_ident.to_string());
+
+    let has_repr_c = item.attrs.iter().any(|x| {
+        let tokens = x.to_token_stream().to_string();
+        tokens.contains("repr") && tokens.contains('C')
+    });
+
+    if !attributes.opaque && !has_repr_c {
+        panic!("Union {} must have `#[repr(C)]` annotation.", union_ident);
+    }
+
+    if !item.generics.params.is_empty() {
+        panic!("Union {} must not be generic, generic unions are not supported.", union_ident);
+    }
+
+    let mut field_names = Vec::new();
+    let mut field_idents = Vec::new();
+    let mut field_types = Vec::new();
+    let mut field_docs = Vec::new();
+    let mut field_visibilities = Vec::new();
+
+    for field in &item.fields.named {
+        let name = field.ident.as_ref().expect("Union fields must be named.").to_string();
+
+        if attributes.skip.contains_key(&name) {
+            continue;
+        }
+
+        field_visibilities.push(attributes.visibility_for_field(field, &name));
+        field_docs.push(extract_doc_lines(&field.attrs).join("\n"));
+        field_types.push(field.ty.to_token_stream());
+        field_idents.push(field.ident.clone());
+        field_names.push(name);
+    }
+
+    let rval_builder = if attributes.opaque {
+        quote! {
+            let rval = ::interoptopus::lang::c::OpaqueType::new(name, meta);
+            ::interoptopus::lang::c::CType::Opaque(rval)
+        }
+    } else {
+        quote! {
+            let mut fields: ::std::vec::Vec<interoptopus::lang::c::Field> = ::std::vec::Vec::new();
+
+            #({
+                let documentation = ::interoptopus::lang::c::Documentation::from_line(#field_docs);
+                let the_type = < #field_types as ::interoptopus::lang::rust::CTypeInfo >::type_info();
+                let field = ::interoptopus::lang::c::Field::with_documentation(#field_names.to_string(), the_type, #field_visibilities, documentation);
+                fields.push(field);
+            })*
+
+            let rval = ::interoptopus::lang::c::UnionType::with_meta(name, fields, meta);
+            ::interoptopus::lang::c::CType::Union(rval)
+        }
+    };
+
+    let layout_info = if attributes.opaque {
+        quote! {}
+    } else {
+        quote! {
+            impl ::interoptopus::lang::rust::LayoutInfo for #union_ident {
+                fn layout_info() -> ::interoptopus::lang::rust::TypeLayout {
+                    ::interoptopus::__layout_info!(#(#field_idents),*)
+                }
+            }
+        }
+    };
+
+    quote! {
+        #input
+
+        #layout_info
+
+        unsafe impl ::interoptopus::lang::rust::CTypeInfo for #union_ident {
+
+            fn type_info() -> ::interoptopus::lang::c::CType {
+                let documentation = ::interoptopus::lang::c::Documentation::from_line(#doc_line);
+                let meta = ::interoptopus::lang::c::Meta::with_namespace_documentation(#namespace.to_string(), documentation);
+                let name = #union_ident_c.to_string();
+
+                #rval_builder
+            }
+        }
+    }
+}
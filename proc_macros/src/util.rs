@@ -1,4 +1,6 @@
 use darling::ToTokens;
+use proc_macro2::TokenStream;
+use quote::quote;
 use syn::punctuated::Punctuated;
 use syn::{Attribute, GenericArgument, Meta, PathArguments, Type};
 
@@ -73,3 +75,16 @@ pub fn purge_lifetimes_from_type(the_type: &Type) -> Type {
 
     rval
 }
+
+/// Records `symbol` in the registry if the `registry` feature is enabled, produces nothing otherwise.
+pub fn registration(symbol: TokenStream) -> TokenStream {
+    if cfg!(feature = "registry") {
+        quote! {
+            ::interoptopus::registry::submit! {
+                ::interoptopus::registry::Registration::new(module_path!(), file!(), line!(), || #symbol)
+            }
+        }
+    } else {
+        quote! {}
+    }
+}
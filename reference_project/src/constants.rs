@@ -1,5 +1,6 @@
 //! Various ways to define constants.
 
+use crate::types::Vec3f32;
 use interoptopus::ffi_constant;
 
 const fn f(x: i32) -> i32 {
@@ -14,3 +15,28 @@ pub const F32_MIN_POSITIVE: f32 = f32::MIN_POSITIVE;
 
 #[ffi_constant]
 pub const COMPUTED_I32: i32 = f(i32::MAX);
+
+#[ffi_constant]
+pub const USIZE: usize = 1 << 20;
+
+/// Only C can express 128-bit constants, and only within the 64-bit range.
+#[ffi_constant]
+pub const U128: u128 = u64::MAX as u128;
+
+/// Exported as its Unicode scalar value.
+#[ffi_constant]
+pub const CHAR: char = 'λ';
+
+/// Exported as a string literal.
+#[ffi_constant]
+pub const VERSION: &str = "1.2.3";
+
+#[ffi_constant]
+pub const ARRAY_U8: [u8; 4] = [1, 2, 3, 4];
+
+#[ffi_constant(namespace = "common", name = "COMMON_U32")]
+pub const NAMESPACED_U32: u32 = 42;
+
+/// Struct constants must be written as struct literals.
+#[ffi_constant]
+pub const VEC3_ONE: Vec3f32 = Vec3f32 { x: 1.0, y: 1.0, z: 1.0 };
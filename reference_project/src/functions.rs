@@ -2,8 +2,9 @@
 
 use crate::patterns::result::{Error, FFIError};
 use crate::types::{
-    ambiguous1, ambiguous2, common, some_foreign_type, Array, Callbacku8u8, EnumDocumented, EnumRenamedXYZ, Generic, Generic2, Generic3, Generic4, Opaque, Phantom,
-    SomeForeignType, StructDocumented, StructRenamedXYZ, Transparent, Tupled, Vec3f32, Visibility1, Visibility2, Weird1, Weird2,
+    ambiguous1, ambiguous2, common, some_foreign_type, Array, Callbacku8u8, EnumDocumented, EnumRenamedXYZ, EnumReprI8, Generic, Generic2, Generic3, Generic4, Opaque,
+    Phantom, SomeForeignType, StructDocumented, StructRenamedXYZ, TaggedInline, TaggedShape, Transparent, Tupled, UnionBits, Vec3f32, Visibility1, Visibility2, Weird1,
+    Weird2,
 };
 use interoptopus::patterns::option::FFIOption;
 use interoptopus::patterns::result::panics_and_errors_to_ffi_enum;
@@ -76,6 +77,38 @@ pub extern "C" fn primitive_i64(x: i64) -> i64 {
     -x
 }
 
+#[ffi_function]
+#[no_mangle]
+pub extern "C" fn primitive_usize(x: usize) -> usize {
+    x.wrapping_mul(2)
+}
+
+#[ffi_function]
+#[no_mangle]
+pub extern "C" fn primitive_isize(x: isize) -> isize {
+    -x
+}
+
+#[ffi_function]
+#[no_mangle]
+pub extern "C" fn primitive_u128(x: u128) -> u128 {
+    x.wrapping_mul(2)
+}
+
+#[ffi_function]
+#[no_mangle]
+pub extern "C" fn primitive_i128(x: i128) -> i128 {
+    -x
+}
+
+/// Callers must pass a valid Unicode scalar value, which is why `rustc` considers `char` not FFI-safe.
+#[ffi_function]
+#[no_mangle]
+#[allow(improper_ctypes_definitions)]
+pub extern "C" fn primitive_char(x: char) -> char {
+    x.to_ascii_uppercase()
+}
+
 #[ffi_function]
 #[no_mangle]
 pub extern "C" fn many_args_5(x0: i64, x1: i64, x2: i64, x3: i64, x4: i64) -> i64 {
@@ -272,6 +305,12 @@ pub extern "C" fn namespaced_inner_slice_mut(x: FFISliceMut<common::Vec>) -> FFI
     x
 }
 
+#[ffi_function(namespace = "common", name = "namespaced_function_renamed")]
+#[no_mangle]
+pub extern "C" fn namespaced_function(x: common::Vec) -> common::Vec {
+    x
+}
+
 #[ffi_function]
 #[no_mangle]
 #[allow(unreachable_code)]
@@ -300,3 +339,39 @@ pub extern "C" fn weird_1(_x: Weird1<u32>, _y: Weird2<u8, 5>) -> bool {
 #[ffi_function]
 #[no_mangle]
 pub extern "C" fn visibility(_x: Visibility1, _y: Visibility2) {}
+
+#[ffi_function]
+#[no_mangle]
+pub extern "C" fn tagged_union_1(x: TaggedShape) -> TaggedShape {
+    match x {
+        TaggedShape::Empty => TaggedShape::Empty,
+        TaggedShape::Circle(r) => TaggedShape::Rect { width: 2.0 * r, height: 2.0 * r },
+        TaggedShape::Rect { width, height } => TaggedShape::Circle(width.max(height) / 2.0),
+    }
+}
+
+#[ffi_function]
+#[no_mangle]
+pub extern "C" fn tagged_union_2(x: &TaggedInline) -> u32 {
+    match x {
+        TaggedInline::A(x) => *x as u32,
+        TaggedInline::B { x } => x.x as u32,
+        TaggedInline::C => 10,
+    }
+}
+
+#[ffi_function]
+#[no_mangle]
+pub extern "C" fn union_1(x: UnionBits) -> u32 {
+    unsafe { x.u }
+}
+
+#[ffi_function]
+#[no_mangle]
+pub extern "C" fn enum_repr_1(x: EnumReprI8) -> EnumReprI8 {
+    match x {
+        EnumReprI8::Negative => EnumReprI8::Positive,
+        EnumReprI8::Zero => EnumReprI8::Zero,
+        EnumReprI8::Positive => EnumReprI8::Negative,
+    }
+}
@@ -7,7 +7,7 @@
 //! Note, many items here are deliberately not documented as testing how and if documentation
 //! is generated is part of the test.
 
-use interoptopus::{constant, extra_type, function, pattern, Inventory, InventoryBuilder};
+use interoptopus::{constant, extra_type, function, merge_inventories, pattern, Inventory, InventoryBuilder};
 
 pub mod constants;
 pub mod functions;
@@ -17,17 +17,21 @@ pub mod patterns {
     pub mod api_guard;
     pub mod ascii_pointer;
     pub mod callbacks;
+    pub mod last_error;
     pub mod option;
     pub mod primitives;
     pub mod result;
     pub mod service;
     pub mod slice;
+    pub mod string;
+    pub mod vec;
 }
 pub mod types;
 
 pub fn ffi_inventory() -> Inventory {
     {
         InventoryBuilder::new()
+            .api_version(1, 0)
             // Functions
             .register(function!(functions::primitive_void))
             .register(function!(functions::primitive_void2))
@@ -40,6 +44,9 @@ pub fn ffi_inventory() -> Inventory {
             .register(function!(functions::primitive_i16))
             .register(function!(functions::primitive_i32))
             .register(function!(functions::primitive_i64))
+            .register(function!(functions::primitive_usize))
+            .register(function!(functions::primitive_isize))
+            .register(function!(functions::primitive_char))
             .register(function!(functions::many_args_5))
             .register(function!(functions::many_args_10))
             .register(function!(functions::ptr))
@@ -68,12 +75,17 @@ pub fn ffi_inventory() -> Inventory {
             .register(function!(functions::namespaced_inner_option))
             .register(function!(functions::namespaced_inner_slice))
             .register(function!(functions::namespaced_inner_slice_mut))
+            .register(function!(functions::namespaced_function))
             .register(function!(functions::panics))
             .register(function!(functions::renamed))
             .register(function!(functions::sleep))
             .register(function!(functions::weird_1))
             .register(function!(functions::visibility))
             .register(function!(functions::repr_transparent))
+            .register(function!(functions::tagged_union_1))
+            .register(function!(functions::tagged_union_2))
+            .register(function!(functions::union_1))
+            .register(function!(functions::enum_repr_1))
             .register(function!(patterns::ascii_pointer::pattern_ascii_pointer_1))
             .register(function!(patterns::ascii_pointer::pattern_ascii_pointer_2))
             .register(function!(patterns::ascii_pointer::pattern_ascii_pointer_len))
@@ -87,8 +99,15 @@ pub fn ffi_inventory() -> Inventory {
             .register(function!(patterns::slice::pattern_ffi_slice_6))
             .register(function!(patterns::slice::pattern_ffi_slice_delegate))
             .register(function!(patterns::slice::pattern_ffi_slice_delegate_huge))
+            .register(function!(patterns::string::pattern_ffi_str_1))
+            .register(function!(patterns::string::pattern_ffi_string_1))
+            .register(function!(patterns::string::pattern_ffi_string_2))
+            .register(function!(patterns::vec::pattern_ffi_vec_1))
+            .register(function!(patterns::vec::pattern_ffi_vec_2))
+            .register(function!(patterns::vec::pattern_ffi_vec_3))
             .register(function!(patterns::option::pattern_ffi_option_1))
             .register(function!(patterns::option::pattern_ffi_option_2))
+            .register(function!(patterns::result::pattern_ffi_result_1))
             .register(function!(patterns::primitives::pattern_ffi_bool))
             .register(function!(patterns::primitives::pattern_ffi_cchar))
             .register(function!(patterns::primitives::pattern_ffi_cchar_const_pointer))
@@ -96,15 +115,37 @@ pub fn ffi_inventory() -> Inventory {
             .register(function!(patterns::api_guard::pattern_api_guard))
             .register(function!(patterns::callbacks::pattern_callback_1))
             .register(function!(patterns::callbacks::pattern_callback_2))
+            .register(function!(patterns::callbacks::pattern_callback_with_data_1))
             // Constants
             .register(constant!(constants::U8))
             .register(constant!(constants::F32_MIN_POSITIVE))
             .register(constant!(constants::COMPUTED_I32))
+            .register(constant!(constants::USIZE))
+            .register(constant!(constants::CHAR))
+            .register(constant!(constants::VERSION))
+            .register(constant!(constants::ARRAY_U8))
+            .register(constant!(constants::NAMESPACED_U32))
+            .register(constant!(constants::VEC3_ONE))
             // Extra Types
             .register(extra_type!(types::ExtraType<f32>))
             // Patterns
             .register(pattern!(patterns::service::SimpleService))
             .register(pattern!(patterns::service::SimpleServiceLifetime))
+            .register(pattern!(patterns::vec::pattern_ffi_vec_u32_destroy))
+            .register(pattern!(patterns::vec::pattern_ffi_vec_vec3_destroy))
+            .register(pattern!(patterns::string::pattern_ffi_string_destroy))
+            .register(pattern!(patterns::last_error::pattern_last_error_message))
             .inventory()
     }
 }
+
+/// The [`ffi_inventory`] plus items using 128-bit integers, which only the C backend supports.
+pub fn ffi_inventory_with_128bit() -> Inventory {
+    let with_128bit = InventoryBuilder::new()
+        .register(function!(functions::primitive_u128))
+        .register(function!(functions::primitive_i128))
+        .register(constant!(constants::U128))
+        .inventory();
+
+    merge_inventories(&[ffi_inventory(), with_128bit])
+}
@@ -6,3 +6,6 @@ use interoptopus::patterns::api_guard::APIVersion;
 pub extern "C" fn pattern_api_guard() -> APIVersion {
     crate::ffi_inventory().into()
 }
+
+// Lets tools read our inventory straight from the compiled library.
+interoptopus::embed_inventory!(crate::ffi_inventory);
@@ -1,8 +1,9 @@
-use interoptopus::{callback, ffi_function};
+use interoptopus::{callback, callback_with_data, ffi_function};
 use std::ffi::c_void;
 
 callback!(MyCallback(value: u32) -> u32);
 callback!(MyCallbackVoid(ptr: *const c_void));
+callback_with_data!(MyCallbackData(value: u32) -> u32);
 
 #[ffi_function]
 #[no_mangle]
@@ -15,3 +16,9 @@ pub extern "C" fn pattern_callback_1(callback: MyCallback, x: u32) -> u32 {
 pub extern "C" fn pattern_callback_2(callback: MyCallbackVoid) -> MyCallbackVoid {
     callback
 }
+
+#[ffi_function]
+#[no_mangle]
+pub extern "C" fn pattern_callback_with_data_1(callback: MyCallbackData, x: u32) -> u32 {
+    callback.call(x)
+}
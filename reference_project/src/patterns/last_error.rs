@@ -0,0 +1,3 @@
+use interoptopus::ffi_last_error;
+
+ffi_last_error!(pattern_last_error_message);
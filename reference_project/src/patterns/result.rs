@@ -1,4 +1,5 @@
-use interoptopus::ffi_type;
+use interoptopus::patterns::result::{panics_and_errors_to_ffi_result, FFIResult};
+use interoptopus::{ffi_function, ffi_type, here};
 use std::fmt::{Display, Formatter};
 
 // This file may look complex but the Interoptopus parts are actually really simple,
@@ -56,3 +57,9 @@ impl Display for Error {
 
 // Tell Rust your error type is an actual Rust Error.
 impl std::error::Error for Error {}
+
+#[ffi_function]
+#[no_mangle]
+pub extern "C" fn pattern_ffi_result_1(x: u32) -> FFIResult<u32, FFIError> {
+    panics_and_errors_to_ffi_result(|| x.checked_mul(2).ok_or(Error::Bad), here!())
+}
@@ -2,7 +2,7 @@ use crate::patterns::callbacks::MyCallback;
 use crate::patterns::result::{Error, FFIError};
 use interoptopus::patterns::primitives::FFIBool;
 use interoptopus::patterns::slice::{FFISlice, FFISliceMut};
-use interoptopus::patterns::string::AsciiPointer;
+use interoptopus::patterns::string::{AsciiPointer, FFIStr, FFIString};
 use interoptopus::{ffi_service, ffi_service_ctor, ffi_service_ignore, ffi_service_method, ffi_type};
 use std::ffi::CString;
 
@@ -57,6 +57,11 @@ impl SimpleService {
         Ok(())
     }
 
+    /// Methods returning a Result<T, _> become an `FFIResult<T, FFIError>`.
+    pub fn method_result_value(&self, x: u32) -> Result<u32, Error> {
+        self.some_value.checked_add(x).ok_or(Error::Bad)
+    }
+
     #[ffi_service_method(on_panic = "return_default")]
     pub fn method_value(&self, x: u32) -> u32 {
         x
@@ -123,6 +128,13 @@ impl SimpleService {
         AsciiPointer::from_cstr(&self.c_string)
     }
 
+    /// Returns an owned copy of this service's string, followed by `suffix`.
+    #[ffi_service_method(on_panic = "return_default")]
+    pub fn return_string_utf8(&mut self, suffix: FFIStr) -> FFIString {
+        let prefix = self.c_string.to_string_lossy();
+        FFIString::from(format!("{}{}", prefix, suffix.as_str().unwrap_or_default()))
+    }
+
     pub fn method_void_ffi_error(&mut self) -> Result<(), Error> {
         Ok(())
     }
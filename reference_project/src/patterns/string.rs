@@ -0,0 +1,22 @@
+use interoptopus::patterns::string::{FFIStr, FFIString};
+use interoptopus::{ffi_function, ffi_string};
+
+ffi_string!(pattern_ffi_string_destroy);
+
+#[ffi_function]
+#[no_mangle]
+pub extern "C" fn pattern_ffi_str_1(x: FFIStr) -> u32 {
+    x.as_str().map(|x| x.chars().count()).unwrap_or(0) as u32
+}
+
+#[ffi_function]
+#[no_mangle]
+pub extern "C" fn pattern_ffi_string_1(x: FFIStr) -> FFIString {
+    FFIString::from(x.as_str().unwrap_or_default().to_uppercase())
+}
+
+#[ffi_function]
+#[no_mangle]
+pub extern "C" fn pattern_ffi_string_2() -> FFIString {
+    FFIString::from("hello\0wörld")
+}
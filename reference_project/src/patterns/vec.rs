@@ -0,0 +1,24 @@
+use crate::types::Vec3f32;
+use interoptopus::patterns::vec::FFIVec;
+use interoptopus::{ffi_function, ffi_vec};
+
+ffi_vec!(pattern_ffi_vec_u32_destroy, u32);
+ffi_vec!(pattern_ffi_vec_vec3_destroy, Vec3f32);
+
+#[ffi_function]
+#[no_mangle]
+pub extern "C" fn pattern_ffi_vec_1(len: u32) -> FFIVec<u32> {
+    FFIVec::from_vec((0..len).collect())
+}
+
+#[ffi_function]
+#[no_mangle]
+pub extern "C" fn pattern_ffi_vec_2(x: f32, len: u32) -> FFIVec<Vec3f32> {
+    FFIVec::from_vec((0..len).map(|_| Vec3f32 { x, y: x, z: x }).collect())
+}
+
+#[ffi_function]
+#[no_mangle]
+pub extern "C" fn pattern_ffi_vec_3(vec: FFIVec<u32>) -> u32 {
+    vec.iter().sum()
+}
@@ -150,6 +150,15 @@ pub enum EnumRenamedXYZ {
     X,
 }
 
+/// Enum stored as a single signed byte.
+#[ffi_type]
+#[repr(i8)]
+pub enum EnumReprI8 {
+    Negative = -1,
+    Zero,
+    Positive = 5,
+}
+
 /// Documented struct.
 #[ffi_type]
 #[repr(C)]
@@ -213,6 +222,37 @@ pub struct Visibility2 {
     pblc2: u8,
 }
 
+/// Enum with payloads, stored as a tag followed by a union.
+#[ffi_type]
+#[repr(C, u8)]
+pub enum TaggedShape {
+    /// Has no payload.
+    Empty,
+    Circle(f32),
+    Rect {
+        width: f32,
+        height: f32,
+    },
+}
+
+#[ffi_type]
+#[repr(u32)]
+pub enum TaggedInline {
+    A(u8),
+    B { x: Vec3f32 },
+    C = 10,
+}
+
+/// Union where all fields share the same four bytes.
+#[ffi_type]
+#[repr(C)]
+pub union UnionBits {
+    pub f: f32,
+    pub u: u32,
+    /// The raw bytes, in native byte order.
+    pub bytes: [u8; 4],
+}
+
 // Doesn't need annotations.
 pub type Callbacku8u8 = extern "C" fn(u8) -> u8;
 
@@ -0,0 +1,11 @@
+#[test]
+#[cfg(target_os = "linux")]
+#[cfg_attr(miri, ignore)]
+fn library_exports_all_functions() {
+    use std::env::consts::{DLL_PREFIX, DLL_SUFFIX};
+
+    let deps = std::env::current_exe().unwrap().parent().unwrap().to_path_buf();
+    let library = deps.join(format!("{}interoptopus_reference_project{}", DLL_PREFIX, DLL_SUFFIX));
+
+    interoptopus::testing::exports::assert_exports_match(&interoptopus_reference_project::ffi_inventory_with_128bit(), library);
+}
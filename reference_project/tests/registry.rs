@@ -0,0 +1,50 @@
+use interoptopus::lang::c::{CType, Constant, Function};
+use interoptopus::{extra_type, InventoryBuilder};
+use std::collections::BTreeSet;
+
+fn function_names(functions: &[Function]) -> BTreeSet<String> {
+    functions.iter().map(|x| x.name().to_string()).collect()
+}
+
+fn constant_names(constants: &[Constant]) -> BTreeSet<String> {
+    constants.iter().map(|x| x.name().to_string()).collect()
+}
+
+fn type_names(types: &[CType]) -> BTreeSet<String> {
+    types.iter().map(|x| x.name_within_lib()).collect()
+}
+
+#[test]
+fn registry_finds_all_items() {
+    let manual = interoptopus_reference_project::ffi_inventory_with_128bit();
+    let registered = InventoryBuilder::new()
+        .register(extra_type!(interoptopus_reference_project::types::ExtraType<f32>))
+        .register_module("interoptopus_reference_project")
+        .inventory();
+
+    assert_eq!(function_names(manual.functions()), function_names(registered.functions()));
+    assert_eq!(constant_names(manual.constants()), constant_names(registered.constants()));
+    assert_eq!(manual.patterns().len(), registered.patterns().len());
+    assert!(type_names(manual.ctypes()).is_subset(&type_names(registered.ctypes())));
+}
+
+#[test]
+fn registry_filters_by_module_and_namespace() {
+    let ambiguous1 = InventoryBuilder::new()
+        .register_module("interoptopus_reference_project::types::ambiguous1")
+        .inventory();
+    let common = InventoryBuilder::new()
+        .register_where(|registration, symbol| registration.is_in_module("interoptopus_reference_project") && symbol.namespace() == Some("common"))
+        .inventory();
+
+    assert!(ambiguous1.functions().is_empty());
+    assert_eq!(type_names(ambiguous1.ctypes()), ["Status1", "Vec1", "f32"].iter().map(|x| x.to_string()).collect());
+    assert_eq!(type_names(common.ctypes()), ["Vec", "f64"].iter().map(|x| x.to_string()).collect());
+}
+
+#[test]
+fn registry_finds_types_only_generic_over_lifetimes() {
+    let types = InventoryBuilder::new().register_module("interoptopus_reference_project::types").inventory();
+
+    assert!(type_names(types.ctypes()).contains("UseAsciiStringPattern"));
+}
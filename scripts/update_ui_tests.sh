@@ -1,15 +1,7 @@
 #
-# Publish all crates.
+# Update all `.expected` files of the backend UI tests, review the changes with `git diff` afterwards.
 #
 
 PROJECT_ROOT="$( cd "$(dirname "$0")/.." ; pwd -P )" # this file
 
-cp "$PROJECT_ROOT"/backends/c/tests/output_nodocs/my_header.h "$PROJECT_ROOT"/backends/c/tests/output_nodocs/my_header.h.expected
-cp "$PROJECT_ROOT"/backends/c/tests/output_docs_inline/my_header.h "$PROJECT_ROOT"/backends/c/tests/output_docs_inline/my_header.h.expected
-cp "$PROJECT_ROOT"/backends/cpython/tests/output/reference_project.py "$PROJECT_ROOT"/backends/cpython/tests/output/reference_project.py.expected
-cp "$PROJECT_ROOT"/backends/csharp/tests/output_safe/Interop.cs "$PROJECT_ROOT"/backends/csharp/tests/output_safe/Interop.cs.expected
-cp "$PROJECT_ROOT"/backends/csharp/tests/output_safe/Interop.common.cs "$PROJECT_ROOT"/backends/csharp/tests/output_safe/Interop.common.cs.expected
-cp "$PROJECT_ROOT"/backends/csharp/tests/output_unsafe/Interop.cs "$PROJECT_ROOT"/backends/csharp/tests/output_unsafe/Interop.cs.expected
-cp "$PROJECT_ROOT"/backends/csharp/tests/output_unsafe/Interop.common.cs "$PROJECT_ROOT"/backends/csharp/tests/output_unsafe/Interop.common.cs.expected
-cp "$PROJECT_ROOT"/backends/csharp/tests/output_unity/Assets/Interop.cs "$PROJECT_ROOT"/backends/csharp/tests/output_unity/Assets/Interop.cs.expected
-cp "$PROJECT_ROOT"/backends/csharp/tests/output_unity/Assets/Interop.common.cs "$PROJECT_ROOT"/backends/csharp/tests/output_unity/Assets/Interop.common.cs.expected
+cd "$PROJECT_ROOT" && INTEROPTOPUS_UPDATE_SNAPSHOTS=1 cargo test -p interoptopus_backend_c -p interoptopus_backend_cpython -p interoptopus_backend_csharp -- bindings_match_reference